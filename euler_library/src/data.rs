@@ -0,0 +1,403 @@
+//! Parsers for the data file formats of Project Euler problems.
+//!
+//! Every parser skips blank lines and reports malformed input as a `ParseError` holding the 1-based line
+//! and column of the offending text. Input without any non-blank line is an error at line 1, column 1.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::data;
+//!
+//! let matrix = data::matrix::<u32>("131,673\n201,96\n").unwrap();
+//! assert_eq!(matrix, vec![vec![131, 673], vec![201, 96]]);
+//!
+//! let triangle = data::triangle::<u32>("3\n7 4\n2 4 6\n").unwrap();
+//! assert_eq!(triangle[2], vec![2, 4, 6]);
+//!
+//! let e = data::matrix::<u32>("131,673\n201,x6\n").unwrap_err();
+//! assert_eq!((e.line, e.column), (2, 5));
+//! assert_eq!(e.to_string(), "line 2, column 5: invalid number `x6`");
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use cards::{self, Hand};
+
+/// Malformed input, located by its 1-based line and column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Returns an error at `line` and `column`.
+    pub fn new(line: usize, column: usize, message: &str) -> ParseError {
+        ParseError { line, column, message: message.to_string() }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A sudoku puzzle, 0 for an empty cell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sudoku {
+    /// Header line of the puzzle, e.g. `Grid 01`.
+    pub name: String,
+    pub cells: [[u8; 9]; 9],
+}
+
+/// Returns the non-blank lines of `text` without trailing whitespace, with their line numbers.
+pub fn lines(text: &str) -> Vec<(usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end()))
+        .filter(|&(_, line)| !line.is_empty())
+        .collect()
+}
+
+/// Fails when `text` has no non-blank line, for parsers built on `lines`.
+///
+/// ```
+/// use euler_library::data;
+///
+/// assert!(data::not_empty("XIV\n").is_ok());
+/// assert_eq!(data::not_empty("\n").unwrap_err().to_string(), "line 1, column 1: no data");
+/// ```
+pub fn not_empty(text: &str) -> Result<(), ParseError> {
+    if lines(text).is_empty() {
+        return Err(ParseError::new(1, 1, "no data"));
+    }
+    Ok(())
+}
+
+/// Parses the decimal digits in `text`, ignoring whitespace.
+///
+/// ```
+/// use euler_library::data;
+///
+/// assert_eq!(data::digits("731\n67\n").unwrap(), vec![7, 3, 1, 6, 7]);
+/// assert_eq!(data::digits("731\n6a\n").unwrap_err().to_string(), "line 2, column 2: invalid digit `a`");
+/// assert_eq!(data::digits("").unwrap_err().to_string(), "line 1, column 1: no data");
+/// ```
+pub fn digits(text: &str) -> Result<Vec<u8>, ParseError> {
+    not_empty(text)?;
+    let mut xs = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        for (j, c) in line.chars().enumerate().filter(|&(_, c)| !c.is_whitespace()) {
+            match c.to_digit(10) {
+                Some(d) => xs.push(d as u8),
+                None => return Err(ParseError::new(i + 1, j + 1, &format!("invalid digit `{}`", c))),
+            }
+        }
+    }
+    Ok(xs)
+}
+
+/// Parses one number per line.
+///
+/// ```
+/// use euler_library::data;
+///
+/// assert_eq!(data::numbers::<u64>("37107287533\n46376937677\n").unwrap(), vec![37107287533, 46376937677]);
+/// let e = data::numbers::<u64>("37107287533\n46376 937677\n").unwrap_err();
+/// assert_eq!(e.to_string(), "line 2, column 7: expected 1 number, found 2");
+/// assert_eq!(data::numbers::<u64>("\n  \n").unwrap_err().to_string(), "line 1, column 1: no data");
+/// ```
+pub fn numbers<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    let xss = separated(text, None)?;
+    for (&(i, line), xs) in lines(text).iter().zip(&xss) {
+        check_len(i, line, None, xs.len(), 1)?;
+    }
+    Ok(xss.into_iter().flat_map(|xs| xs).collect())
+}
+
+/// Parses lines of comma separated numbers, of any length.
+///
+/// ```
+/// use euler_library::data;
+///
+/// assert_eq!(data::rows::<u32>("1,2,3\n4\n").unwrap(), vec![vec![1, 2, 3], vec![4]]);
+/// assert_eq!(data::rows::<u32>("1,,3\n").unwrap_err().to_string(), "line 1, column 3: missing number");
+/// assert_eq!(data::rows::<u32>("").unwrap_err().line, 1);
+/// ```
+pub fn rows<T: FromStr>(text: &str) -> Result<Vec<Vec<T>>, ParseError> {
+    separated(text, Some(','))
+}
+
+/// Parses lines of exactly `n` comma separated numbers.
+///
+/// ```
+/// use euler_library::data;
+///
+/// assert_eq!(data::records::<u32>("519432,525806\n", 2).unwrap(), vec![vec![519432, 525806]]);
+/// let e = data::records::<u32>("519432,525806\n632382\n", 2).unwrap_err();
+/// assert_eq!(e.to_string(), "line 2, column 7: expected 2 numbers, found 1");
+/// assert_eq!(data::records::<u32>("", 2).unwrap_err().line, 1);
+/// ```
+pub fn records<T: FromStr>(text: &str, n: usize) -> Result<Vec<Vec<T>>, ParseError> {
+    let xss = separated(text, Some(','))?;
+    for (&(i, line), xs) in lines(text).iter().zip(&xss) {
+        check_len(i, line, Some(','), xs.len(), n)?;
+    }
+    Ok(xss)
+}
+
+/// Parses a square matrix, lines of comma separated numbers.
+///
+/// ```
+/// use euler_library::data;
+///
+/// let e = data::matrix::<u32>("131,673\n201\n").unwrap_err();
+/// assert_eq!(e.to_string(), "line 2, column 4: expected 2 numbers, found 1");
+/// assert_eq!(data::matrix::<u32>("\n").unwrap_err().to_string(), "line 1, column 1: no data");
+/// ```
+pub fn matrix<T: FromStr>(text: &str) -> Result<Vec<Vec<T>>, ParseError> {
+    let xss = separated(text, Some(','))?;
+    square(text, Some(','), &xss)?;
+    Ok(xss)
+}
+
+/// Parses a triangle of whitespace separated numbers, the first line holding one number and every other
+/// line one more than the line above.
+///
+/// ```
+/// use euler_library::data;
+///
+/// let e = data::triangle::<u32>("3\n7 4\n2 4\n").unwrap_err();
+/// assert_eq!(e.to_string(), "line 3, column 4: expected 3 numbers, found 2");
+/// assert_eq!(data::triangle::<u32>("").unwrap_err().to_string(), "line 1, column 1: no data");
+/// ```
+pub fn triangle<T: FromStr>(text: &str) -> Result<Vec<Vec<T>>, ParseError> {
+    let xss = separated(text, None)?;
+    for (k, (&(i, line), xs)) in lines(text).iter().zip(&xss).enumerate() {
+        check_len(i, line, None, xs.len(), k + 1)?;
+    }
+    Ok(xss)
+}
+
+/// Parses a square adjacency matrix of comma separated weights, `-` where two vertices are not connected.
+///
+/// ```
+/// use euler_library::data;
+///
+/// let network = data::network::<u32>("-,16\n16,-\n").unwrap();
+/// assert_eq!(network, vec![vec![None, Some(16)], vec![Some(16), None]]);
+/// assert_eq!(data::network::<u32>("").unwrap_err().line, 1);
+/// ```
+pub fn network<T: FromStr>(text: &str) -> Result<Vec<Vec<Option<T>>>, ParseError> {
+    not_empty(text)?;
+    let xss = lines(text)
+        .into_iter()
+        .map(|(i, line)| {
+            fields(line, Some(','))
+                .into_iter()
+                .map(|field| match field {
+                    (_, "-") => Ok(None),
+                    _ => number(i, field).map(Some),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    square(text, Some(','), &xss)?;
+    Ok(xss)
+}
+
+/// Parses comma separated words of upper case letters in double quotes, e.g. `"MARY","PATRICIA"`.
+///
+/// ```
+/// use euler_library::data;
+///
+/// assert_eq!(data::words("\"MARY\",\"PATRICIA\"").unwrap(), vec!["MARY", "PATRICIA"]);
+/// let e = data::words("\"MARY\",\"Patricia\"").unwrap_err();
+/// assert_eq!(e.to_string(), "line 1, column 10: invalid letter `a`");
+/// assert_eq!(data::words(" \n").unwrap_err().to_string(), "line 1, column 1: no data");
+/// ```
+pub fn words(text: &str) -> Result<Vec<String>, ParseError> {
+    not_empty(text)?;
+    let mut xs = Vec::new();
+    for (i, line) in lines(text) {
+        for (column, field) in fields(line, Some(',')) {
+            if field.len() < 2 || !field.starts_with('"') || !field.ends_with('"') {
+                return Err(ParseError::new(i, column, "expected a word in double quotes"));
+            }
+            let word = &field[1..field.len() - 1];
+            if let Some((j, c)) = word.chars().enumerate().find(|&(_, c)| !c.is_ascii_uppercase()) {
+                return Err(ParseError::new(i, column + 1 + j, &format!("invalid letter `{}`", c)));
+            }
+            xs.push(word.to_string());
+        }
+    }
+    Ok(xs)
+}
+
+/// Parses sudoku puzzles, each a `Grid NN` line followed by nine lines of nine digits.
+///
+/// ```
+/// use euler_library::data;
+///
+/// let text = "Grid 01\n003020600\n900305001\n001806400\n008102900\n700000008\n006708200\n002609500\n\
+///             800203009\n005010300\n";
+/// let sudokus = data::sudokus(text).unwrap();
+/// assert_eq!(sudokus[0].name, "Grid 01");
+/// assert_eq!(sudokus[0].cells[0], [0, 0, 3, 0, 2, 0, 6, 0, 0]);
+///
+/// let e = data::sudokus("Grid 01\n003020600\n").unwrap_err();
+/// assert_eq!(e.to_string(), "line 2, column 10: Grid 01 has 1 rows, expected 9");
+/// assert_eq!(data::sudokus("").unwrap_err().line, 1);
+/// ```
+pub fn sudokus(text: &str) -> Result<Vec<Sudoku>, ParseError> {
+    not_empty(text)?;
+    let mut xs: Vec<Sudoku> = Vec::new();
+    let mut rows = 9;
+    let mut last = (0, "");
+    for (i, line) in lines(text) {
+        let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("Grid") {
+            incomplete_grid(&xs, rows, last)?;
+            xs.push(Sudoku { name: trimmed.to_string(), cells: [[0; 9]; 9] });
+            rows = 0;
+        } else if rows == 9 {
+            return Err(ParseError::new(i, indent + 1, "expected a `Grid NN` line"));
+        } else {
+            let n = trimmed.chars().count();
+            if n != 9 {
+                let message = format!("expected 9 digits, found {}", n);
+                return Err(ParseError::new(i, indent + n.min(9) + 1, &message));
+            }
+            let sudoku = xs.last_mut().unwrap();
+            for (j, c) in trimmed.chars().enumerate() {
+                match c.to_digit(10) {
+                    Some(d) => sudoku.cells[rows][j] = d as u8,
+                    None => return Err(ParseError::new(i, indent + j + 1, &format!("invalid digit `{}`", c))),
+                }
+            }
+            rows += 1;
+        }
+        last = (i, line);
+    }
+    incomplete_grid(&xs, rows, last)?;
+    Ok(xs)
+}
+
+/// Parses poker hands, lines of ten cards such as `8C TS KC 9H 4S 7D 2S 5D 3S AC`: a value of `2`-`9`,
+/// `T`, `J`, `Q`, `K` or `A` followed by a suit of `C`, `D`, `H` or `S`. Returns the hands of both players.
+///
+/// ```
+/// use euler_library::data;
+///
+/// let hands = data::hands("5H 5C 6S 7S KD 2C 3S 8S 8D TD\n").unwrap();
+/// assert!(hands[0].0.get_rank() < hands[0].1.get_rank());
+/// let e = data::hands("5H 5C 6S 7S KD 2C 3S 8S 8D 1D\n").unwrap_err();
+/// assert_eq!(e.to_string(), "line 1, column 28: invalid card `1D`");
+/// assert_eq!(data::hands("").unwrap_err().line, 1);
+/// ```
+pub fn hands(text: &str) -> Result<Vec<(Hand, Hand)>, ParseError> {
+    not_empty(text)?;
+    let mut xs = Vec::new();
+    for (i, line) in lines(text) {
+        let cards = fields(line, None);
+        check_len(i, line, None, cards.len(), 10)?;
+        let mut cs = Vec::new();
+        for (column, card) in cards {
+            match card.chars().collect::<Vec<_>>()[..] {
+                [val, suit] if "23456789TJQKA".contains(val) && "CDHS".contains(suit) => {
+                    cs.push(val);
+                    cs.push(suit);
+                }
+                _ => return Err(ParseError::new(i, column, &format!("invalid card `{}`", card))),
+            }
+        }
+        let second = cs.split_off(10);
+        xs.push((cards::get_hand(5, cs), cards::get_hand(5, second)));
+    }
+    Ok(xs)
+}
+
+// Parses lines of numbers separated by `sep`, or by whitespace when `None`
+fn separated<T: FromStr>(text: &str, sep: Option<char>) -> Result<Vec<Vec<T>>, ParseError> {
+    not_empty(text)?;
+    lines(text)
+        .into_iter()
+        .map(|(i, line)| fields(line, sep).into_iter().map(|field| number(i, field)).collect())
+        .collect()
+}
+
+// Splits a line at `sep`, or at whitespace when `None`, into trimmed fields and the columns they start at
+fn fields(line: &str, sep: Option<char>) -> Vec<(usize, &str)> {
+    let column = |offset: usize| line[..offset].chars().count() + 1;
+    let mut xs = Vec::new();
+    match sep {
+        Some(sep) => {
+            let mut offset = 0;
+            for field in line.split(sep) {
+                xs.push((column(offset + field.len() - field.trim_start().len()), field.trim()));
+                offset += field.len() + sep.len_utf8();
+            }
+        }
+        None => {
+            let mut start = None;
+            for (i, c) in line.char_indices().chain(Some((line.len(), ' '))) {
+                match (start, c.is_whitespace()) {
+                    (None, false) => start = Some(i),
+                    (Some(s), true) => {
+                        xs.push((column(s), &line[s..i]));
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    xs
+}
+
+fn number<T: FromStr>(line: usize, (column, field): (usize, &str)) -> Result<T, ParseError> {
+    if field.is_empty() {
+        return Err(ParseError::new(line, column, "missing number"));
+    }
+    field.parse().map_err(|_| ParseError::new(line, column, &format!("invalid number `{}`", field)))
+}
+
+// Fails unless a line has `expected` fields, pointing at the first extra field or the end of the line
+fn check_len(i: usize, line: &str, sep: Option<char>, found: usize, expected: usize) -> Result<(), ParseError> {
+    if found == expected {
+        return Ok(());
+    }
+    let column = match fields(line, sep).get(expected) {
+        Some(&(column, _)) => column,
+        None => line.chars().count() + 1,
+    };
+    let message = format!("expected {} number{}, found {}", expected, if expected == 1 { "" } else { "s" }, found);
+    Err(ParseError::new(i, column, &message))
+}
+
+// Fails unless the rows parsed from `text` form a square matrix
+fn square<T>(text: &str, sep: Option<char>, xss: &[Vec<T>]) -> Result<(), ParseError> {
+    let lines = lines(text);
+    for (&(i, line), xs) in lines.iter().zip(xss) {
+        check_len(i, line, sep, xs.len(), xss.len())?;
+    }
+    Ok(())
+}
+
+// Fails when the last sudoku has fewer than nine rows; `last` is the line read before
+fn incomplete_grid(xs: &[Sudoku], rows: usize, last: (usize, &str)) -> Result<(), ParseError> {
+    match xs.last() {
+        Some(sudoku) if rows < 9 => {
+            let message = format!("{} has {} rows, expected 9", sudoku.name, rows);
+            Err(ParseError::new(last.0, last.1.chars().count() + 1, &message))
+        }
+        _ => Ok(()),
+    }
+}
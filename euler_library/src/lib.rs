@@ -20,4 +20,7 @@
 pub mod big;
 pub mod cards;
 pub mod common;
+pub mod data;
+pub mod memory;
 pub mod primes;
+pub mod solution;
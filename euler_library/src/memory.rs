@@ -0,0 +1,139 @@
+//! Heap usage of solutions, counted by an instrumented global allocator.
+//!
+//! `CountingAllocator` forwards to the system allocator and keeps, per thread, the bytes currently
+//! allocated, their peak and the total ever allocated. A program installs it with `#[global_allocator]`;
+//! `Solution::run` then reports the `Usage` of every solution. Memory freed by another thread than the one
+//! that allocated it is not attributed correctly, which is fine for solutions running on a single thread.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_library;
+//!
+//! use euler_library::memory::{self, CountingAllocator};
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//!
+//! fn main() {
+//!     let (xs, usage) = memory::measure(|| vec![0u64; 1000]);
+//!     assert_eq!(xs.len(), 1000);
+//!     let usage = usage.unwrap();
+//!     assert!(usage.peak >= 8000);
+//!     assert!(usage.allocated >= usage.peak);
+//! }
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Global allocator counting the heap usage of every thread.
+pub struct CountingAllocator;
+
+/// Heap usage of a piece of code, in bytes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Usage {
+    /// Most bytes allocated at the same time, beyond those allocated before.
+    pub peak: usize,
+    /// Total bytes allocated, a reallocation counting as an allocation of its new size.
+    pub allocated: usize,
+}
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static CURRENT: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+    static TOTAL: Cell<usize> = const { Cell::new(0) };
+}
+
+// The counters hold no destructors, but may already be gone while a thread shuts down
+fn grow(size: usize) {
+    // a load first keeps allocations from writing the shared flag once it is set
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+    let _ = CURRENT.try_with(|current| {
+        current.set(current.get() + size);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+    });
+    let _ = TOTAL.try_with(|total| total.set(total.get() + size));
+}
+
+fn shrink(size: usize) {
+    let _ = CURRENT.try_with(|current| current.set(current.get().saturating_sub(size)));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            shrink(layout.size());
+            grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Returns true when `CountingAllocator` is the global allocator of the program.
+pub fn is_installed() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f` and returns its result with its heap usage on the calling thread, `None` unless
+/// `CountingAllocator` is installed.
+pub fn measure<F: FnOnce() -> R, R>(f: F) -> (R, Option<Usage>) {
+    let (base, total) = (CURRENT.with(Cell::get), TOTAL.with(Cell::get));
+    PEAK.with(|peak| peak.set(base));
+    let res = f();
+    let usage = Usage {
+        peak: PEAK.with(Cell::get).saturating_sub(base),
+        allocated: TOTAL.with(Cell::get) - total,
+    };
+    (res, if is_installed() { Some(usage) } else { None })
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 MiB`.
+///
+/// ```
+/// use euler_library::memory::format_bytes;
+///
+/// assert_eq!(format_bytes(512), "512 B");
+/// assert_eq!(format_bytes(1536), "1.5 KiB");
+/// assert_eq!(format_bytes(800_000_000), "762.9 MiB");
+/// ```
+pub fn format_bytes(bytes: usize) -> String {
+    let units = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < units.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} B", bytes),
+        _ => format!("{:.1} {}", value, units[unit]),
+    }
+}
@@ -0,0 +1,1183 @@
+//! Types describing a Project Euler solution and the result of running it.
+//!
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::solution::{Answer, Solution, Status};
+//!
+//! fn p001() -> Answer {
+//!     Answer::from((0..1000).filter(|x| x % 3 == 0 || x % 5 == 0).sum::<usize>())
+//! }
+//!
+//! let res = Solution::new("my_crate", 1, "Multiples of 3 and 5", p001).run();
+//! assert_eq!(res.answer, Some(Answer::Integer(233168)));
+//! assert_eq!(res.status, Status::Ok);
+//! assert_eq!(res.legacy(), "p001 = 233168");
+//!
+//! fn p002() -> Answer {
+//!     assert_eq!(1 + 1, 3, "wrong answer");
+//!     Answer::from(0)
+//! }
+//!
+//! let res = Solution::new("my_crate", 2, "Even Fibonacci numbers", p002).run();
+//! assert_eq!(res.answer, None);
+//! match res.status {
+//!     Status::Failed { ref message, ref location } => {
+//!         assert!(message.contains("wrong answer"));
+//!         assert!(location.is_some());
+//!     }
+//!     _ => panic!("expected a failure"),
+//! }
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::RandomState;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Read};
+use std::panic;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use data::ParseError;
+use memory::{self, Usage};
+
+/// Typed answer returned by a solution. Integers beyond the range of an `i64` are kept as text.
+///
+/// ```
+/// use euler_library::solution::Answer;
+///
+/// assert_eq!(Answer::from(1366u64), Answer::Integer(1366));
+/// assert_eq!(Answer::from(u64::MAX), Answer::Text("18446744073709551615".to_string()));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::Text(ref s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Answer {
+                Answer::Integer(i64::from(n))
+            }
+        })*
+    }
+}
+
+answer_from_integer!(u8, u16, u32, i8, i16, i32, i64);
+
+// Integers not always fitting an `i64` keep their exact value as text when they do not
+macro_rules! answer_from_wide_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Answer {
+                i64::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Integer)
+            }
+        })*
+    }
+}
+
+answer_from_wide_integer!(u64, usize, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl<'a> From<&'a str> for Answer {
+    fn from(s: &'a str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl Answer {
+    /// Returns the answer written as the decimal digits `s`, e.g. a concatenation of numbers: an integer
+    /// when it fits an `i64` and has no leading zero to lose, text otherwise.
+    ///
+    /// ```
+    /// use euler_library::solution::Answer;
+    ///
+    /// assert_eq!(Answer::from_digits("6531031914842725"), Answer::Integer(6531031914842725));
+    /// assert_eq!(Answer::from_digits("0015"), Answer::Text("0015".to_string()));
+    /// assert_eq!(Answer::from_digits("99999999999999999999"), Answer::Text("99999999999999999999".to_string()));
+    /// ```
+    pub fn from_digits(s: &str) -> Answer {
+        match s.parse::<i64>() {
+            Ok(n) if !s.starts_with('0') || s == "0" => Answer::Integer(n),
+            _ => Answer::from(s),
+        }
+    }
+
+    /// Parses an answer in the string form solutions returned before `Answer` existed,
+    /// e.g. `"p016 = 1366"`, into its problem number and typed answer.
+    ///
+    /// ```
+    /// use euler_library::solution::Answer;
+    ///
+    /// assert_eq!(Answer::from_legacy("p016 = 1366"), Some((16, Answer::Integer(1366))));
+    /// assert_eq!(Answer::from_legacy("p084 = 101524"), Some((84, Answer::Integer(101524))));
+    /// assert_eq!(Answer::from_legacy("1366"), None);
+    /// ```
+    pub fn from_legacy(s: &str) -> Option<(u32, Answer)> {
+        let mut parts = s.splitn(2, " = ");
+        let id = parts.next()?;
+        let value = parts.next()?.trim().trim_matches('"');
+        if !id.starts_with('p') {
+            return None;
+        }
+        let (problem, _) = parse_id(id)?;
+        Some((problem, Answer::from_digits(value)))
+    }
+}
+
+/// Outcome of running a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
+    Ok,
+    /// The solution panicked, e.g. on a failed `assert_eq!` in its examples, or could not parse its data.
+    Failed {
+        message: String,
+        /// `file:line:column` of the panic, or of the malformed data.
+        location: Option<String>,
+    },
+    /// The solution was still running when its time limit, in fractional seconds, ran out.
+    TimedOut(f64),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Status::Ok => f.pad("ok"),
+            Status::Failed { .. } => f.pad("failed"),
+            Status::TimedOut(_) => f.pad("timed out"),
+        }
+    }
+}
+
+impl Status {
+    /// Returns true unless the solution failed or timed out.
+    pub fn is_ok(&self) -> bool {
+        *self == Status::Ok
+    }
+}
+
+/// What running a `Solution` does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Task {
+    /// Compute the full-size answer.
+    Answer,
+    /// Check the small examples given in the problem statement; panics when one fails.
+    Examples,
+}
+
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Task::Answer => f.pad("answer"),
+            Task::Examples => f.pad("examples"),
+        }
+    }
+}
+
+/// How a solution stands towards its problem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Implementation {
+    /// The main solution of its problem, computing its answer.
+    Solved,
+    /// A placeholder for a problem not solved yet; the runner skips it.
+    Unimplemented,
+    /// Another way to compute the answer of the main solution with the given id, e.g. `p070`.
+    Variant(String),
+}
+
+impl fmt::Display for Implementation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Implementation::Solved => f.pad("solved"),
+            Implementation::Unimplemented => f.pad("unimplemented"),
+            Implementation::Variant(ref of) => f.pad(&format!("variant of {}", of)),
+        }
+    }
+}
+
+/// A numeric input of a solution, such as a search limit, with the value the Euler problem uses.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: u64,
+    pub help: &'static str,
+    /// Smallest value the solution handles, checked by `Solution::with_args`.
+    pub min: u64,
+    /// Largest value the solution handles, checked by `Solution::with_args`.
+    pub max: u64,
+}
+
+impl Param {
+    /// Returns a parameter `name` defaulting to `default`, taking any value.
+    pub fn new(name: &'static str, default: u64, help: &'static str) -> Param {
+        Param { name, default, help, min: 0, max: u64::MAX }
+    }
+
+    /// Restricts the parameter to values of at least `min`.
+    pub fn at_least(mut self, min: u64) -> Param {
+        self.min = min;
+        self
+    }
+
+    /// Restricts the parameter to values of at most `max`.
+    pub fn at_most(mut self, max: u64) -> Param {
+        self.max = max;
+        self
+    }
+}
+
+/// Parses a parameter value such as `1000`, `1_500_000` or `10^8`.
+///
+/// ```
+/// use euler_library::solution::parse_param_value;
+///
+/// assert_eq!(parse_param_value("1_500_000"), Ok(1_500_000));
+/// assert_eq!(parse_param_value("10^8"), Ok(100_000_000));
+/// assert!(parse_param_value("10^20").is_err());
+/// assert!(parse_param_value("ten").is_err());
+/// ```
+pub fn parse_param_value(s: &str) -> Result<u64, String> {
+    let number = |x: &str| x.trim().replace('_', "").parse::<u64>().map_err(|_| format!("invalid number: {}", s));
+    match s.find('^') {
+        Some(pos) => {
+            let (base, exp) = (number(&s[..pos])?, number(&s[pos + 1..])?);
+            if exp > u64::from(u32::MAX) {
+                return Err(format!("number too large: {}", s));
+            }
+            base.checked_pow(exp as u32).ok_or(format!("number too large: {}", s))
+        }
+        None => number(s),
+    }
+}
+
+/// Text a solution reads instead of its embedded data file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    /// Path of the file the text was read from, `-` for standard input.
+    pub source: String,
+    pub text: Arc<String>,
+}
+
+impl Input {
+    /// Returns an input holding `text`, read from `source`.
+    pub fn new(source: &str, text: &str) -> Input {
+        Input { source: source.to_string(), text: Arc::new(text.to_string()) }
+    }
+
+    /// Reads the file at `source`, or standard input when `source` is `-`.
+    pub fn read(source: &str) -> Result<Input, String> {
+        let mut text = String::new();
+        let read = match source {
+            "-" => io::stdin().read_to_string(&mut text).map(|_| ()),
+            _ => fs::File::open(source).and_then(|mut f| f.read_to_string(&mut text)).map(|_| ()),
+        };
+        match read {
+            Ok(()) => Ok(Input { source: source.to_string(), text: Arc::new(text) }),
+            Err(e) if source == "-" => Err(format!("cannot read data from standard input: {}", e)),
+            Err(e) => Err(format!("cannot read data {}: {}", source, e)),
+        }
+    }
+}
+
+/// A solution to one Project Euler problem.
+///
+/// A problem may have several solutions; all but the main one are told apart by a `variant` suffix.
+#[derive(Clone)]
+pub struct Solution {
+    pub problem: u32,
+    pub title: &'static str,
+    /// Name of the crate the solution lives in.
+    pub crate_name: &'static str,
+    pub variant: Option<&'static str>,
+    pub func: fn() -> Answer,
+    /// Checks of the examples in the problem statement, if any.
+    pub examples: Option<fn()>,
+    /// What `run` executes, `Task::Answer` unless returned by `examples_task`.
+    pub task: Task,
+    /// Inputs the answer can be computed for instead of the Euler problem's, empty for solutions that read
+    /// data or whose problem has no size, and for problems 51, 68 and 103 until their searches are generalised.
+    pub params: Vec<Param>,
+    /// Computes the answer from the values of `params`, in order.
+    pub func_with: Option<fn(&[u64]) -> Answer>,
+    /// Parameter values replacing the defaults, in order of `params`.
+    pub args: Vec<(&'static str, u64)>,
+    /// Name of the data file embedded in the solution, if it reads one.
+    pub data: Option<&'static str>,
+    /// Computes the answer from the text of a data file in the format of `data`, failing on malformed text.
+    pub func_from: Option<fn(&str) -> Result<Answer, ParseError>>,
+    /// Text read instead of the embedded data file.
+    pub input: Option<Input>,
+    pub implementation: Implementation,
+    /// Computes the answer from random numbers drawn from a generator seeded with its argument.
+    pub func_seeded: Option<fn(u64) -> Answer>,
+    /// Seed of the random number generator, drawn anew on every run unless set by `with_seed`.
+    pub seed: Option<u64>,
+}
+
+impl fmt::Debug for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Solution({}, {:?}, {}, {})", self.id(), self.title, self.crate_name, self.task)
+    }
+}
+
+impl Solution {
+    /// Returns a new `Solution` for `problem` computed by `func`.
+    pub fn new(crate_name: &'static str, problem: u32, title: &'static str, func: fn() -> Answer) -> Solution {
+        Solution {
+            problem,
+            title,
+            crate_name,
+            variant: None,
+            func,
+            examples: None,
+            task: Task::Answer,
+            params: Vec::new(),
+            func_with: None,
+            args: Vec::new(),
+            data: None,
+            func_from: None,
+            input: None,
+            implementation: Implementation::Solved,
+            func_seeded: None,
+            seed: None,
+        }
+    }
+
+    /// Declares the parameters of the solution; `func_with` computes the answer from their values.
+    ///
+    /// ```
+    /// use euler_library::solution::{Answer, Param, Solution};
+    ///
+    /// fn p001_with(limit: u64) -> Answer {
+    ///     Answer::from((0..limit).filter(|x| x % 3 == 0 || x % 5 == 0).sum::<u64>())
+    /// }
+    ///
+    /// fn p001() -> Answer {
+    ///     p001_with(1000)
+    /// }
+    ///
+    /// let solution = Solution::new("my_crate", 1, "Multiples of 3 and 5", p001)
+    ///     .params(vec![Param::new("limit", 1000, "sum the multiples below limit")], |v| p001_with(v[0]));
+    /// let res = solution.with_args(&[("limit".to_string(), 10)]).unwrap().run();
+    /// assert_eq!(res.answer, Some(Answer::from(23)));
+    /// assert_eq!(res.legacy(), "p001 [limit=10] = 23");
+    ///
+    /// assert!(solution.with_args(&[("max".to_string(), 10)]).is_err());
+    ///
+    /// fn p007_with(n: u64) -> Answer {
+    ///     Answer::from((2..).filter(|&k| (2..k).all(|d| k % d != 0)).nth(n as usize - 1).unwrap())
+    /// }
+    ///
+    /// let solution = Solution::new("my_crate", 7, "10001st prime", || p007_with(10001))
+    ///     .params(vec![Param::new("n", 10001, "find the nth prime").at_least(1)], |v| p007_with(v[0]));
+    /// let e = solution.with_args(&[("n".to_string(), 0)]).unwrap_err();
+    /// assert_eq!(e, "p007 parameter n must be at least 1");
+    /// ```
+    pub fn params(mut self, params: Vec<Param>, func_with: fn(&[u64]) -> Answer) -> Solution {
+        self.params = params;
+        self.func_with = Some(func_with);
+        self
+    }
+
+    /// Returns the solution computing its answer for the parameter values in `args`, failing when the
+    /// solution has no parameter of a given name or a value is out of the parameter's range.
+    pub fn with_args(&self, args: &[(String, u64)]) -> Result<Solution, String> {
+        let mut solution = self.clone();
+        for &(ref name, value) in args {
+            let param = match self.params.iter().find(|p| p.name == *name) {
+                Some(p) => p,
+                None => {
+                    let names = self.params.iter().map(|p| p.name).collect::<Vec<_>>();
+                    return Err(match names.len() {
+                        0 => format!("{} has no parameters", self.id()),
+                        _ => format!("{} has no parameter {} (expected {})", self.id(), name, names.join(", ")),
+                    });
+                }
+            };
+            if value < param.min {
+                return Err(format!("{} parameter {} must be at least {}", self.id(), name, param.min));
+            }
+            if value > param.max {
+                return Err(format!("{} parameter {} must be at most {}", self.id(), name, param.max));
+            }
+            solution.args.retain(|&(n, _)| n != param.name);
+            solution.args.push((param.name, value));
+        }
+        solution.args.sort_by_key(|&(n, _)| self.params.iter().position(|p| p.name == n));
+        Ok(solution)
+    }
+
+    /// Returns the value of every parameter, the default unless set by `with_args`.
+    pub fn param_values(&self) -> Vec<u64> {
+        self.params
+            .iter()
+            .map(|p| self.args.iter().find(|&&(n, _)| n == p.name).map_or(p.default, |&(_, v)| v))
+            .collect()
+    }
+
+    /// Declares the data file `data` embedded in the solution; `func_from` computes the answer from the
+    /// text of any file in the same format. Malformed text fails the solution with the location of the
+    /// error in the file.
+    ///
+    /// ```
+    /// use euler_library::data::{self, ParseError};
+    /// use euler_library::solution::{Answer, Input, Solution, Status};
+    ///
+    /// fn p013_from(text: &str) -> Result<Answer, ParseError> {
+    ///     Ok(Answer::from(data::numbers::<u64>(text)?.into_iter().sum::<u64>()))
+    /// }
+    ///
+    /// fn p013() -> Answer {
+    ///     p013_from("1\n2\n3\n").unwrap()
+    /// }
+    ///
+    /// let solution = Solution::new("my_crate", 13, "Large sum", p013).data("p013_sum.txt", p013_from);
+    /// assert_eq!(solution.run().answer, Some(Answer::from(6)));
+    ///
+    /// let res = solution.with_input(&Input::new("mine.txt", "10\n20\n")).unwrap().run();
+    /// assert_eq!(res.answer, Some(Answer::from(30)));
+    /// assert_eq!(res.legacy(), "p013 [data=mine.txt] = 30");
+    ///
+    /// let res = solution.with_input(&Input::new("mine.txt", "10\n2o\n")).unwrap().run();
+    /// let message = "invalid number `2o`".to_string();
+    /// assert_eq!(res.status, Status::Failed { message, location: Some("mine.txt:2:1".to_string()) });
+    /// ```
+    pub fn data(mut self, data: &'static str, func_from: fn(&str) -> Result<Answer, ParseError>) -> Solution {
+        self.data = Some(data);
+        self.func_from = Some(func_from);
+        self
+    }
+
+    /// Returns the solution computing its answer from `input` instead of its embedded data file, failing
+    /// when it reads no data.
+    pub fn with_input(&self, input: &Input) -> Result<Solution, String> {
+        match self.data {
+            Some(_) => Ok(Solution { input: Some(input.clone()), ..self.clone() }),
+            None => Err(format!("{} reads no data", self.id())),
+        }
+    }
+
+    /// Declares the solution stochastic; `func_seeded` computes the answer from the random numbers of a
+    /// generator seeded with its argument, so that a run can be replayed with the seed of its result.
+    ///
+    /// ```
+    /// use euler_library::solution::{Answer, Solution};
+    ///
+    /// fn p084_seeded(seed: u64) -> Answer {
+    ///     Answer::from(seed % 7)
+    /// }
+    ///
+    /// fn p084() -> Answer {
+    ///     p084_seeded(0)
+    /// }
+    ///
+    /// let solution = Solution::new("my_crate", 84, "Monopoly odds", p084).seeded(p084_seeded);
+    /// let res = solution.run();
+    /// assert_eq!(res.answer, Some(Answer::from(res.seed.unwrap() % 7)));
+    ///
+    /// let res = solution.with_seed(12).unwrap().run();
+    /// assert_eq!(res.answer, Some(Answer::from(5)));
+    /// assert_eq!(res.legacy(), "p084 [seed=12] = 5");
+    /// ```
+    pub fn seeded(mut self, func_seeded: fn(u64) -> Answer) -> Solution {
+        self.func_seeded = Some(func_seeded);
+        self
+    }
+
+    /// Returns the solution drawing its random numbers from a generator seeded with `seed`, failing when
+    /// it draws none.
+    pub fn with_seed(&self, seed: u64) -> Result<Solution, String> {
+        match self.func_seeded {
+            Some(_) => Ok(Solution { seed: Some(seed), ..self.clone() }),
+            None => Err(format!("{} draws no random numbers", self.id())),
+        }
+    }
+
+    /// Adds the checks of the examples in the problem statement.
+    ///
+    /// ```
+    /// use euler_library::solution::{Answer, Solution, Task};
+    ///
+    /// fn sum_below(n: usize) -> usize {
+    ///     (0..n).filter(|x| x % 3 == 0 || x % 5 == 0).sum()
+    /// }
+    ///
+    /// fn examples() {
+    ///     assert_eq!(sum_below(10), 23);
+    /// }
+    ///
+    /// fn solve() -> Answer {
+    ///     Answer::from(sum_below(1000))
+    /// }
+    ///
+    /// let solution = Solution::new("my_crate", 1, "Multiples of 3 and 5", solve).examples(examples);
+    /// let res = solution.examples_task().unwrap().run();
+    /// assert_eq!(res.task, Task::Examples);
+    /// assert!(res.status.is_ok());
+    /// assert_eq!(res.answer, None);
+    /// ```
+    pub fn examples(mut self, examples: fn()) -> Solution {
+        self.examples = Some(examples);
+        self
+    }
+
+    /// Returns the solution set to check its examples instead of computing the answer, `None` when it
+    /// has no examples.
+    pub fn examples_task(&self) -> Option<Solution> {
+        self.examples.map(|_| Solution { task: Task::Examples, ..self.clone() })
+    }
+
+    /// Marks the solution as an alternative implementation of its problem.
+    ///
+    /// ```
+    /// use euler_library::solution::{Answer, Implementation, Solution};
+    ///
+    /// fn p070a() -> Answer {
+    ///     Answer::from(8319823)
+    /// }
+    ///
+    /// let solution = Solution::new("euler_p061_070", 70, "Totient permutation", p070a).variant("a");
+    /// assert_eq!(solution.id(), "p070a");
+    /// assert_eq!(solution.implementation, Implementation::Variant("p070".to_string()));
+    /// ```
+    pub fn variant(mut self, variant: &'static str) -> Solution {
+        self.variant = Some(variant);
+        self.implementation = Implementation::Variant(format_id(self.problem, None));
+        self
+    }
+
+    /// Marks the solution as a placeholder for a problem not solved yet.
+    ///
+    /// ```
+    /// use euler_library::solution::{Answer, Implementation, Solution};
+    ///
+    /// fn p128() -> Answer {
+    ///     Answer::from("unimplemented")
+    /// }
+    ///
+    /// let solution = Solution::new("euler_p121_130", 128, "Hexagonal tile differences", p128).unimplemented();
+    /// assert_eq!(solution.implementation, Implementation::Unimplemented);
+    /// ```
+    pub fn unimplemented(mut self) -> Solution {
+        self.implementation = Implementation::Unimplemented;
+        self
+    }
+
+    /// Returns the problem id in the form `p001`, followed by the variant if any.
+    pub fn id(&self) -> String {
+        format_id(self.problem, self.variant)
+    }
+
+    /// Executes the solution's task and returns its timed result, including its heap usage when
+    /// `memory::CountingAllocator` is installed.
+    ///
+    /// A panic inside the solution is caught and reported as `Status::Failed` instead of unwinding
+    /// into the caller.
+    pub fn run(&self) -> SolutionResult {
+        let seed = match (self.task, self.func_seeded) {
+            (Task::Answer, Some(_)) => Some(self.seed.unwrap_or_else(random_seed)),
+            _ => None,
+        };
+        install_panic_hook();
+        IN_SOLUTION.with(|x| x.set(true));
+        let instant = Instant::now();
+        let (outcome, memory) = memory::measure(|| {
+            match (self.task, self.examples, self.func_with, self.func_from, &self.input) {
+                (Task::Answer, _, _, _, None) if self.args.is_empty() && seed.is_some() => {
+                    let (func_seeded, seed) = (self.func_seeded.unwrap(), seed.unwrap());
+                    panic::catch_unwind(move || func_seeded(seed)).map(|answer| Ok(Some(answer)))
+                }
+                (Task::Examples, Some(examples), _, _, _) => panic::catch_unwind(examples).map(|()| Ok(None)),
+                (Task::Answer, _, _, Some(func_from), Some(input)) => {
+                    let text = input.text.clone();
+                    panic::catch_unwind(move || func_from(&text)).map(|res| res.map(Some))
+                }
+                (Task::Answer, _, Some(func_with), _, _) if !self.args.is_empty() => {
+                    let values = self.param_values();
+                    panic::catch_unwind(move || func_with(&values)).map(|answer| Ok(Some(answer)))
+                }
+                _ => panic::catch_unwind(self.func).map(|answer| Ok(Some(answer))),
+            }
+        });
+        let elapsed = seconds(instant.elapsed());
+        IN_SOLUTION.with(|x| x.set(false));
+
+        let (answer, status) = match outcome {
+            Ok(Ok(answer)) => (answer, Status::Ok),
+            Ok(Err(e)) => {
+                let source = self.input.as_ref().map_or("", |i| &i.source);
+                let location = Some(format!("{}:{}:{}", source, e.line, e.column));
+                (None, Status::Failed { message: e.message, location })
+            }
+            Err(payload) => {
+                let message = match payload.downcast_ref::<&str>() {
+                    Some(s) => s.to_string(),
+                    None => payload.downcast_ref::<String>().cloned().unwrap_or_default(),
+                };
+                let location = PANIC_LOCATION.with(|x| x.borrow_mut().take());
+                (None, Status::Failed { message, location })
+            }
+        };
+        SolutionResult { memory, seed, ..self.result(answer, elapsed, status) }
+    }
+
+    /// Executes the solution on a worker thread and waits at most `timeout` for its result.
+    ///
+    /// A solution still running after `timeout` is reported as `Status::TimedOut`. Threads cannot be
+    /// killed, so its worker is left to finish in the background.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use euler_library::solution::{Answer, Solution, Status};
+    ///
+    /// fn hangs() -> Answer {
+    ///     std::thread::sleep(Duration::from_secs(60));
+    ///     Answer::from(0)
+    /// }
+    ///
+    /// let res = Solution::new("my_crate", 1, "Hangs", hangs).run_with_timeout(Duration::from_millis(50));
+    /// assert_eq!(res.status, Status::TimedOut(0.05));
+    /// assert_eq!(res.answer, None);
+    /// ```
+    pub fn run_with_timeout(&self, timeout: Duration) -> SolutionResult {
+        let solution = self.clone();
+        let (tx, rx) = mpsc::channel();
+        let spawned = thread::Builder::new().name(self.id()).spawn(move || {
+            let _ = tx.send(solution.run());
+        });
+        if let Err(e) = spawned {
+            let message = format!("cannot start worker thread: {}", e);
+            return self.result(None, 0.0, Status::Failed { message, location: None });
+        }
+        match rx.recv_timeout(timeout) {
+            Ok(res) => res,
+            Err(RecvTimeoutError::Timeout) => {
+                let limit = seconds(timeout);
+                self.result(None, limit, Status::TimedOut(limit))
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                let message = "worker thread exited without a result".to_string();
+                self.result(None, 0.0, Status::Failed { message, location: None })
+            }
+        }
+    }
+
+    /// Returns a result of the solution with the given outcome, e.g. one obtained by running it in another
+    /// process.
+    pub fn result(&self, answer: Option<Answer>, elapsed: f64, status: Status) -> SolutionResult {
+        SolutionResult {
+            problem: self.problem,
+            title: self.title,
+            crate_name: self.crate_name,
+            variant: self.variant,
+            task: self.task,
+            args: self.args.clone(),
+            input: self.input.as_ref().map(|i| i.source.clone()),
+            answer,
+            elapsed,
+            memory: None,
+            rss: None,
+            seed: self.seed,
+            status,
+        }
+    }
+}
+
+/// The result of running a `Solution`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolutionResult {
+    pub problem: u32,
+    pub title: &'static str,
+    pub crate_name: &'static str,
+    pub variant: Option<&'static str>,
+    pub task: Task,
+    /// Parameter values the answer was computed for instead of the Euler problem's.
+    pub args: Vec<(&'static str, u64)>,
+    /// Source of the data the answer was computed from instead of the embedded data file.
+    pub input: Option<String>,
+    /// `None` when the solution failed or timed out, or for examples.
+    pub answer: Option<Answer>,
+    /// Execution time in fractional seconds.
+    pub elapsed: f64,
+    /// Heap usage, `None` unless `memory::CountingAllocator` is the global allocator, or when the
+    /// solution timed out. The counters are per thread, so only the allocations of the thread running the
+    /// solution are counted, not those of any worker threads it spawns.
+    pub memory: Option<Usage>,
+    /// Peak resident set size in bytes of the process the solution ran in, only known when that process
+    /// ran nothing else.
+    pub rss: Option<usize>,
+    /// Seed of the random numbers the answer was computed from, for stochastic solutions.
+    pub seed: Option<u64>,
+    pub status: Status,
+}
+
+impl SolutionResult {
+    /// Returns the problem id in the form `p001`, followed by the variant if any.
+    pub fn id(&self) -> String {
+        format_id(self.problem, self.variant)
+    }
+
+    /// Returns true when the answer was computed for other parameters or data than the Euler problem's.
+    pub fn has_custom_input(&self) -> bool {
+        !self.args.is_empty() || self.input.is_some()
+    }
+
+    /// Compatibility shim returning the result in the string form solutions used to return,
+    /// e.g. `"p016 = 1366"`.
+    ///
+    /// Examples read e.g. `"p002 examples = ok"`, answers for other inputs `"p001 [limit=10] = 23"` or
+    /// `"p067 [data=triangle.txt] = 1074"` and those of stochastic solutions `"p084 [seed=42] = 101524"`.
+    pub fn legacy(&self) -> String {
+        let id = match self.task {
+            Task::Answer if self.has_custom_input() || self.seed.is_some() => {
+                let mut args = self.args.iter().map(|&(n, v)| format!("{}={}", n, v)).collect::<Vec<_>>();
+                args.extend(self.input.iter().map(|source| format!("data={}", source)));
+                args.extend(self.seed.iter().map(|seed| format!("seed={}", seed)));
+                format!("{} [{}]", self.id(), args.join(", "))
+            }
+            Task::Answer => self.id(),
+            Task::Examples => format!("{} {}", self.id(), self.task),
+        };
+        match self.answer {
+            Some(ref answer) => format!("{} = {}", id, answer),
+            None => format!("{} = {}", id, self.status),
+        }
+    }
+}
+
+impl fmt::Display for SolutionResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.legacy())
+    }
+}
+
+thread_local! {
+    static IN_SOLUTION: Cell<bool> = Cell::new(false);
+    static PANIC_LOCATION: RefCell<Option<String>> = RefCell::new(None);
+}
+
+static HOOK: Once = Once::new();
+
+// Records where a solution panicked, leaving panics outside of `Solution::run` to the previous hook.
+fn install_panic_hook() {
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IN_SOLUTION.with(|x| x.get()) {
+                let location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+                PANIC_LOCATION.with(|x| *x.borrow_mut() = location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Returns a seed for a random number generator, different on every call.
+pub fn random_seed() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+/// Returns the problem id for `problem` and `variant`, e.g. `p070` or `p070a`.
+pub fn format_id(problem: u32, variant: Option<&str>) -> String {
+    format!("p{:03}{}", problem, variant.unwrap_or(""))
+}
+
+/// Parses a problem id such as `70`, `070`, `p070` or `p070a` into its problem number and variant.
+///
+/// ```
+/// use euler_library::solution::parse_id;
+///
+/// assert_eq!(parse_id("7"), Some((7, None)));
+/// assert_eq!(parse_id("p070a"), Some((70, Some("a".to_string()))));
+/// assert_eq!(parse_id("p"), None);
+/// ```
+pub fn parse_id(id: &str) -> Option<(u32, Option<String>)> {
+    let id = id.trim();
+    let id = if id.starts_with('p') { &id[1..] } else { id };
+    let digits = id.chars().take_while(|c| c.is_ascii_digit()).count();
+    let problem = id[..digits].parse().ok()?;
+    let variant = &id[digits..];
+    if variant.is_empty() {
+        Some((problem, None))
+    } else if variant.chars().all(|c| c.is_ascii_lowercase()) {
+        Some((problem, Some(variant.to_string())))
+    } else {
+        None
+    }
+}
+
+/// Returns a `Duration` in fractional seconds with microsecond precision.
+pub fn seconds(dur: Duration) -> f64 {
+    let micros = dur.as_secs() * 1_000_000 + u64::from(dur.subsec_nanos()) / 1_000;
+    micros as f64 / 1_000_000.0
+}
+
+// Whether `s` is found in `text` at `i`
+const fn at(text: &[u8], i: usize, s: &[u8]) -> bool {
+    if i + s.len() > text.len() {
+        return false;
+    }
+    let mut j = 0;
+    while j < s.len() {
+        if text[i + j] != s[j] {
+            return false;
+        }
+        j += 1;
+    }
+    true
+}
+
+// End of the name of identifier characters, and dashes when `dashes`, starting at `i`
+const fn name_end(text: &[u8], mut i: usize, dashes: bool) -> usize {
+    while i < text.len() && (text[i].is_ascii_alphanumeric() || text[i] == b'_' || (dashes && text[i] == b'-')) {
+        i += 1;
+    }
+    i
+}
+
+// First byte from `i` on that is not white space, or only not a space or tab when `lines` is false
+const fn skip_space(text: &[u8], mut i: usize, lines: bool) -> usize {
+    while i < text.len() && (text[i] == b' ' || text[i] == b'\t' || (lines && text[i].is_ascii_whitespace())) {
+        i += 1;
+    }
+    i
+}
+
+// Whether the name from `start` to `end` is one of `names`
+const fn is_named(text: &[u8], start: usize, end: usize, names: &[&str]) -> bool {
+    let mut k = 0;
+    while k < names.len() {
+        if names[k].len() == end - start && at(text, start, names[k].as_bytes()) {
+            return true;
+        }
+        k += 1;
+    }
+    false
+}
+
+// Whether the name from `start` to `end` is `p`, three digits and an optional variant of lower case letters
+const fn is_solution_name(text: &[u8], start: usize, end: usize) -> bool {
+    if end < start + 4 || text[start] != b'p' {
+        return false;
+    }
+    let mut i = start + 1;
+    while i < end {
+        let ok = if i < start + 4 { text[i].is_ascii_digit() } else { text[i].is_ascii_lowercase() };
+        if !ok {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// Whether a function whose name ends at `end` takes no arguments and returns a path ending in `Answer`
+const fn returns_answer(text: &[u8], end: usize) -> bool {
+    let mut i = skip_space(text, end, true);
+    if !at(text, i, b"(") {
+        return false;
+    }
+    i = skip_space(text, i + 1, true);
+    if !at(text, i, b")") {
+        return false;
+    }
+    i = skip_space(text, i + 1, true);
+    if !at(text, i, b"->") {
+        return false;
+    }
+    i = skip_space(text, i + 2, true);
+    loop {
+        let segment = i;
+        let segment_end = name_end(text, i, false);
+        i = skip_space(text, segment_end, true);
+        if at(text, i, b"::") {
+            i = skip_space(text, i + 2, true);
+        } else {
+            return segment_end - segment == 6 && at(text, segment, b"Answer");
+        }
+    }
+}
+
+/// Returns whether every function of the Rust source `source` shaped like a solution, named `p`, three digits
+/// and an optional variant such as `a`, taking no arguments and returning an `Answer`, is one of `names`.
+/// Callable in constants, where `solutions!` checks with it that every solution function of a crate is
+/// declared.
+///
+/// It scans the text rather than parsing it: whatever their visibility, attributes, layout or path to
+/// `Answer`, the functions written out in `source` are found and those in comments and string literals are
+/// skipped, but functions generated by other macros are not seen.
+///
+/// ```
+/// use euler_library::solution::solutions_named;
+///
+/// let source = "pub fn p001() -> Answer { p001_with(1000) }
+///               pub fn p001_with(n: usize) -> Answer { Answer::from(n) }
+///               #[inline]
+///               pub(crate) fn p002a()
+///                   -> euler_library::solution::Answer { unimplemented!() }
+///               // pub fn p003() -> Answer
+///               const NOTE: &str = \"fn p004() -> Answer\";";
+/// assert!(solutions_named(source, &["p001", "p002a"]));
+/// assert!(!solutions_named(source, &["p001"]));
+/// ```
+///
+/// A solution left undeclared does not compile, as in `solutions!`:
+///
+/// ```compile_fail
+/// use euler_library::solution::solutions_named;
+///
+/// const _: () = assert!(solutions_named("pub fn p001() -> Answer {}\npub fn p001a() -> Answer {}", &["p001"]));
+///
+/// fn main() {}
+/// ```
+pub const fn solutions_named(source: &str, names: &[&str]) -> bool {
+    let text = source.as_bytes();
+    let mut i = 0;
+    while i < text.len() {
+        if at(text, i, b"//") {
+            while i < text.len() && text[i] != b'\n' {
+                i += 1;
+            }
+        } else if at(text, i, b"/*") {
+            i += 2;
+            while i < text.len() && !at(text, i, b"*/") {
+                i += 1;
+            }
+            i += 2;
+        } else if text[i] == b'"' {
+            i += 1;
+            while i < text.len() && text[i] != b'"' {
+                i += if text[i] == b'\\' { 2 } else { 1 };
+            }
+            i += 1;
+        } else if text[i] == b'r' && (at(text, i + 1, b"\"") || at(text, i + 1, b"#\"") || at(text, i + 1, b"##\"")) {
+            // a raw string, ending in a quote and as many hashes as it starts with
+            let mut hashes = 0;
+            while text[i + 1 + hashes] == b'#' {
+                hashes += 1;
+            }
+            i += hashes + 2;
+            loop {
+                if i >= text.len() {
+                    break;
+                }
+                let mut h = 0;
+                while h < hashes && at(text, i + 1 + h, b"#") {
+                    h += 1;
+                }
+                if text[i] == b'"' && h == hashes {
+                    i += hashes + 1;
+                    break;
+                }
+                i += 1;
+            }
+        } else if text[i] == b'\'' {
+            // a character literal, or a lifetime left to the names
+            if at(text, i + 1, b"\\") {
+                i += 2;
+                while i < text.len() && text[i] != b'\'' {
+                    i += 1;
+                }
+                i += 1;
+            } else if at(text, i + 2, b"'") {
+                i += 3;
+            } else {
+                i += 1;
+            }
+        } else if text[i].is_ascii_alphanumeric() || text[i] == b'_' {
+            let end = name_end(text, i, false);
+            if end - i == 2 && at(text, i, b"fn") {
+                let start = skip_space(text, end, true);
+                let name = name_end(text, start, false);
+                let solution = is_solution_name(text, start, name) && returns_answer(text, name);
+                if solution && !is_named(text, start, name, names) {
+                    return false;
+                }
+            }
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    true
+}
+
+/// Returns whether every dependency of the Cargo manifest `manifest` whose name starts with `prefix` is one of
+/// `names`. Callable in constants, where a program can check with it that it uses every crate it depends on.
+///
+/// Dependencies are read from the keys of `[dependencies]` tables, including target specific ones, and from
+/// the headers of tables such as `[dependencies.name]`, a line at a time.
+///
+/// ```
+/// use euler_library::solution::dependencies_named;
+///
+/// let manifest = "[dependencies]\neuler_library = { path = \"euler_library\" }\neuler_p001_010= \"0.1\"\n\
+///                 [target.'cfg(unix)'.dependencies.euler_p011_020]\npath = \"euler_p011_020\"\n\
+///                 [features]\neuler_p021_030 = []";
+/// assert!(dependencies_named(manifest, "euler_p", &["euler_p001_010", "euler_p011_020"]));
+/// assert!(!dependencies_named(manifest, "euler_p", &["euler_p001_010"]));
+/// ```
+pub const fn dependencies_named(manifest: &str, prefix: &str, names: &[&str]) -> bool {
+    let (text, prefix) = (manifest.as_bytes(), prefix.as_bytes());
+    let mut in_dependencies = false;
+    let mut i = 0;
+    while i < text.len() {
+        let start = skip_space(text, i, false);
+        let mut end = start;
+        while end < text.len() && text[end] != b'\n' {
+            end += 1;
+        }
+        if at(text, start, b"[") {
+            // a table of dependencies, or of one of them
+            let mut close = start;
+            while close < end && text[close] != b']' {
+                close += 1;
+            }
+            let mut k = start;
+            while k < close && !at(text, k, b"dependencies") {
+                k += 1;
+            }
+            in_dependencies = k + 12 == close;
+            if k + 13 < close && text[k + 12] == b'.' {
+                let name = k + 13 + if text[k + 13] == b'"' { 1 } else { 0 };
+                if at(text, name, prefix) && !is_named(text, name, name_end(text, name, true), names) {
+                    return false;
+                }
+            }
+        } else if in_dependencies {
+            let name = start + if at(text, start, b"\"") { 1 } else { 0 };
+            let name_end = name_end(text, name, true);
+            let key_end = skip_space(text, name_end + if at(text, name_end, b"\"") { 1 } else { 0 }, false);
+            if at(text, name, prefix) && (at(text, key_end, b"=") || at(text, key_end, b"."))
+                && !is_named(text, name, name_end, names)
+            {
+                return false;
+            }
+        }
+        i = end + 1;
+    }
+    true
+}
+
+/// Defines `get_functions`, returning the solutions of a problem crate, from a declaration of each.
+///
+/// The inner attribute `#![euler(problems = FIRST..=LAST)]` gives the problems of the crate. Each solution
+/// is then declared by an attribute `#[euler(problem = N, title = "...")]` followed by the function computing
+/// its answer and any `Solution` builder calls, e.g. `.examples(f)` or `.variant("a")`. A problem of the
+/// crate left without a solution fails the build, as does a function `pNNN...() -> Answer` of the crate's
+/// `src/lib.rs`, such as a variant, left undeclared; see `solutions_named` for which functions are found.
+///
+/// ```
+/// #[macro_use]
+/// extern crate euler_library;
+///
+/// use euler_library::solution::{Answer, Param};
+///
+/// fn p001() -> Answer {
+///     Answer::from(233168)
+/// }
+///
+/// fn p002_with(limit: u64) -> Answer {
+///     Answer::from(limit / 2)
+/// }
+///
+/// fn p002() -> Answer {
+///     p002_with(4_000_000)
+/// }
+///
+/// solutions! {
+///     #![euler(problems = 1..=2)]
+///
+///     #[euler(problem = 1, title = "Multiples of 3 and 5")]
+///     p001,
+///     #[euler(problem = 2, title = "Even Fibonacci numbers")]
+///     p002.params(vec![Param::new("limit", 4_000_000, "sum the terms below limit")], |v| p002_with(v[0])),
+/// }
+///
+/// fn main() {
+///     let solutions = get_functions();
+///     assert_eq!(solutions[1].id(), "p002");
+///     assert_eq!(solutions[1].title, "Even Fibonacci numbers");
+///     assert_eq!(solutions[1].params.len(), 1);
+/// }
+/// ```
+///
+/// Forgetting problem 2 does not compile:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate euler_library;
+///
+/// use euler_library::solution::Answer;
+///
+/// fn p001() -> Answer {
+///     Answer::from(233168)
+/// }
+///
+/// solutions! {
+///     #![euler(problems = 1..=2)]
+///
+///     #[euler(problem = 1, title = "Multiples of 3 and 5")]
+///     p001,
+/// }
+///
+/// fn main() {}
+/// ```
+#[macro_export]
+macro_rules! solutions {
+    (
+        #![euler(problems = $first:literal ..= $last:literal)]
+        $(
+            #[euler(problem = $problem:literal, title = $title:literal)]
+            $func:ident $(.$method:ident($($arg:expr),*))*
+        ),* $(,)*
+    ) => {
+        /// Returns the solutions in this crate.
+        pub fn get_functions() -> Vec<$crate::solution::Solution> {
+            let krate = env!("CARGO_PKG_NAME");
+            vec![$($crate::solution::Solution::new(krate, $problem, $title, $func)$(.$method($($arg),*))*),*]
+        }
+
+        const _: () = {
+            let problems: &[u32] = &[$($problem),*];
+            let mut n = $first;
+            while n <= $last {
+                let mut i = 0;
+                while i < problems.len() && problems[i] != n {
+                    i += 1;
+                }
+                assert!(i < problems.len(), "a problem of this crate has no solution in `solutions!`");
+                n += 1;
+            }
+
+            let source = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/lib.rs"));
+            assert!(
+                $crate::solution::solutions_named(source, &[$(stringify!($func)),*]),
+                "a solution function of this crate is not declared in `solutions!`"
+            );
+        };
+    };
+}
@@ -10,74 +10,97 @@ extern crate primal;
 extern crate itertools;
 use itertools::Itertools;
 
+#[macro_use]
 extern crate euler_library;
+use euler_library::solution::{Answer, Param};
 use euler_library::common as eu;
+use euler_library::data::{self, ParseError};
 
 /// Multiples of 3 and 5
-pub fn p001() -> String {
-    let n = 1000;
+pub fn p001() -> Answer {
+    p001_with(1000)
+}
+
+/// Sum of the multiples of 3 or 5 below `n`
+pub fn p001_with(n: usize) -> Answer {
     let res = (0..n).fold(0,
                           |acc, x| if x % 3 == 0 || x % 5 == 0 { acc + x } else { acc });
 
-    assert_eq!(res, 233168);
-    format!("p001 = {}", res)
-} // 233168
+    Answer::from(res)
+}
 
-/// Even Fibonacci numbers
-pub fn p002() -> String {
-    struct Fibonacci {
-        curr: usize,
-        next: usize,
-    }
+struct Fibonacci {
+    curr: usize,
+    next: usize,
+}
 
-    impl Iterator for Fibonacci {
-        type Item = usize;
-        fn next(&mut self) -> Option<usize> {
-            let new_next = self.curr + self.next;
-            self.curr = self.next;
-            self.next = new_next;
-            Some(self.curr)
-        }
+impl Iterator for Fibonacci {
+    type Item = usize;
+    fn next(&mut self) -> Option<usize> {
+        let new_next = self.curr + self.next;
+        self.curr = self.next;
+        self.next = new_next;
+        Some(self.curr)
     }
+}
 
-    // Returns a fibonacci sequence generator
-    fn fibonacci() -> Fibonacci {
-        Fibonacci { curr: 1, next: 1 }
-    }
+// Returns a fibonacci sequence generator
+fn fibonacci() -> Fibonacci {
+    Fibonacci { curr: 1, next: 1 }
+}
 
-    fn solve(n: usize) -> usize {
-        fibonacci()
-            .take_while(|&x| x < n)
-            .filter(|x| x % 2 == 0)
-            .scan(0, |acc, fib| {
-                *acc += fib;
-                Some(*acc)
-            })
-            .max()
-            .unwrap()
-    }
+// Sum of the even fibonacci numbers below n
+fn sum_even_fibonacci(n: usize) -> usize {
+    fibonacci()
+        .take_while(|&x| x < n)
+        .filter(|x| x % 2 == 0)
+        .scan(0, |acc, fib| {
+            *acc += fib;
+            Some(*acc)
+        })
+        .max()
+        .unwrap_or(0)
+}
 
-    assert_eq!(solve(50), 44);
+/// Checks the examples given in the statement of p002
+pub fn p002_examples() {
+    assert_eq!(sum_even_fibonacci(50), 44);
+}
 
-    let sum = solve(4_000_000);
-    assert_eq!(sum, 4613732);
-    format!("p002 = {}", sum)
-} // 4613732
+/// Even Fibonacci numbers
+pub fn p002() -> Answer {
+    p002_with(4_000_000)
+}
 
+/// Sum of the even Fibonacci numbers below `limit`
+pub fn p002_with(limit: usize) -> Answer {
+    let sum = sum_even_fibonacci(limit);
+    Answer::from(sum)
+}
 
 /// Largest prime factor
-pub fn p003() -> String {
-    let sieve = primal::Sieve::new(10_000);
-    let (res, _) = sieve.factor(600851475143).unwrap().into_iter().max().unwrap();
-    assert_eq!(res, 6857);
-    format!("p003 = {}", res)
-} // 6857
+pub fn p003() -> Answer {
+    p003_with(600851475143)
+}
+
+/// Largest prime factor of `n`, `n` at least 2
+pub fn p003_with(n: usize) -> Answer {
+    // sieving up to the square root of n leaves at most one prime factor above it, which factor returns too
+    let sieve = primal::Sieve::new((n as f64).sqrt() as usize + 1);
+    let (res, _) = sieve.factor(n).unwrap().into_iter().max().unwrap();
+    Answer::from(res)
+}
 
 /// Largest palindrome product
-pub fn p004() -> String {
-    fn solve() -> usize {
+pub fn p004() -> Answer {
+    p004_with(3)
+}
+
+/// Largest palindrome made from the product of two `digits`-digit numbers
+pub fn p004_with(digits: usize) -> Answer {
+    fn solve(digits: u32) -> usize {
         let mut max = 0;
-        let it = (99..999).rev();
+        let it = (10usize.pow(digits - 1)..10usize.pow(digits)).rev();
         for i in it.clone() {
             for j in it.clone() {
                 let t = i * j;
@@ -92,59 +115,93 @@ pub fn p004() -> String {
         max
     }
 
-    let max = solve();
-    assert_eq!(max, 906609);
-    format!("p004 = {}", max)
-} // 906609
+    let max = solve(digits as u32);
+    Answer::from(max)
+}
 
 /// Smallest multiple
-pub fn p005() -> String {
-    fn solve() -> usize {
-        let mut i = 2520;
+pub fn p005() -> Answer {
+    p005_with(20)
+}
+
+/// Smallest number evenly divisible by all of the numbers from 1 to `n`
+pub fn p005_with(n: usize) -> Answer {
+    // steps through the multiples of the answer for 1 to n / 2, 2520 for 20, checking those above n / 2
+    fn solve(n: usize) -> usize {
+        if n < 2 {
+            return 1;
+        }
+        let step = solve(n / 2);
+        let mut i = step;
         loop {
-            if i % 11 + i % 12 + i % 13 + i % 14 + i % 15 + i % 16 + i % 17 + i % 18 + i % 19 == 0 {
+            if (n / 2 + 1..n + 1).all(|x| i % x == 0) {
                 return i;
             }
-            i += 2520;
+            i += step;
         }
     }
 
-    let res = solve();
-    assert_eq!(res, 232792560);
-    format!("p005 = {}", res)
-} // 232792560
+    let res = solve(n);
+    Answer::from(res)
+}
 
-/// Sum square difference
-pub fn p006() -> String {
-    fn solve(n: usize) -> usize {
-        let sum = (1..n + 1).fold(0, Add::add);
-        let sum_square = (1..n + 1).fold(0, |acc, x| acc + x * x);
-        sum * sum - sum_square
-    }
+// Difference between the square of the sum and the sum of the squares of 1 to n
+fn sum_square_difference(n: usize) -> usize {
+    let sum = (1..n + 1).fold(0, Add::add);
+    let sum_square = (1..n + 1).fold(0, |acc, x| acc + x * x);
+    sum * sum - sum_square
+}
 
-    assert_eq!(solve(10), 2640);
+/// Checks the examples given in the statement of p006
+pub fn p006_examples() {
+    assert_eq!(sum_square_difference(10), 2640);
+}
 
-    let res = solve(100);
-    assert_eq!(res, 25164150);
-    format!("p006 = {}", res)
-} // 25164150
+/// Sum square difference
+pub fn p006() -> Answer {
+    p006_with(100)
+}
+
+/// Sum square difference of the first `n` natural numbers
+pub fn p006_with(n: usize) -> Answer {
+    let res = sum_square_difference(n);
+    Answer::from(res)
+}
 
 /// 10001st prime
-pub fn p007() -> String {
-    let nth_prime = primal::Primes::all().nth(10001 - 1).unwrap();
-    assert_eq!(nth_prime, 104743);
-    format!("p007 = {}", nth_prime)
-} // 104743
+pub fn p007() -> Answer {
+    p007_with(10001)
+}
+
+/// The `n`th prime, `n` at least 1
+pub fn p007_with(n: usize) -> Answer {
+    let nth_prime = primal::Primes::all().nth(n.checked_sub(1).expect("n must be at least 1")).unwrap();
+    Answer::from(nth_prime)
+}
 
 /// Largest product in a series
-pub fn p008() -> String {
-    let vals = include_str!("../data/p008_product.txt")
-        .bytes()
-        .filter(|&b| b != 10)
-        .map(|x| x as usize - 48)
-        .collect::<Vec<_>>();
-
-    let max = (0..(vals.len() - 12))
+pub fn p008() -> Answer {
+    p008_from(include_str!("../data/p008_product.txt")).unwrap()
+}
+
+/// Largest product of 13 adjacent digits in `text`, lines of digits, failing on fewer than 13 digits
+///
+/// ```
+/// use euler_p001_010::p008_from;
+///
+/// assert_eq!(p008_from("1111111111111\n2").unwrap().to_string(), "2");
+/// let e = p008_from("123\n").unwrap_err();
+/// assert_eq!(e.message, "expected at least 13 digits, found 3");
+/// assert!(p008_from("").is_err());
+/// ```
+pub fn p008_from(text: &str) -> Result<Answer, ParseError> {
+    let vals = data::digits(text)?.into_iter().map(usize::from).collect::<Vec<_>>();
+    let windows = match vals.len().checked_sub(12) {
+        Some(n) if n > 0 => n,
+        _ => return Err(ParseError::new(1, 1, &format!("expected at least 13 digits, found {}", vals.len()))),
+    };
+
+    let max = (0..windows)
         .map(|i| {
             vals.iter()
                 .take(i + 13)
@@ -154,54 +211,126 @@ pub fn p008() -> String {
         .max()
         .unwrap();
 
-    assert_eq!(max, 23514624000);
-    format!("p008 = {}", max)
-} // 23514624000
+    Ok(Answer::from(max))
+}
 
 /// Special Pythagorean triplet
-pub fn p009() -> String {
-    let res = (1..500)
+pub fn p009() -> Answer {
+    p009_with(1000)
+}
+
+/// Product abc of the Pythagorean triplet a < b < c with the least a for which a + b + c = `sum`, 0 when
+/// there is none
+pub fn p009_with(sum: usize) -> Answer {
+    // c is less than a + b, so a and b are below half the sum
+    let half = sum / 2;
+    let res = (1..half)
         .flat_map(|a| {
-            (a..500).filter_map(move |b| {
+            (a..half).filter_map(move |b| {
                 let c = ((a * a + b * b) as f64).sqrt();
                 let circum = a + b + (c as usize);
-                if c.fract() < EPSILON && circum == 1000 { Some(a * b * (c as usize)) } else { None }
+                if c.fract() < EPSILON && circum == sum { Some(a * b * (c as usize)) } else { None }
             })
         })
         .nth(0)
-        .unwrap();
+        .unwrap_or(0);
 
-    assert_eq!(res, 31875000);
-    format!("p009 = {}", res)
-} // 31875000
+    Answer::from(res)
+}
 
-/// Summation of primes
-pub fn p010() -> String {
-    fn solve(n: usize) -> usize {
-        match n {
-            0...1 => 0,
-            2 => 2,
-            3...4 => 5,
-            5...7 => 10,
-            _ => {
-                let sieve = primal::Sieve::new(2_000_000);
-                (7..n)
-                    .step(2)
-                    .fold(0,
-                          |acc, i| if i % 5 != 0 && sieve.is_prime(i) { acc + i } else { acc }) + 10
-            }
+// Sum of the primes below n
+fn sum_of_primes(n: usize) -> usize {
+    match n {
+        0...1 => 0,
+        2 => 2,
+        3...4 => 5,
+        5...7 => 10,
+        _ => {
+            let sieve = primal::Sieve::new(n);
+            (7..n)
+                .step(2)
+                .fold(0,
+                      |acc, i| if i % 5 != 0 && sieve.is_prime(i) { acc + i } else { acc }) + 10
         }
     }
+}
+
+/// Checks the examples given in the statement of p010
+pub fn p010_examples() {
+    assert_eq!(sum_of_primes(10), 17);
+}
 
-    assert_eq!(solve(9), 17);
+/// Summation of primes
+pub fn p010() -> Answer {
+    p010_with(2_000_000)
+}
+
+/// Sum of the primes below `limit`
+pub fn p010_with(limit: usize) -> Answer {
+    let sum = sum_of_primes(limit);
+    Answer::from(sum)
+}
 
-    let sum = solve(2_000_000);
-    assert_eq!(sum, 142913828922);
-    format!("p010 = {}", sum)
-} // 142913828922
+solutions! {
+    #![euler(problems = 1..=10)]
 
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-    // Euler solutions in this crate.
-    (1, vec![p001, p002, p003, p004, p005, p006, p007, p008, p009, p010])
+    #[euler(problem = 1, title = "Multiples of 3 and 5")]
+    p001
+        .params(
+            vec![Param::new("n", 1000, "sum the multiples below n").at_most(8_891_427_027)],
+            |v| p001_with(v[0] as usize)
+        ),
+    #[euler(problem = 2, title = "Even Fibonacci numbers")]
+    p002
+        .examples(p002_examples)
+        .params(
+            vec![Param::new("limit", 4_000_000, "sum the terms below limit").at_most(7_540_113_804_746_346_429)],
+            |v| p002_with(v[0] as usize)
+        ),
+    #[euler(problem = 3, title = "Largest prime factor")]
+    p003
+        .params(
+            vec![Param::new("n", 600851475143, "largest prime factor of n").at_least(2).at_most(10_000_000_000_000_000)],
+            |v| p003_with(v[0] as usize)
+        ),
+    #[euler(problem = 4, title = "Largest palindrome product")]
+    p004
+        .params(
+            vec![Param::new("digits", 3, "product of two numbers of this many digits").at_least(1).at_most(6)],
+            |v| p004_with(v[0] as usize)
+        ),
+    #[euler(problem = 5, title = "Smallest multiple")]
+    p005
+        .params(
+            vec![Param::new("n", 20, "divisible by each of 1 to n").at_most(40)],
+            |v| p005_with(v[0] as usize)
+        ),
+    #[euler(problem = 6, title = "Sum square difference")]
+    p006
+        .examples(p006_examples)
+        .params(
+            vec![Param::new("n", 100, "of the first n natural numbers").at_most(92_681)],
+            |v| p006_with(v[0] as usize)
+        ),
+    #[euler(problem = 7, title = "10001st prime")]
+    p007
+        .params(
+            vec![Param::new("n", 10001, "find the nth prime").at_least(1).at_most(1_000_000_000)],
+            |v| p007_with(v[0] as usize)
+        ),
+    #[euler(problem = 8, title = "Largest product in a series")]
+    p008.data("p008_product.txt", p008_from),
+    #[euler(problem = 9, title = "Special Pythagorean triplet")]
+    p009
+        .params(
+            vec![Param::new("sum", 1000, "a + b + c = sum").at_most(20_000)],
+            |v| p009_with(v[0] as usize)
+        ),
+    #[euler(problem = 10, title = "Summation of primes")]
+    p010
+        .examples(p010_examples)
+        .params(
+            vec![Param::new("limit", 2_000_000, "sum the primes below limit").at_most(10_000_000_000)],
+            |v| p010_with(v[0] as usize)
+        ),
 }
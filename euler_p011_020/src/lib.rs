@@ -2,128 +2,127 @@
 //!
 //! This crate is designed to be used via crate `euler`.
 
-use std::str::FromStr;
 use std::cmp;
 
-extern crate itertools;
-use itertools::Itertools;
-
 extern crate num;
 use num::{BigUint, pow};
 use num::bigint::ToBigUint;
 
+#[macro_use]
 extern crate euler_library;
+use euler_library::solution::{Answer, Param};
 use euler_library::big as eu_big;
+use euler_library::data::{self, ParseError};
 
 
 /// Largest product in a grid
-pub fn p011() -> String {
-    fn get_data() -> Vec<Vec<usize>> {
-        let buffer = include_str!("../data/p011_grid.txt");
-        buffer.lines()
-              .map(|x| {
-                  x.split(',')
-                   .map(|x| x.parse().unwrap())
-                   .collect::<Vec<usize>>()
-              })
-              .collect::<Vec<Vec<usize>>>()
-    }
+pub fn p011() -> Answer {
+    p011_from(include_str!("../data/p011_grid.txt")).unwrap()
+}
 
-    let data = get_data();
+/// Largest product of four adjacent numbers in a line in `text`, lines of comma separated numbers
+pub fn p011_from(text: &str) -> Result<Answer, ParseError> {
+    let data = data::rows::<usize>(text)?;
+    // numbers outside of the grid count as 0
+    let at = |i: usize, j: usize| data.get(i).and_then(|v| v.get(j)).cloned().unwrap_or(0);
     let mut max = 0;
 
-    for (i, vi) in data.iter().enumerate().take(20) {
-        for (j, _) in vi.iter().enumerate().take(20) {
+    for (i, vi) in data.iter().enumerate() {
+        for (j, _) in vi.iter().enumerate() {
 
-            let mut t = data[i][j] * data[i][j + 1] * data[i][j + 2] * data[i][j + 3];
+            let mut t = at(i, j) * at(i, j + 1) * at(i, j + 2) * at(i, j + 3);
             if t > max {
                 max = t
             }
             // down
-            t = data[i][j] * data[i + 1][j] * data[i + 2][j] * data[i + 3][j];
+            t = at(i, j) * at(i + 1, j) * at(i + 2, j) * at(i + 3, j);
             if t > max {
                 max = t
             }
             // diag right
-            t = data[i][j] * data[i + 1][j + 1] * data[i + 2][j + 2] * data[i + 3][j + 3];
+            t = at(i, j) * at(i + 1, j + 1) * at(i + 2, j + 2) * at(i + 3, j + 3);
             if t > max {
                 max = t
             }
             // diag left
-            t = data[i][j + 3] * data[i + 1][j + 2] * data[i + 2][j + 1] * data[i + 3][j];
+            t = at(i, j + 3) * at(i + 1, j + 2) * at(i + 2, j + 1) * at(i + 3, j);
             if t > max {
                 max = t
             }
         }
     }
 
-    assert_eq!(max, 70600674);
-    format!("p011 = {}", max)
-} // 70600674
+    Ok(Answer::from(max))
+}
 
 
-/// Highly divisible triangular number
-pub fn p012() -> String {
-    // Returns cnt of factors of n excluding 1 and n
-    fn factor_cnt(mut n: usize) -> usize {
-        if n < 2 {
-            return 1;
+// Returns the number of divisors of n, 1 and n included
+fn divisor_cnt(mut n: usize) -> usize {
+    let mut divisors = 1;
+    let mut i = 2;
+    while i * i <= n {
+        let mut p = 0;
+        while n % i == 0 {
+            p += 1;
+            n /= i
         }
-        let mut factors = 1;
-        let max = (n as f64).sqrt() as usize;
-        for i in 2.. {
-            if i > max {
-                break;
-            }
-            let mut p = 0;
-            while n % i == 0 {
-                p += 1;
-                n /= i
-            }
-            factors *= p + 1;
-        }
-        factors
+        divisors *= p + 1;
+        i += 1;
     }
-    assert_eq!(factor_cnt(21), 2);
-    assert_eq!(factor_cnt(28), 3);
-
-    fn solve() -> usize {
-        for i in (2..).step(4) {
-            let temp = factor_cnt(i + 1);
-            if temp * factor_cnt(i / 2) > 500 {
-                return i * (i + 1) / 2;
-            }
-            if temp * factor_cnt((i + 2) / 2) > 500 {
-                return (i + 1) * (i + 2) / 2;
-            }
-        }
-        0
+    // a prime factor left above the square root
+    if n > 1 {
+        divisors *= 2;
     }
+    divisors
+}
 
-    let tri_number = solve();
-    assert_eq!(tri_number, 76576500);
-    format!("p012 = {}", tri_number)
-} // 76576500
+/// Checks the examples given in the statement of p012
+pub fn p012_examples() {
+    // the divisors of the first seven triangle numbers
+    let counts = [1, 3, 6, 10, 15, 21, 28].iter().map(|&n| divisor_cnt(n)).collect::<Vec<_>>();
+    assert_eq!(counts, [1, 2, 4, 4, 4, 4, 6]);
+    assert_eq!(p012_with(5), Answer::from(28));
+}
+
+/// Highly divisible triangular number
+pub fn p012() -> Answer {
+    p012_with(500)
+}
+
+/// First triangle number with over `divisors` divisors
+pub fn p012_with(divisors: usize) -> Answer {
+    // n and n + 1 are coprime, so the divisors of n(n + 1)/2 are those of its two coprime halves combined
+    for n in 1.. {
+        let (a, b) = if n % 2 == 0 { (n / 2, n + 1) } else { (n, (n + 1) / 2) };
+        if divisor_cnt(a) * divisor_cnt(b) > divisors {
+            return Answer::from(n * (n + 1) / 2);
+        }
+    }
+    unreachable!()
+}
 
 /// Large sum
-pub fn p013() -> String {
-    let buffer = include_str!("../data/p013_sum.txt");
-    let xs: Vec<&str> = buffer.split_whitespace().collect();
-    let sum = xs.into_iter()
-                .fold(0.to_biguint().unwrap(), |acc, x| {
-                    let bu: BigUint = FromStr::from_str(x).unwrap();
-                    acc + bu
-                });
-
-    let str = &sum.to_string()[..10];
-    assert_eq!(str, "5537376230");
-    format!("p013 = {}", str)
-} // 5537376230
+pub fn p013() -> Answer {
+    p013_from(include_str!("../data/p013_sum.txt")).unwrap()
+}
+
+/// First ten digits of the sum of the numbers in `text`, one per line
+pub fn p013_from(text: &str) -> Result<Answer, ParseError> {
+    let xs = data::numbers::<BigUint>(text)?;
+    let sum = xs.into_iter().fold(0.to_biguint().unwrap(), |acc, bu| acc + bu);
+
+    let str = sum.to_string().chars().take(10).collect::<String>();
+    Ok(Answer::from_digits(&str))
+}
 
 /// Longest Collatz sequence
-pub fn p014() -> String {
-    const LIMIT: usize = 1_000_000;
-    let mut cache: Vec<usize> = vec![0; LIMIT];
+pub fn p014() -> Answer {
+    p014_with(1_000_000)
+}
+
+/// Starting number below `limit` producing the longest Collatz chain
+pub fn p014_with(limit: usize) -> Answer {
+    let mut cache: Vec<usize> = vec![0; limit];
 
     let mut max = 0;
     let mut answer = 1;
@@ -145,35 +144,46 @@ pub fn p014() -> String {
         }
     }
 
-    assert_eq!(answer, 837799);
-    format!("p014 = {}", answer)
-} // 837799
+    Answer::from(answer)
+}
 
 /// Lattice paths
-pub fn p015() -> String {
+pub fn p015() -> Answer {
+    p015_with(20)
+}
+
+/// Number of routes through a `size` by `size` grid, moving only right and down
+pub fn p015_with(size: usize) -> Answer {
     // C(n,r) = n! / ( r! (n - r)! )
     // 40! / (20! (40 - 20)!)
-    let fact_n = eu_big::factorial(40);
-    let fact_r = eu_big::factorial(20);
+    let fact_n = eu_big::factorial(2 * size);
+    let fact_r = eu_big::factorial(size);
     let s = (&fact_n / (&fact_r * &fact_r)).to_string();
 
-    assert_eq!(s, "137846528820".to_string());
-    format!("p015 = {}", s)
-} // 137846528820
+    Answer::from_digits(&s)
+}
 
 /// Power digit sum
-pub fn p016() -> String {
-    let n = 1000;
+pub fn p016() -> Answer {
+    p016_with(1000)
+}
+
+/// Sum of the digits of 2^`n`
+pub fn p016_with(n: usize) -> Answer {
     let two = 2.to_biguint().unwrap();
     let xs = pow(two, n).to_string();
     let res = xs.chars().fold(0, |acc, x| acc + (x as usize) - 48);
 
-    assert_eq!(res, 1366);
-    format!("p016 = {}", res)
-} // 1366
+    Answer::from(res)
+}
 
-/// Maximum path sum I
-pub fn p017() -> String {
+/// Number letter counts
+pub fn p017() -> Answer {
+    p017_with(1000)
+}
+
+/// Letters used writing out the numbers from 1 to `n` in British English words, `n` at most 1000
+pub fn p017_with(n: usize) -> Answer {
     macro_rules! hashmap {
         ($( $key: expr => $val: expr ),*) => {{
              let mut map = ::std::collections::HashMap::new();
@@ -188,86 +198,130 @@ pub fn p017() -> String {
 
     let m_len = |n: usize| -> usize { m.get(&n).unwrap().len() };
 
-    let one_to_nine = (1..10).fold(0, |acc, x| acc + m_len(x));
-
-    let one_to_nineteen = &one_to_nine + (10..20).fold(0, |acc, x| acc + m_len(x));
-
-    let tys = m_len(20) + m_len(30) + m_len(40) + m_len(50) + m_len(60) + m_len(70) + m_len(80) + m_len(90);
-
-    let one_to_ninety_nine = &one_to_nineteen + 8 * &one_to_nine + 10 * &tys;
+    let below_hundred = |n: usize| -> usize {
+        match n {
+            0 => 0,
+            1...20 => m_len(n),
+            _ if n % 10 == 0 => m_len(n),
+            _ => m_len(n / 10 * 10) + m_len(n % 10),
+        }
+    };
+
+    let letters = |n: usize| -> usize {
+        match (n / 100, n % 100) {
+            (10, _) => "onethousand".len(),
+            (0, rest) => below_hundred(rest),
+            (hundreds, 0) => m_len(hundreds) + "hundred".len(),
+            (hundreds, rest) => m_len(hundreds) + "hundredand".len() + below_hundred(rest),
+        }
+    };
 
-    let res = 100 * &one_to_nine + 9 * ("hundred".len() + 99 * "hundredand".len()) + 10 * &one_to_ninety_nine +
-              "onethousand".len();
+    let res = (1..n + 1).fold(0, |acc, x| acc + letters(x));
 
-    assert_eq!(res, 21124);
-    format!("p017 = {}", res)
-} // 21124
+    Answer::from(res)
+}
 
 /// Maximum path sum I
-pub fn p018() -> String {
-    fn get_data() -> Vec<Vec<usize>> {
-        let buffer = include_str!("../data/p018_triangle.txt");
-        buffer.lines()
-              .map(|x| {
-                  x.split(' ')
-                   .map(|x| x.parse().unwrap())
-                   .collect::<Vec<usize>>()
-              })
-              .collect::<Vec<Vec<usize>>>()
-    }
+pub fn p018() -> Answer {
+    p018_from(include_str!("../data/p018_triangle.txt")).unwrap()
+}
 
-    let mut xss = get_data();
+/// Maximum total from top to bottom of the triangle in `text`, rows of space separated numbers
+pub fn p018_from(text: &str) -> Result<Answer, ParseError> {
+    let mut xss = data::triangle::<usize>(text)?;
     for i in (0..xss.len()).rev() {
         for j in 0..i {
             xss[i - 1][j] += cmp::max(xss[i][j], xss[i][j + 1])
         }
     }
 
-    assert_eq!(xss[0][0], 1074);
-    format!("p018 = {}", xss[0][0])
-} // 1074
+    Ok(Answer::from(xss[0][0]))
+}
 
 /// Counting Sundays
-pub fn p019() -> String {
-    let mut sunday = 7;
+pub fn p019() -> Answer {
+    p019_with(1901, 2000)
+}
+
+/// Sundays falling on the first of the month from the year `first` through the year `last`, `first` no
+/// earlier than 1900
+pub fn p019_with(first: usize, last: usize) -> Answer {
+    // 1 Jan 1900 was a Monday; days of the week are counted from Sunday as 0
+    let mut weekday = 1;
     let mut cnt = 0;
-    for i in 1900..2001 {
-        let days = if i % 4 == 0 && (i % 100 != 0 || i % 400 == 0) { 366 } else { 365 };
-        while sunday < days {
-            if i != 1900 {
-                if days == 365 {
-                    match sunday {
-                        1 | 32 | 60 | 91 | 121 | 152 | 182 | 213 | 244 | 274 | 305 | 335 => cnt += 1,
-                        _ => (),
-                    }
-                } else {
-                    match sunday {
-                        1 | 32 | 61 | 92 | 122 | 153 | 183 | 214 | 245 | 275 | 306 | 336 => cnt += 1,
-                        _ => (),
-                    }
-                }
+    for year in 1900..last + 1 {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        for &days in &[31, if leap { 29 } else { 28 }, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31] {
+            if year >= first && weekday == 0 {
+                cnt += 1;
             }
-            sunday += 7;
+            weekday = (weekday + days) % 7;
         }
-        sunday = if sunday == 0 { 7 } else { sunday - days }
     }
 
-    assert_eq!(cnt, 171);
-    format!("p019 = {}", cnt)
-} // 171
+    Answer::from(cnt)
+}
 
 /// Factorial digit sum
-pub fn p020() -> String {
-    let n = 100;
+pub fn p020() -> Answer {
+    p020_with(100)
+}
+
+/// Sum of the digits of `n`!
+pub fn p020_with(n: usize) -> Answer {
     let xs = eu_big::factorial(n).to_string();
     let res = xs.chars().fold(0, |acc, x| acc + (x as usize) - 48);
 
-    assert_eq!(res, 648);
-    format!("p020 = {}", res)
-} // 648
+    Answer::from(res)
+}
 
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-    (11,
-     vec![p011, p012, p013, p014, p015, p016, p017, p018, p019, p020])
+solutions! {
+    #![euler(problems = 11..=20)]
+
+    #[euler(problem = 11, title = "Largest product in a grid")]
+    p011.data("p011_grid.txt", p011_from),
+    #[euler(problem = 12, title = "Highly divisible triangular number")]
+    p012
+        .examples(p012_examples)
+        .params(
+            vec![Param::new("divisors", 500, "first triangle number with over this many divisors").at_most(2000)],
+            |v| p012_with(v[0] as usize)
+        ),
+    #[euler(problem = 13, title = "Large sum")]
+    p013.data("p013_sum.txt", p013_from),
+    #[euler(problem = 14, title = "Longest Collatz sequence")]
+    p014
+        .params(
+            vec![Param::new("limit", 1_000_000, "starting numbers below limit").at_most(100_000_000)],
+            |v| p014_with(v[0] as usize)
+        ),
+    #[euler(problem = 15, title = "Lattice paths")]
+    p015
+        .params(
+            vec![Param::new("size", 20, "routes through a size by size grid").at_most(10_000)],
+            |v| p015_with(v[0] as usize)
+        ),
+    #[euler(problem = 16, title = "Power digit sum")]
+    p016
+        .params(vec![Param::new("n", 1000, "digits of 2^n").at_most(100_000)], |v| p016_with(v[0] as usize)),
+    #[euler(problem = 17, title = "Number letter counts")]
+    p017
+        .params(
+            vec![Param::new("n", 1000, "numbers from 1 to n written out").at_most(1000)],
+            |v| p017_with(v[0] as usize)
+        ),
+    #[euler(problem = 18, title = "Maximum path sum I")]
+    p018.data("p018_triangle.txt", p018_from),
+    #[euler(problem = 19, title = "Counting Sundays")]
+    p019
+        .params(
+            vec![
+                Param::new("first", 1901, "from the year first").at_least(1900),
+                Param::new("last", 2000, "through the year last").at_most(1_000_000),
+            ],
+            |v| p019_with(v[0] as usize, v[1] as usize)
+        ),
+    #[euler(problem = 20, title = "Factorial digit sum")]
+    p020
+        .params(vec![Param::new("n", 100, "digits of n!").at_most(10_000)], |v| p020_with(v[0] as usize)),
 }
@@ -15,35 +15,43 @@ extern crate num;
 use num::{BigUint, One, Zero};
 use num::bigint::ToBigUint;
 
+#[macro_use]
 extern crate euler_library;
+use euler_library::solution::{Answer, Param};
 use euler_library::common as eu;
 use euler_library::big as eu_big;
+use euler_library::data::{self, ParseError};
 
 
 /// Amicable numbers
-pub fn p021() -> String {
-    const N: usize = 10_000;
+pub fn p021() -> Answer {
+    p021_with(10_000)
+}
 
-    let amic = eu::divisor_sum_list(N);
+/// Sum of the amicable numbers below `limit`
+pub fn p021_with(limit: usize) -> Answer {
+    let amic = eu::divisor_sum_list(limit);
     let mut sum = 0;
-    for (i, item) in amic.clone().into_iter().enumerate().take(N) {
-        if item < N && i != item && i == amic[item] {
-            sum += item + amic[item]
+    for (i, &item) in amic.iter().enumerate().take(limit) {
+        // the partner of an amicable number below limit may lie above it
+        let partner_sum = if item < limit { amic[item] } else { eu::divisor_sum(item) };
+        if i != item && i == partner_sum {
+            sum += i
         }
     }
 
-    assert_eq!(sum / 2, 31626);
-    format!("p021 = {}", sum / 2)
-} // 31626
+    Answer::from(sum)
+}
 
 /// Names scores
-pub fn p022() -> String {
-    let buffer = include_str!("../data/p022_names.txt")
-        .chars()
-        .filter(|&x| x != '\"' && x != '\n')
-        .collect::<String>();
+pub fn p022() -> Answer {
+    p022_from(include_str!("../data/p022_names.txt")).unwrap()
+}
 
-    let names = buffer.split(',')
+/// Total of the name scores of the quoted, comma separated upper case names in `text`
+pub fn p022_from(text: &str) -> Result<Answer, ParseError> {
+    let names = data::words(text)?
+        .iter()
         .map(|x| {
             let ys = eu::to_bytes(&x);
             ys.iter().map(|&y| y as usize - 64).collect::<Vec<usize>>()
@@ -57,22 +65,26 @@ pub fn p022() -> String {
             acc + val * (i + 1)
         });
 
-    assert!(sum == 871198282);
-    format!("p022 = {}", sum)
-} // 871198282
+    Ok(Answer::from(sum))
+}
 
 /// Non-abundant sums
-pub fn p023() -> String {
-    const N: usize = 28124;
-    let factor_sums = eu::divisor_sum_list(N - 1);
-    let mut abundants = [false; N];
+pub fn p023() -> Answer {
+    p023_with(28123)
+}
+
+/// Sum of the positive integers up to `limit` that are not the sum of two abundant numbers
+pub fn p023_with(limit: usize) -> Answer {
+    let n = limit + 1;
+    let factor_sums = eu::divisor_sum_list(limit);
+    let mut abundants = vec![false; n];
     for (i, item) in factor_sums.iter().enumerate() {
         abundants[i] = *item > i;
     }
 
     let mut sum = 0;
-    for i in 0..N {
-        for (j, abundant) in abundants.iter().enumerate().take(N) {
+    for i in 0..n {
+        for (j, abundant) in abundants.iter().enumerate().take(n) {
             if *abundant {
                 if j >= i {
                     sum += i;
@@ -85,14 +97,18 @@ pub fn p023() -> String {
         }
     }
 
-    assert_eq!(sum, 4179871);
-    format!("p023 = {}", sum)
-} // 4179871
+    Answer::from(sum)
+}
 
 /// Lexicographic permutations
-pub fn p024() -> String {
+pub fn p024() -> Answer {
+    p024_with(1_000_000)
+}
+
+/// The `n`th lexicographic permutation of the digits 0 to 9, `n` from 1 to 10!
+pub fn p024_with(n: usize) -> Answer {
     let mut res: Vec<usize> = Vec::new();
-    let mut n = 1_000_000;
+    let mut n = n;
     let mut a = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
     let mut e = a.len();
 
@@ -116,15 +132,19 @@ pub fn p024() -> String {
         tt.push_str(&*v.to_string())
     }
 
-    assert_eq!(tt, "2783915460".to_string());
-    format!("p024 = {}", tt)
-} // 2783915460
+    Answer::from_digits(&tt)
+}
 
 /// 1000-digit Fibonacci number
-pub fn p025() -> String {
+pub fn p025() -> Answer {
+    p025_with(1000)
+}
+
+/// Index of the first term in the Fibonacci sequence to contain `digits` digits, `digits` at least 1
+pub fn p025_with(digits: usize) -> Answer {
     let bu10 = 10.to_biguint().unwrap();
     let mut limit: BigUint = One::one();
-    for _ in 0..999 {
+    for _ in 1..digits {
         limit = limit * &bu10
     }
     let mut a: BigUint = Zero::zero();
@@ -136,12 +156,16 @@ pub fn p025() -> String {
         cnt += 1;
     }
 
-    assert_eq!(cnt, 4782);
-    format!("p025 = {}", cnt)
-} // 4782
+    Answer::from(cnt)
+}
 
 /// Reciprocal cycles
-pub fn p026() -> String {
+pub fn p026() -> Answer {
+    p026_with(1000)
+}
+
+/// Value of d below `limit` for which 1/d has the longest recurring cycle, 0 when no d has one
+pub fn p026_with(limit: usize) -> Answer {
     fn repeat_cnt(n: usize) -> usize {
         let mut cnt = 2;
         if n % 5 != 0 {
@@ -155,7 +179,7 @@ pub fn p026() -> String {
     }
 
     let (mut max, mut idx, mut i) = (0, 0, 3);
-    while i < 1000 {
+    while i < limit {
         let current = repeat_cnt(i);
         if current > max {
             max = current;
@@ -164,17 +188,22 @@ pub fn p026() -> String {
         i += 2;
     }
 
-    assert_eq!(idx, 983);
-    format!("p026 = {}", idx)
-} // 983
+    Answer::from(idx)
+}
 
 /// Quadratic primes
-pub fn p027() -> String {
-    fn eval_quad(a: i32, b: i32, sieve: &primal::Sieve) -> i32 {
+pub fn p027() -> Answer {
+    p027_with(1000)
+}
+
+/// Product of the coefficients of n^2 + an + b, |a| < `limit` and |b| <= `limit`, producing the most
+/// primes for consecutive n from 0
+pub fn p027_with(limit: usize) -> Answer {
+    fn eval_quad(a: i64, b: i64) -> i64 {
         let mut cnt = 0;
         for i in 0.. {
             let v = i * i + a * i + b;
-            if v > 0 && sieve.is_prime(v as usize) {
+            if v > 0 && primal::is_prime(v as u64) {
                 cnt += 1
             } else {
                 break;
@@ -183,67 +212,155 @@ pub fn p027() -> String {
         cnt
     }
 
-    let sieve = primal::Sieve::new(15_000);
+    // b is the value at n = 0, so a prime
+    let sieve = primal::Sieve::new(limit + 1);
+    let limit = limit as i64;
     let mut max = 0;
-    let mut ab: (i32, i32) = (0, 0);
-    for a in -1000..1001 {
-        for b in 1..1001 {
-            if sieve.is_prime(b) {
-                let t = eval_quad(a, b as i32, &sieve);
-                if t > max {
-                    max = t;
-                    ab = (a, b as i32);
-                }
+    let mut ab: (i64, i64) = (0, 0);
+    for a in 1 - limit..limit {
+        for b in sieve.primes_from(0).take_while(|&b| b as i64 <= limit) {
+            let t = eval_quad(a, b as i64);
+            if t > max {
+                max = t;
+                ab = (a, b as i64);
             }
         }
     }
 
     let res = ab.0 * ab.1;
-    assert_eq!(res, -59231);
-    format!("p027 = {}", res)
-} // -59231
+    Answer::from(res)
+}
 
 /// Number spiral diagonals
-pub fn p028() -> String {
-    let sum = (3..1002).step(2).fold(1, |acc, i| acc + 4 * i * i - 6 * (i - 1));
-    assert_eq!(sum, 669171001);
-    format!("p028 = {}", sum)
-} // 669171001
+pub fn p028() -> Answer {
+    p028_with(1001)
+}
+
+/// Sum of the numbers on the diagonals of a `size` by `size` spiral, `size` odd; an even size sums the
+/// spiral one smaller
+pub fn p028_with(size: usize) -> Answer {
+    let sum = (3..size + 1).step(2).fold(1, |acc, i| acc + 4 * i * i - 6 * (i - 1));
+    Answer::from(sum)
+}
 
 /// Distinct powers
-pub fn p029() -> String {
+pub fn p029() -> Answer {
+    p029_with(100)
+}
+
+/// Number of distinct terms a^b for 2 <= a <= `limit` and 2 <= b <= `limit`
+pub fn p029_with(limit: usize) -> Answer {
+    // a^b written as root^(k * b) with root the smallest number a is a power k of, equal terms then coincide
+    let mut roots = vec![(0, 0); limit + 1];
+    for a in 2..limit + 1 {
+        if roots[a].0 == 0 {
+            let (mut x, mut k) = (a, 1);
+            while x <= limit {
+                roots[x] = (a, k);
+                x = match x.checked_mul(a) {
+                    Some(x) => x,
+                    None => break,
+                };
+                k += 1;
+            }
+        }
+    }
+
     let mut map = HashSet::new();
-    for a in 2..101 {
-        for b in 2..101 {
-            map.insert(((b as f64) * (a as f64).ln()).to_string());
+    for &(root, k) in roots.iter().skip(2) {
+        for b in 2..limit + 1 {
+            map.insert((root, k * b));
         }
     }
 
-    assert_eq!(map.len(), 9240);
-    format!("p029 = {}", map.len())
-} // 9240
+    Answer::from(map.len())
+}
 
 /// Digit fifth powers
-pub fn p030() -> String {
-    const B: u32 = 5;
+pub fn p030() -> Answer {
+    p030_with(5)
+}
+
+/// Sum of the numbers, 1 excluded, that are the sum of the `power`th powers of their digits
+pub fn p030_with(power: usize) -> Answer {
+    let b = power as u32;
 
-    let sum_pow5 = |mut n: u32| -> u32 {
+    let sum_pow = |mut n: u64| -> u64 {
         let mut sum = 0;
         while n != 0 {
-            sum += (n % 10).pow(B);
+            sum += (n % 10).pow(b);
             n /= 10
         }
         sum
     };
 
-    let max = (9 as u32).pow(B) * (B - 1);
-    let res = (2..max).fold(0, |acc, x| if sum_pow5(x) == x { acc + x } else { acc });
-    assert_eq!(res, 443839);
-    format!("p030 = {}", res)
-} // 443839
+    // a number of d digits has a digit power sum of at most d * 9^b, which falls behind 10^(d-1) for large d
+    let mut digits = 1;
+    while 10u64.pow(digits) <= u64::from(digits + 1) * 9u64.pow(b) {
+        digits += 1;
+    }
+    let max = u64::from(digits) * 9u64.pow(b);
+    let res = (2..max + 1).fold(0, |acc, x| if sum_pow(x) == x { acc + x } else { acc });
+    Answer::from(res)
+}
 
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-    // Euler solutions in this crate.
-    (21, vec![p021, p022, p023, p024, p025, p026, p027, p028, p029, p030])
+solutions! {
+    #![euler(problems = 21..=30)]
+
+    #[euler(problem = 21, title = "Amicable numbers")]
+    p021
+        .params(
+            vec![Param::new("limit", 10_000, "sum the amicable numbers below limit").at_most(10_000_000)],
+            |v| p021_with(v[0] as usize)
+        ),
+    #[euler(problem = 22, title = "Names scores")]
+    p022.data("p022_names.txt", p022_from),
+    #[euler(problem = 23, title = "Non-abundant sums")]
+    p023
+        .params(
+            vec![Param::new("limit", 28123, "integers up to limit").at_most(1_000_000)],
+            |v| p023_with(v[0] as usize)
+        ),
+    #[euler(problem = 24, title = "Lexicographic permutations")]
+    p024
+        .params(
+            vec![Param::new("n", 1_000_000, "the nth permutation").at_least(1).at_most(3_628_800)],
+            |v| p024_with(v[0] as usize)
+        ),
+    #[euler(problem = 25, title = "1000-digit Fibonacci number")]
+    p025
+        .params(
+            vec![Param::new("digits", 1000, "first term with this many digits").at_least(1).at_most(10_000)],
+            |v| p025_with(v[0] as usize)
+        ),
+    #[euler(problem = 26, title = "Reciprocal cycles")]
+    p026
+        .params(
+            vec![Param::new("limit", 1000, "d below limit").at_most(100_000)],
+            |v| p026_with(v[0] as usize)
+        ),
+    #[euler(problem = 27, title = "Quadratic primes")]
+    p027
+        .params(
+            vec![Param::new("limit", 1000, "|a| < limit and |b| <= limit").at_most(10_000)],
+            |v| p027_with(v[0] as usize)
+        ),
+    #[euler(problem = 28, title = "Number spiral diagonals")]
+    p028
+        .params(
+            vec![Param::new("size", 1001, "spiral of size by size").at_most(2_000_001)],
+            |v| p028_with(v[0] as usize)
+        ),
+    #[euler(problem = 29, title = "Distinct powers")]
+    p029
+        .params(
+            vec![Param::new("limit", 100, "a and b from 2 to limit").at_most(2000)],
+            |v| p029_with(v[0] as usize)
+        ),
+    #[euler(problem = 30, title = "Digit fifth powers")]
+    p030
+        .params(
+            vec![Param::new("power", 5, "sum of the powers of the digits").at_least(1).at_most(7)],
+            |v| p030_with(v[0] as usize)
+        ),
 }
@@ -10,28 +10,34 @@ extern crate primal;
 extern crate num;
 use num::integer::gcd;
 
+#[macro_use]
 extern crate euler_library;
+use euler_library::solution::{Answer, Param};
 use euler_library::common as eu;
 
 /// Coin sums
-pub fn p031() -> String {
-    let mut ws = vec![0; 201];
+pub fn p031() -> Answer {
+    p031_with(200)
+}
+
+/// Number of ways to make `pence` pence from any number of UK coins
+pub fn p031_with(pence: usize) -> Answer {
+    let mut ws: Vec<usize> = vec![0; pence + 1];
     ws[0] = 1;
     let pences = vec![1, 2, 5, 10, 20, 50, 100, 200];
     for v in pences {
-        for (j, _) in ws.clone().iter().enumerate().take(201).skip(v) {
+        for (j, _) in ws.clone().iter().enumerate().take(pence + 1).skip(v) {
             // for j in v..201 {
             ws[j] += ws[j - v]
         }
     }
 
     let res = *ws.last().unwrap();
-    assert_eq!(res, 73682);
-    format!("p031 = {}", res)
-} // 73682
+    Answer::from(res)
+}
 
 /// Pandigital products
-pub fn p032() -> String {
+pub fn p032() -> Answer {
     let mut m: HashSet<usize> = HashSet::new();
     // 1-digit * 4-digits in map to avoid dups
     for i in 2..10 {
@@ -60,12 +66,11 @@ pub fn p032() -> String {
     }
 
     let sum = m.iter().fold(0, |acc, x| acc + x);
-    assert_eq!(sum, 45228);
-    format!("p032 = {}", sum)
-} // 45228
+    Answer::from(sum)
+}
 
 /// Digit cancelling fractions
-pub fn p033() -> String {
+pub fn p033() -> Answer {
     fn is_digit_canceling_fraction(n: u32, d: u32) -> bool {
         let ns = format!("{}", n).into_bytes();
         let ds = format!("{}", d).into_bytes();
@@ -90,12 +95,11 @@ pub fn p033() -> String {
     }
 
     let answer = prod_den / gcd(prod_num, prod_den);
-    assert_eq!(answer, 100);
-    format!("p033 = {}", answer)
-} // 100
+    Answer::from(answer)
+}
 
 /// Digit factorials
-pub fn p034() -> String {
+pub fn p034() -> Answer {
     let fact = [1, 1, 2, 6, 24, 120, 720, 5040, 40320, 362880];
 
     let is_digit_fact = |n: usize| -> bool {
@@ -109,12 +113,16 @@ pub fn p034() -> String {
 
     // max value 5*9! = 1814400 < 10^7
     let sum = (10..1814401).fold(0, |acc, x| if is_digit_fact(x) { acc + x } else { acc });
-    assert_eq!(sum, 40730);
-    format!("p034 = {}", sum)
-} // 40730
+    Answer::from(sum)
+}
 
 /// Circular primes
-pub fn p035() -> String {
+pub fn p035() -> Answer {
+    p035_with(1_000_000)
+}
+
+/// Number of circular primes below `limit`
+pub fn p035_with(limit: usize) -> Answer {
     fn rotate(mut vec: Vec<u8>) -> Vec<u8> {
         let first = vec.remove(0);
         vec.push(first);
@@ -125,7 +133,8 @@ pub fn p035() -> String {
         eu::from_bytes(&rotate(eu::to_bytes(n))).unwrap()
     }
 
-    let sieve = primal::Sieve::new(1_000_000);
+    // rotations keep the number of digits, but may reach past limit
+    let sieve = primal::Sieve::new(10usize.pow(limit.saturating_sub(1).to_string().len() as u32));
 
     let is_circular_prime = |n: usize| -> bool {
         if sieve.is_prime(n) {
@@ -147,21 +156,26 @@ pub fn p035() -> String {
         false
     };
 
-    let mut sum = 0;
+    // 2, the only even prime, counted apart
+    let mut sum = if limit > 2 { 1 } else { 0 };
     let mut i = 1;
-    while i < 1_000_000 {
+    while i < limit {
         if is_circular_prime(i) {
             sum += 1
         }
         i += 2;
     }
 
-    assert_eq!(sum + 1, 55);
-    format!("p035 = {}", sum + 1)
-} // 55
+    Answer::from(sum)
+}
 
 /// Double-base palindromes
-pub fn p036() -> String {
+pub fn p036() -> Answer {
+    p036_with(1_000_000)
+}
+
+/// Sum of the numbers below `limit` that are palindromic in base 10 and base 2
+pub fn p036_with(limit: usize) -> Answer {
     fn is_palindrome_base10(n: usize) -> bool {
         let mut rev = 0;
         let mut num = n;
@@ -182,14 +196,19 @@ pub fn p036() -> String {
         0
     }
 
-    let a = (1..1000000).fold(0,
+    let a = (1..limit).fold(0,
                               |acc, x| if is_palindrome_base10(x) { acc + is_palindrome_binary(x) } else { acc });
-    assert_eq!(a, 872187);
-    format!("p036 = {}", a)
-} // 872187
+    Answer::from(a)
+}
 
 /// Truncatable primes
-pub fn p037() -> String {
+pub fn p037() -> Answer {
+    p037_with(11)
+}
+
+/// Sum of the first `count` primes that stay prime truncated from the left and from the right, `count` at
+/// most 11, as there are no more
+pub fn p037_with(count: usize) -> Answer {
     fn trunc_left(n: usize) -> usize {
         let mut xs = eu::to_bytes(n);
         if xs.len() < 2 {
@@ -238,18 +257,17 @@ pub fn p037() -> String {
         if is_trunc_prime(i) {
             trunc_primes.push(i)
         }
-        if trunc_primes.len() == 11 {
+        if trunc_primes.len() == count {
             break;
         }
     }
 
     let sum = trunc_primes.iter().fold(0, |acc, x| acc + x);
-    assert_eq!(sum, 748317);
-    format!("p037 = {}", sum)
-} // 748317
+    Answer::from(sum)
+}
 
 /// Pandigital multiples
-pub fn p038() -> String {
+pub fn p038() -> Answer {
     // 9 * (1,2,3,4,5)
     let mut max = String::from("918273645");
 
@@ -271,20 +289,24 @@ pub fn p038() -> String {
         }
     }
 
-    assert_eq!(max, "932718654".to_string());
-    format!("p038 = {}", max)
-} // 932718654
+    Answer::from_digits(&max)
+}
 
 /// Integer right triangles
-pub fn p039() -> String {
-    let mut ps = vec![0; 1001];
+pub fn p039() -> Answer {
+    p039_with(1000)
+}
 
-    for a in 3..999 {
-        for b in (a + 1)..999 {
+/// Perimeter p <= `limit` with the most right angle triangles of integral sides, 0 when there is none
+pub fn p039_with(limit: usize) -> Answer {
+    let mut ps = vec![0; limit + 1];
+
+    for a in 3..limit {
+        for b in (a + 1)..limit {
             let hypot = ((a * a + b * b) as f64).sqrt();
             if hypot - hypot.floor() == 0.0 {
                 let p = a + b + (hypot as usize);
-                if p > 1000 {
+                if p > limit {
                     break;
                 }
                 ps[p] += 1
@@ -293,6 +315,9 @@ pub fn p039() -> String {
     }
 
     let max = ps.iter().max().unwrap();
+    if *max == 0 {
+        return Answer::from(0);
+    }
     let mut res = 0;
     for (i, p) in ps.iter().enumerate().skip(3) {
         // for i in 3..ps.len() {
@@ -303,31 +328,78 @@ pub fn p039() -> String {
 
     }
 
-    assert_eq!(res, 840);
-    format!("p039 = {}", res)
-} // 840
+    Answer::from(res)
+}
 
 /// Champernowne's constant
-pub fn p040() -> String {
+pub fn p040() -> Answer {
+    p040_with(1_000_000)
+}
+
+/// Product of the digits d1 × d10 × d100 × ... of Champernowne's constant at the positions up to `limit`,
+/// `limit` at least 1
+pub fn p040_with(limit: usize) -> Answer {
     let (mut prod, mut cnt, mut next) = (1, 1, 1);
     for i in 1.. {
         let ds = eu::to_bytes(i);
         if next >= cnt && next < (cnt + ds.len()) {
             prod *= ds[next - cnt] - 48;
             next *= 10;
-            if next > 1_000_000 {
+            if next > limit {
                 break;
             }
         }
         cnt += ds.len();
     }
 
-    assert_eq!(prod, 210);
-    format!("p040 = {}", prod)
-} // 210
+    Answer::from(prod)
+}
 
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-    // Euler solutions in this crate.
-    (31, vec![p031, p032, p033, p034, p035, p036, p037, p038, p039, p040])
+solutions! {
+    #![euler(problems = 31..=40)]
+
+    #[euler(problem = 31, title = "Coin sums")]
+    p031
+        .params(
+            vec![Param::new("pence", 200, "ways to make this many pence").at_most(40_536)],
+            |v| p031_with(v[0] as usize)
+        ),
+    #[euler(problem = 32, title = "Pandigital products")]
+    p032,
+    #[euler(problem = 33, title = "Digit cancelling fractions")]
+    p033,
+    #[euler(problem = 34, title = "Digit factorials")]
+    p034,
+    #[euler(problem = 35, title = "Circular primes")]
+    p035
+        .params(
+            vec![Param::new("limit", 1_000_000, "circular primes below limit").at_most(100_000_000)],
+            |v| p035_with(v[0] as usize)
+        ),
+    #[euler(problem = 36, title = "Double-base palindromes")]
+    p036
+        .params(
+            vec![Param::new("limit", 1_000_000, "palindromes below limit").at_most(1_000_000_000)],
+            |v| p036_with(v[0] as usize)
+        ),
+    #[euler(problem = 37, title = "Truncatable primes")]
+    p037
+        .params(
+            vec![Param::new("count", 11, "first count truncatable primes").at_least(1).at_most(11)],
+            |v| p037_with(v[0] as usize)
+        ),
+    #[euler(problem = 38, title = "Pandigital multiples")]
+    p038,
+    #[euler(problem = 39, title = "Integer right triangles")]
+    p039
+        .params(
+            vec![Param::new("limit", 1000, "perimeters up to limit").at_most(20_000)],
+            |v| p039_with(v[0] as usize)
+        ),
+    #[euler(problem = 40, title = "Champernowne's constant")]
+    p040
+        .params(
+            vec![Param::new("limit", 1_000_000, "digits at the powers of 10 up to limit").at_least(1).at_most(100_000_000)],
+            |v| p040_with(v[0] as usize)
+        ),
 }
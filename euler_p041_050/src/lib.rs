@@ -10,12 +10,15 @@ extern crate primal;
 extern crate permutohedron;
 use permutohedron::Heap;
 
+#[macro_use]
 extern crate euler_library;
+use euler_library::solution::{Answer, Param};
 use euler_library::common as eu;
 use euler_library::primes;
+use euler_library::data::{self, ParseError};
 
 /// Pandigital prime
-pub fn p041() -> String {
+pub fn p041() -> Answer {
     const LIMIT: usize = 10_000_000;
     let sieve = primal::Sieve::new(LIMIT);
 
@@ -28,21 +31,21 @@ pub fn p041() -> String {
         i -= 2;
     }
 
-    assert_eq!(i, 7652413);
-    format!("p041 = {}", i)
-} // 7652413
+    Answer::from(i)
+}
 
 /// Coded triangle numbers
-pub fn p042() -> String {
-    fn get_data() -> Vec<Vec<u8>> {
-        let buffer = include_str!("../data/p042_words.txt")
-                         .chars()
-                         .filter(|&x| x != '\"' && x != '\n' && x != '\"')
-                         .collect::<String>();
-
-        buffer.split(',')
-              .map(|x| eu::to_bytes(x).into_iter().map(|y| y - 64).collect::<Vec<_>>())
-              .collect::<Vec<_>>()
+pub fn p042() -> Answer {
+    p042_from(include_str!("../data/p042_words.txt")).unwrap()
+}
+
+/// Number of triangle words among the quoted, comma separated upper case words in `text`
+pub fn p042_from(text: &str) -> Result<Answer, ParseError> {
+    fn get_data(text: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+        let words = data::words(text)?;
+        Ok(words.iter()
+                .map(|x| eu::to_bytes(x).into_iter().map(|y| y - 64).collect::<Vec<_>>())
+                .collect::<Vec<_>>())
     }
 
     fn is_triangle_num(n: f64) -> bool {
@@ -51,7 +54,7 @@ pub fn p042() -> String {
         (quad.floor() - quad).abs() < EPSILON
     }
 
-    let names = get_data();
+    let names = get_data(text)?;
     let mut cnt = 0;
     for name in names {
         let sum = name.iter().fold(0, |acc, x| acc + x);
@@ -61,12 +64,11 @@ pub fn p042() -> String {
         }
     }
 
-    assert_eq!(cnt, 162);
-    format!("p042 = {}", cnt)
-} // 162
+    Ok(Answer::from(cnt))
+}
 
 /// Sub-string divisibility
-pub fn p043() -> String {
+pub fn p043() -> Answer {
 
     // 4 hefty helper functions.  very fast solution - .006 sec
     fn get_next_grp(xss: Vec<Vec<char>>) -> Vec<Vec<char>> {
@@ -184,20 +186,25 @@ pub fn p043() -> String {
     let list = final_reduce(res);
     let sum = list.iter().fold(0, |acc, x| acc + x);
 
-    assert_eq!(sum, 16695334890);
-    format!("p043 = {}", sum)
-} // 16695334890
+    Answer::from(sum)
+}
 
 /// Pentagon numbers
-pub fn p044() -> String {
+pub fn p044() -> Answer {
+    p044_with(10_000)
+}
+
+/// Difference of the first pair of pentagonal numbers `P_k < P_j`, `j` below `limit`, whose sum and difference
+/// are pentagonal, 0 when there is none
+pub fn p044_with(limit: usize) -> Answer {
     fn is_pent(x: usize) -> bool {
         let n = (1.0 + (1.0 + 24.0 * x as f64).sqrt()) / 6.0;
         n - n.floor() < EPSILON
     }
 
-    fn solve() -> usize {
+    fn solve(limit: usize) -> usize {
         // first value hit will be the smallest
-        for (idx, j) in (1..).map(|x| (x, x * (3 * x - 1) / 2)) {
+        for (idx, j) in (1..limit).map(|x| (x, x * (3 * x - 1) / 2)) {
             for k in (1..idx).rev().map(|x| x * (3 * x - 1) / 2) {
                 if is_pent(j - k) && is_pent(j + k) {
                     return j - k;
@@ -207,18 +214,24 @@ pub fn p044() -> String {
         0
     }
 
-    let res = solve();
-    assert_eq!(res, 5482660);
-    format!("p044 = {}", res)
-} // 5482660
+    let res = solve(limit);
+    Answer::from(res)
+}
 
 /// Triangular, pentagonal, and hexagonal
-pub fn p045() -> String {
-    // ignore triangles, all hexagonals are triangles
-    fn solve() -> usize {
+pub fn p045() -> Answer {
+    p045_with(40755)
+}
 
-        let mut pent_stream = (166..).map(|x| x * (3 * x - 1) / 2).filter(|x| x % 5 == 0);
-        let mut hex_stream = (144..).map(|x| x * (2 * x - 1)).filter(|x| x % 5 == 0);
+/// First number above `after` that is triangular, pentagonal and hexagonal
+pub fn p045_with(after: usize) -> Answer {
+    // ignore triangles, all hexagonals are triangles
+    fn solve(after: usize) -> usize {
+        // start just below the first pentagonal and hexagonal numbers above after
+        let pent_start = ((after as f64 / 1.5).sqrt() as usize).max(1);
+        let hex_start = ((after as f64 / 2.0).sqrt() as usize).max(1);
+        let mut pent_stream = (pent_start..).map(|x| x * (3 * x - 1) / 2).filter(|&x| x > after);
+        let mut hex_stream = (hex_start..).map(|x| x * (2 * x - 1)).filter(|&x| x > after);
 
         let mut pent = 0;
         loop {
@@ -233,13 +246,12 @@ pub fn p045() -> String {
 
     }
 
-    let pent = solve();
-    assert_eq!(pent, 1533776805);
-    format!("p045 = {}", pent)
-} // 1533776805
+    let pent = solve(after);
+    Answer::from(pent)
+}
 
 /// Goldbach's other conjecture
-pub fn p046() -> String {
+pub fn p046() -> Answer {
     let sieve = primal::Sieve::new(10_000);
     let mut i = 9;
     loop {
@@ -259,47 +271,55 @@ pub fn p046() -> String {
         i += 2;
     }
 
-    assert_eq!(i, 5777);
-    format!("p046 = {}", i)
-} // 5777
+    Answer::from(i)
+}
 
 /// Distinct primes factors
-pub fn p047() -> String {
-    fn four_distinct() -> usize {
-        let pfcs = primes::prime_factor_cnt(200_000);
+pub fn p047() -> Answer {
+    p047_with(4)
+}
+
+/// First of the first `n` consecutive integers to have `n` distinct prime factors each, `n` at least 1
+pub fn p047_with(n: usize) -> Answer {
+    fn n_distinct(n: usize, limit: usize) -> Option<usize> {
+        let pfcs = primes::prime_factor_cnt(limit);
         let pfc = |n: usize| pfcs[n];
 
-        for i in 646..200_000 {
-            if pfc(i) == 4 && (1..4).all(|j| pfc(i + j) == 4) {
-                return i;
-            }
-        }
-        0
+        (2..limit - n + 1).find(|&i| (0..n).all(|j| pfc(i + j) == n))
     }
 
-    let res = four_distinct();
-    assert_eq!(res, 134043);
-    format!("p047 = {}", res)
-} // 134043
+    // the search range is doubled until it holds the run
+    let mut limit = 200_000;
+    loop {
+        if let Some(res) = n_distinct(n, limit) {
+            return Answer::from(res);
+        }
+        limit *= 2
+    }
+}
 
 /// Self powers
-pub fn p048() -> String {
+pub fn p048() -> Answer {
+    p048_with(1000)
+}
+
+/// Last ten digits of the series 1^1 + 2^2 + 3^3 + ... + `n`^`n`
+pub fn p048_with(n: usize) -> Answer {
     let digs = 10_000_000_000;
     let mut res: usize = 0;
-    for i in 1..1_000 {
+    for i in 1..n + 1 {
         let mut term = 1;
         for _ in 1..i + 1 {
             term = (term * i) % digs;
         }
-        res += term;
+        res = (res + term) % digs;
     }
 
-    assert_eq!(res % digs, 9110846700);
-    format!("p048 = {:?}", res % digs)
-} // 9110846700
+    Answer::from(res % digs)
+}
 
 /// Prime permutations
-pub fn p049() -> String {
+pub fn p049() -> Answer {
     fn find_delta(xs: Vec<i32>, pos: usize) -> Option<i32> {
         let mut delta;
         for i in pos..xs.len() - 1 {
@@ -355,15 +375,17 @@ pub fn p049() -> String {
         }
     }
 
-    assert_eq!(res, "296962999629".to_string());
-    format!("p049 = {}", res)
-} // 296962999629
+    Answer::from_digits(&res)
+}
 
 /// Consecutive prime sum
-pub fn p050() -> String {
-    const MAX: usize = 1_000_001;
+pub fn p050() -> Answer {
+    p050_with(1_000_000)
+}
 
-    let sieve = primal::Sieve::new(MAX + 1);
+/// Prime below `limit` written as the sum of the most consecutive primes, `limit` at least 3
+pub fn p050_with(limit: usize) -> Answer {
+    let sieve = primal::Sieve::new(limit);
     let mut cum_primes: Vec<(usize, usize)> = Vec::new();
 
     let count_primes = |mut xs: Vec<(usize, usize)>| -> (usize, usize) {
@@ -385,10 +407,10 @@ pub fn p050() -> String {
     };
 
     cum_primes.push((2, 2));
-    for i in 3..MAX + 1 {
+    for i in 3..limit {
         if sieve.is_prime(i) {
             let sum = cum_primes.last().unwrap().1 + i;
-            if sum < MAX {
+            if sum < limit {
                 cum_primes.push((i, sum))
             }
         }
@@ -404,13 +426,47 @@ pub fn p050() -> String {
         cum_primes.remove(0);
     }
 
-    assert_eq!(max_prime, 997651);
-    format!("p050 = {}", max_prime)
-} // 997651
+    Answer::from(max_prime)
+}
 
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-    // Euler solutions in this crate.
-    (41,
-     vec![p041, p042, p043, p044, p045, p046, p047, p048, p049, p050])
+solutions! {
+    #![euler(problems = 41..=50)]
+
+    #[euler(problem = 41, title = "Pandigital prime")]
+    p041,
+    #[euler(problem = 42, title = "Coded triangle numbers")]
+    p042.data("p042_words.txt", p042_from),
+    #[euler(problem = 43, title = "Sub-string divisibility")]
+    p043,
+    #[euler(problem = 44, title = "Pentagon numbers")]
+    p044
+        .params(
+            vec![Param::new("limit", 10_000, "pentagonal numbers P_j, j below limit").at_most(1_000_000)],
+            |v| p044_with(v[0] as usize)
+        ),
+    #[euler(problem = 45, title = "Triangular, pentagonal, and hexagonal")]
+    p045
+        .params(
+            vec![Param::new("after", 40755, "first such number above after").at_most(2_172_315_626_468_283_464)],
+            |v| p045_with(v[0] as usize)
+        ),
+    #[euler(problem = 46, title = "Goldbach's other conjecture")]
+    p046,
+    #[euler(problem = 47, title = "Distinct primes factors")]
+    p047
+        .params(
+            vec![Param::new("n", 4, "n consecutive numbers with n distinct prime factors").at_least(1).at_most(4)],
+            |v| p047_with(v[0] as usize)
+        ),
+    #[euler(problem = 48, title = "Self powers")]
+    p048
+        .params(vec![Param::new("n", 1000, "series up to n^n").at_most(20_000)], |v| p048_with(v[0] as usize)),
+    #[euler(problem = 49, title = "Prime permutations")]
+    p049,
+    #[euler(problem = 50, title = "Consecutive prime sum")]
+    p050
+        .params(
+            vec![Param::new("limit", 1_000_000, "prime below limit").at_least(3).at_most(100_000_000)],
+            |v| p050_with(v[0] as usize)
+        ),
 }
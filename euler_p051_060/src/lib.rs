@@ -8,12 +8,14 @@ extern crate num;
 use num::{BigUint, pow};
 use num::bigint::ToBigUint;
 
+#[macro_use]
 extern crate euler_library;
+use euler_library::solution::{Answer, Param};
 use euler_library::common as eu;
-use euler_library::cards::{Card, Hand, char_to_suit, char_to_val};
+use euler_library::data::{self, ParseError};
 
 /// Prime digit replacements
-pub fn p051() -> String {
+pub fn p051() -> Answer {
     // assume solution set has:
     // 6 digits, 3 'same' digits that change,
     // ends in fixed number
@@ -83,12 +85,16 @@ pub fn p051() -> String {
         }
     }
 
-    format!("p051 = {}", res.unwrap())
-} // 121313
+    Answer::from(res.unwrap())
+}
 
 /// Permuted multiples
-pub fn p052() -> String {
+pub fn p052() -> Answer {
+    p052_with(6)
+}
 
+/// Smallest positive x such that 2x, 3x, ..., `multiples`x contain the same digits, `multiples` from 1 to 6
+pub fn p052_with(multiples: usize) -> Answer {
     fn same_digits(a: usize, b: usize) -> bool {
         let mut xs = eu::to_bytes(a);
         xs.sort();
@@ -97,114 +103,78 @@ pub fn p052() -> String {
         xs == ys
     }
 
-    fn get_permuted(xs: Vec<usize>) -> Option<usize> {
+    fn get_permuted(xs: Vec<usize>, multiples: usize) -> Option<usize> {
         for v in xs {
-            if !same_digits(v, 2 * v) {
-                continue;
-            }
-            if !same_digits(v, 3 * v) {
-                continue;
-            }
-            if !same_digits(v, 4 * v) {
-                continue;
-            }
-            if !same_digits(v, 5 * v) {
-                continue;
-            }
-            if same_digits(v, 6 * v) {
+            if (2..multiples + 1).all(|m| same_digits(v, m * v)) {
                 return Some(v);
             }
         }
         None
     }
 
-    let mut res = Some(0);
-    let mut min = 1_000;
-    let mut max = 10_000;
-    for _ in 1..5 {
-        let xs = (min..max).filter(|&x| x / min == 1).collect::<Vec<_>>();
-        res = get_permuted(xs);
-        if res != None {
-            break;
-        }
+    let mut res = None;
+    let mut min = 1;
+    let mut max = 10;
+    while res == None {
+        // multiples times x has as many digits as x
+        let xs = (min..max).filter(|&x| x * multiples < max).collect::<Vec<_>>();
+        res = get_permuted(xs, multiples);
         min *= 10;
         max *= 10;
     }
 
-    format!("p052 = {}", res.unwrap())
-} // 142857
+    Answer::from(res.unwrap())
+}
 
 /// Combinatoric selections
-pub fn p053() -> String {
+pub fn p053() -> Answer {
+    p053_with(100, 1_000_000)
+}
+
+/// Number of values of n C r, for 1 <= n <= `n`, greater than `threshold`
+pub fn p053_with(n: usize, threshold: usize) -> Answer {
     // combinations n C r
     fn ncr_recur(n: usize, r: usize) -> usize {
         if r == 0 { 1 } else { ncr_recur(n - 1, r - 1) * n / r }
     }
 
-    // total available = 23+24+..+98+99+100 = 4797
-    let mut cnt = 4797;
-    for n in 23..101 {
+    // rows before the first with a value over threshold, 23 for a million, have none
+    let first = (1..).find(|&m| ncr_recur(m, m / 2) > threshold).unwrap();
+
+    // total available = first + (first + 1) + .. + n, 23 + 24 + .. + 100 = 4797
+    let mut cnt = (first..n + 1).fold(0, |acc, m| acc + m);
+    for m in first..n + 1 {
         for r in 1.. {
-            if ncr_recur(n, r) <= 1_000_000 { cnt -= 1 } else { break };
+            if ncr_recur(m, r) <= threshold { cnt -= 1 } else { break };
         }
-        for r in (1..n + 1).rev() {
-            if ncr_recur(n, r) <= 1_000_000 { cnt -= 1 } else { break };
+        for r in (1..m + 1).rev() {
+            if ncr_recur(m, r) <= threshold { cnt -= 1 } else { break };
         }
     }
-    format!("p053 = {}", cnt)
-} // 4075
+    Answer::from(cnt)
+}
 
 
 /// Poker hands
-pub fn p054() -> String {
-    fn get_data() -> Vec<char> {
-        let buffer = include_str!("../data/p054_hands.txt");
-        buffer.chars().filter(|&x| x != ' ' && x != '\n').collect::<Vec<char>>()
-    }
-
-    fn get_hand(mut cs: Vec<char>) -> Hand {
-        let mut cards: Vec<Card> = Vec::new();
-        for _ in 0..5 {
-            let suit = char_to_suit(cs.pop().unwrap());
-            let val = char_to_val(cs.pop().unwrap());
-            cards.push(Card { suit: suit, val: val })
-        }
-        Hand { cards: cards }
-    }
-
-    fn get_hands() -> Vec<Hand> {
-        let hands_chars = get_data();
-        let mut cs: Vec<char> = Vec::new();
-        let mut hands: Vec<Hand> = Vec::new();
-        for v in hands_chars {
-            cs.push(v);
-            if cs.len() == 10 {
-                hands.push(get_hand(cs.clone()));
-                cs.clear();
-            }
-        }
-        hands.reverse();
-        hands
-    }
+pub fn p054() -> Answer {
+    p054_from(include_str!("../data/p054_hands.txt")).unwrap()
+}
 
-    // main program
-    let mut hands = get_hands();
-    let mut cnt = 0;
-    while hands.len() > 1 {
-        let a = hands.pop().unwrap();
-        let b = hands.pop().unwrap();
-        let ra = a.get_rank();
-        let rb = b.get_rank();
-        if ra > rb {
-            cnt += 1;
-        }
-    }
-    assert_eq!(cnt, 376);
-    format!("p054 = {}", cnt)
-} // 376
+/// Number of hands player 1 wins in `text`, lines of ten space separated cards such as
+/// `8C TS KC 9H 4S 7D 2S 5D 3S AC`
+pub fn p054_from(text: &str) -> Result<Answer, ParseError> {
+    let hands = data::hands(text)?;
+    let cnt = hands.iter().filter(|&&(ref a, ref b)| a.get_rank() > b.get_rank()).count();
+    Ok(Answer::from(cnt))
+}
 
 /// Lychrel numbers
-pub fn p055() -> String {
+pub fn p055() -> Answer {
+    p055_with(10_000)
+}
+
+/// Number of Lychrel numbers below `limit`
+pub fn p055_with(limit: usize) -> Answer {
     fn is_lychrel(n: usize) -> bool {
         let mut x: BigUint = n.to_biguint().unwrap().clone();
         for _ in 1..50 {
@@ -219,15 +189,29 @@ pub fn p055() -> String {
         true
     }
 
-    let cnt = (1..10_000).fold(0, |acc, x| if is_lychrel(x) { acc + 1 } else { acc });
-    format!("p055 = {}", cnt)
-} // 249
+    let cnt = (1..limit).fold(0, |acc, x| if is_lychrel(x) { acc + 1 } else { acc });
+    Answer::from(cnt)
+}
 
 /// Powerful digit sum
-pub fn p056() -> String {
+pub fn p056() -> Answer {
+    p056_with(100)
+}
+
+/// Maximum digital sum of a^b, for a, b < `n`
+pub fn p056_with(n: usize) -> Answer {
+    // from the largest a and b down, as far as a^b has enough digits to beat the maximum, 90 to 99 for 100
+    let enough_digits = |a: usize, b: usize, max: usize| 9 * ((b as f64 * (a as f64).log10()) as usize + 1) > max;
+
     let mut max = 0;
-    for i in (90..100).rev() {
-        for b in (90..100).rev() {
+    for i in (1..n).rev() {
+        if !enough_digits(i, n - 1, max) {
+            break;
+        }
+        for b in (1..n).rev() {
+            if !enough_digits(i, b, max) {
+                break;
+            }
             let a = i.to_biguint().unwrap();
             let digs = eu::to_bytes(pow(a.clone(), b));
             let cnt = digs.iter().fold(0, |acc, x| acc + (*x - 48) as usize);
@@ -237,29 +221,40 @@ pub fn p056() -> String {
         }
 
     }
-    format!("p056 = {}", max)
-} // 972
+    Answer::from(max)
+}
 
 /// Square root convergents
-pub fn p057() -> String {
+pub fn p057() -> Answer {
+    p057_with(1000)
+}
+
+/// Number of the first `n` expansions of the square root of two with a numerator of more digits than the
+/// denominator
+pub fn p057_with(n: usize) -> Answer {
     let mut cnt = 0;
-    let mut n = 1.to_biguint().unwrap();
-    let mut d = 2.to_biguint().unwrap();;
-    for _ in 1..1000 {
-        if (&n + &d).to_string().len() > d.to_string().len() {
+    let mut num = 1.to_biguint().unwrap();
+    let mut d = 2.to_biguint().unwrap();
+    for _ in 0..n {
+        if (&num + &d).to_string().len() > d.to_string().len() {
             cnt += 1;
         }
-        let new_n = d.clone();
-        d = 2.to_biguint().unwrap() * d + n;
-        n = new_n;
+        let new_num = d.clone();
+        d = 2.to_biguint().unwrap() * d + num;
+        num = new_num;
     }
 
 
-    format!("p057 = {}", cnt)
-} // 153
+    Answer::from(cnt)
+}
 
 /// Spiral primes
-pub fn p058() -> String {
+pub fn p058() -> Answer {
+    p058_with(10)
+}
+
+/// Side length of the first square spiral with less than `percent` % of primes along both diagonals
+pub fn p058_with(percent: usize) -> Answer {
     let get_result = || -> usize {
         let mut prime_cnt = 0;
         let mut factor = 2;
@@ -272,21 +267,25 @@ pub fn p058() -> String {
                 v += factor;
             }
             factor += 2;
-            let side = (v as f32).sqrt();
-            let tot_diag = (2.0 * side - 1.0) * 0.1;
-            if (prime_cnt as f32) < tot_diag {
+            let side = factor - 1;
+            if prime_cnt * 100 < (2 * side - 1) * percent as u64 {
                 return side as usize;
             }
         }
     };
 
     let res = get_result();
-    assert_eq!(res, 26241);
-    format!("p058 = {}", res)
-} // 26241
+    Answer::from(res)
+}
 
 /// XOR decryption
-pub fn p059() -> String {
+pub fn p059() -> Answer {
+    p059_from(include_str!("../data/p059_cipher.txt")).unwrap()
+}
+
+/// Sum of the ASCII values of the message in `text`, comma separated character codes encrypted with a
+/// key of three lower case letters
+pub fn p059_from(text: &str) -> Result<Answer, ParseError> {
     fn decode(msg: &[u8], key: &[u8]) -> Vec<u8> {
         msg.iter()
             .zip(key.iter()
@@ -296,19 +295,8 @@ pub fn p059() -> String {
             .collect::<Vec<u8>>()
     }
 
-    fn get_msg() -> Vec<u8> {
-        let buffer = include_str!("../data/p059_cipher.txt")
-            .chars()
-            .filter(|&x| x != '\"' && x != '\n')
-            .collect::<String>();
-
-        buffer.split(',')
-            .map(|x| x.parse::<u8>().unwrap())
-            .collect()
-    }
-
     let comb = eu::perms_without_reps_recur(3, &"abcdefghijklmnopqrstuvwxyz".to_string().into_bytes());
-    let msg = get_msg();
+    let msg = data::rows::<u8>(text)?.concat();
     let mut res: usize = 0;
     for v in comb {
         let decrypted = decode(&msg, &v);
@@ -318,13 +306,19 @@ pub fn p059() -> String {
             break;
         }
     }
-    assert_eq!(res, 107359);
-    format!("p059 = {}", res)
-} // 107359
+    Ok(Answer::from(res))
+}
 
 /// Prime pair sets
-pub fn p060() -> String {
-    fn eval(w: &[usize], k: usize, s: usize, sieve: &primal::Sieve) -> usize {
+pub fn p060() -> Answer {
+    p060_with(5)
+}
+
+/// Lowest sum of a set of `size` primes below 10,000 of which any two concatenate to a prime in either order,
+/// 0 when there is none
+pub fn p060_with(size: usize) -> Answer {
+    // keeps searching after the first set found for one of a lower sum, as long as one can still be found
+    fn eval(w: &[usize], k: usize, s: usize, sieve: &primal::Sieve, best: &mut usize) {
         let ok = |a, b| {
             let mut ten = 1;
             while ten <= b {
@@ -334,22 +328,22 @@ pub fn p060() -> String {
         };
 
         if k == 0 {
-            return s;
+            *best = s;
+            return;
         }
 
-        let mut sum = 0;
         for (i, &wi) in w.iter().enumerate() {
+            // the k primes left are at least wi each
+            if s + k * wi >= *best {
+                break;
+            }
             let w2 = w[i + 1..]
                 .iter()
                 .cloned()
                 .filter(|&wj| ok(wi, wj) && ok(wj, wi))
                 .collect::<Vec<_>>();
-            sum = eval(&w2, k - 1, s + wi, sieve);
-            if sum != 0 {
-                break;
-            }
+            eval(&w2, k - 1, s + wi, sieve, best);
         }
-        sum
     }
 
     let sieve = primal::Sieve::new(100_000_005);
@@ -357,12 +351,60 @@ pub fn p060() -> String {
         .take_while(|&p| p < 10_000)
         .collect::<Vec<_>>();
 
-    let sum = eval(&some_primes, 5, 0, &sieve);
-    format!("p060 = {}", sum)
-} // 26033
+    let mut sum = usize::MAX;
+    eval(&some_primes, size, 0, &sieve, &mut sum);
+    Answer::from(if sum == usize::MAX { 0 } else { sum })
+}
 
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-    // Euler solutions in this crate.
-    (51, vec![p051, p052, p053, p054, p055, p056, p057, p058, p059, p060])
+solutions! {
+    #![euler(problems = 51..=60)]
+
+    #[euler(problem = 51, title = "Prime digit replacements")]
+    p051,
+    #[euler(problem = 52, title = "Permuted multiples")]
+    p052
+        .params(
+            vec![Param::new("multiples", 6, "multiples up to this times x").at_least(1).at_most(6)],
+            |v| p052_with(v[0] as usize)
+        ),
+    #[euler(problem = 53, title = "Combinatoric selections")]
+    p053
+        .params(
+            vec![
+                Param::new("n", 100, "values of n C r for n up to n").at_most(3000),
+                Param::new("threshold", 1_000_000, "count the values over threshold").at_least(1).at_most(1_000_000_000_000),
+            ],
+            |v| p053_with(v[0] as usize, v[1] as usize)
+        ),
+    #[euler(problem = 54, title = "Poker hands")]
+    p054.data("p054_hands.txt", p054_from),
+    #[euler(problem = 55, title = "Lychrel numbers")]
+    p055
+        .params(
+            vec![Param::new("limit", 10_000, "Lychrel numbers below limit").at_most(1_000_000)],
+            |v| p055_with(v[0] as usize)
+        ),
+    #[euler(problem = 56, title = "Powerful digit sum")]
+    p056
+        .params(vec![Param::new("n", 100, "a^b for a, b < n").at_most(500)], |v| p056_with(v[0] as usize)),
+    #[euler(problem = 57, title = "Square root convergents")]
+    p057
+        .params(
+            vec![Param::new("n", 1000, "first n expansions").at_most(10_000)],
+            |v| p057_with(v[0] as usize)
+        ),
+    #[euler(problem = 58, title = "Spiral primes")]
+    p058
+        .params(
+            vec![Param::new("percent", 10, "ratio of primes below percent").at_least(9).at_most(100)],
+            |v| p058_with(v[0] as usize)
+        ),
+    #[euler(problem = 59, title = "XOR decryption")]
+    p059.data("p059_cipher.txt", p059_from),
+    #[euler(problem = 60, title = "Prime pair sets")]
+    p060
+        .params(
+            vec![Param::new("size", 5, "sets of size primes").at_least(1).at_most(5)],
+            |v| p060_with(v[0] as usize)
+        ),
 }
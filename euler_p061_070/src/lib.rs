@@ -13,17 +13,26 @@ extern crate num;
 use num::BigUint;
 use num::bigint::ToBigUint;
 
+#[macro_use]
 extern crate euler_library;
+use euler_library::solution::{Answer, Param};
 use euler_library::common as eu;
 use euler_library::big as eu_big;
+use euler_library::data::{self, ParseError};
 
 /// Cyclical figurate numbers
-pub fn p061() -> String {
+pub fn p061() -> Answer {
+    p061_with(6)
+}
+
+/// Lowest sum of a cyclic set of different 4-digit numbers with one of each of the first `types` polygonal
+/// types, triangle, square, pentagonal and so on to octagonal, `types` from 3 to 6
+pub fn p061_with(types: usize) -> Answer {
     fn is_cyclic(left: usize, right: usize) -> bool {
         right / 100 == left % 100
     }
 
-    fn get_polygonals() -> Vec<Vec<usize>> {
+    fn get_polygonals(types: usize) -> Vec<Vec<usize>> {
         fn f3(n: usize) -> usize {
             n * (n + 1) / 2
         };
@@ -45,6 +54,7 @@ pub fn p061() -> String {
         let fs: Vec<fn(usize) -> usize> = vec![f3, f4, f5, f6, f7, f8];
 
         fs.iter()
+            .take(types)
             .map(|f| {
                 (1..)
                     .map(f)
@@ -73,77 +83,91 @@ pub fn p061() -> String {
     }
 
     fn eval(pss: Vec<Vec<usize>>) -> usize {
-        let p = &vec![0, 1, 2, 3, 4, 5];
+        let p = &(0..pss.len()).collect::<Vec<_>>();
         let perms = eu::perms_without_reps_recur(p.len(), p);
+        // fewer than 6 types may make more than one set
+        let mut min = 0;
         for perm in perms {
             let mut list: Vec<Vec<usize>> = Vec::new();
             for p in perm {
                 list = add_to_set(list, pss[p].clone());
             }
-            let res = list.iter()
-                .filter(|xs| is_cyclic(xs[xs.len() - 1], xs[0]))
-                .collect::<Vec<_>>();
-            if res.len() == 1 && res[0].len() == p.len() {
-                // println!("{:?}", res);
-                return res[0].iter().fold(0, |acc, x| acc + x);
+            let sums = list.iter()
+                .filter(|xs| xs.len() == p.len() && is_cyclic(xs[xs.len() - 1], xs[0]))
+                .filter(|xs| xs.iter().all(|x| xs.iter().filter(|&y| y == x).count() == 1))
+                .map(|xs| xs.iter().fold(0, |acc, x| acc + x));
+            for sum in sums {
+                if min == 0 || sum < min {
+                    min = sum;
+                }
             }
         }
-        0
+        min
     }
 
-    let res = eval(get_polygonals());
-    assert_eq!(res, 28684);
-    format!("p061 = {}", eval(get_polygonals()))
-} // 28684
+    Answer::from(eval(get_polygonals(types)))
+}
 
 /// Cubic permutations
-pub fn p062() -> String {
-    let mut xs: Vec<(usize, usize)> = Vec::new();
-    for i in 300..10000 {
-        let cube = i * i * i;
-        let mut ds = eu::to_bytes(cube);
-        ds.sort();
-        // pad front of number with '9' so '0's not lost
-        ds.insert(0, 57 as u8);
-        xs.push((eu::from_bytes(&ds).unwrap(), cube));
-    }
+pub fn p062() -> Answer {
+    p062_with(5)
+}
 
-    xs.sort();
-    let grouped = xs.into_iter().group_by(|&(k, _)| k);
+/// Smallest cube for which exactly `perms` permutations of its digits are cubes, `perms` at least 1
+pub fn p062_with(perms: usize) -> Answer {
+    // permutations keep the number of digits, so the cubes are grouped one number of digits at a time
+    let mut i: usize = 1;
+    let mut max = 10;
+    loop {
+        let mut xs: Vec<(Vec<u8>, usize)> = Vec::new();
+        while i * i * i < max {
+            let cube = i * i * i;
+            let mut ds = eu::to_bytes(cube);
+            ds.sort();
+            xs.push((ds, cube));
+            i += 1;
+        }
 
-    let mut res: usize = 0;
-    for (_, group) in &grouped {
-        let t = group.into_iter().map(|x| x).collect::<Vec<_>>();
-        if t.len() == 5 {
-            res = t[0].1;
-            break;
+        xs.sort();
+        let grouped = xs.into_iter().group_by(|&(ref k, _)| k.clone());
+
+        let res = grouped.into_iter()
+            .map(|(_, group)| group.map(|(_, cube)| cube).collect::<Vec<_>>())
+            .filter(|t| t.len() == perms)
+            .map(|t| t[0])
+            .min();
+        if let Some(res) = res {
+            return Answer::from(res);
         }
+        max *= 10;
     }
-
-    assert_eq!(res, 127035954683);
-    format!("p062 = {}", res)
-} // 127035954683 == 5027^3
+}
 
 /// Powerful digit counts
-pub fn p063() -> String {
-    let mut cnt = 1;
+pub fn p063() -> Answer {
+    let mut cnt = 0;
     for m in 1..11 {
         for n in 1..100 {
-            let l = ((m as f64).powf(n as f64) as usize).to_string().len();
+            // digits of m^n counted from its logarithm, as m^n itself overflows a usize beyond 9^20
+            let l = (n as f64 * (m as f64).log10()).floor() as usize + 1;
             if l == n {
                 cnt += 1;
             }
         }
     }
 
-    assert_eq!(cnt, 49);
-    format!("p063 = {}", cnt)
-} // 49
+    Answer::from(cnt)
+}
 
 /// Odd period square roots
-pub fn p064() -> String {
-    fn is_odd_period(n: u32) -> bool {
-        let a0 = (n as f32).sqrt() as u32;
+pub fn p064() -> Answer {
+    p064_with(10_000)
+}
+
+/// Number of continued fractions of the square roots of N <= `n` with an odd period
+pub fn p064_with(n: usize) -> Answer {
+    fn is_odd_period(n: u64) -> bool {
+        let a0 = (n as f64).sqrt() as u64;
         if a0 * a0 == n {
             return false;
         }
@@ -160,19 +184,24 @@ pub fn p064() -> String {
         period % 2 == 1
     }
 
-    let cnt = (1..10001).fold(0, |acc, n| if is_odd_period(n) { acc + 1 } else { acc });
-    assert_eq!(cnt, 1322);
-    format!("p064 = {}", cnt)
-} // 1322
+    let cnt = (1..n as u64 + 1).fold(0, |acc, n| if is_odd_period(n) { acc + 1 } else { acc });
+    Answer::from(cnt)
+}
 
 /// Convergents of e
-pub fn p065() -> String {
+pub fn p065() -> Answer {
+    p065_with(100)
+}
+
+/// Sum of the digits of the numerator of the `n`th convergent of the continued fraction for e, `n` at
+/// least 1
+pub fn p065_with(n: usize) -> Answer {
     let e = [1].iter()
         .cycle()
         .enumerate()
         .skip(2)
         .map(|(idx, &x)| if idx % 3 == 0 { idx / 3 * 2 } else { x })
-        .take(99)
+        .take(n - 1)
         .collect::<Vec<_>>();
 
     let (n, _) = eu_big::continued_fraction(2, e);
@@ -182,40 +211,58 @@ pub fn p065() -> String {
         .iter()
         .fold(0 as u32, |acc, &x| acc + (x as u32) - 48);
 
-    assert_eq!(sum, 272);
-    format!("p065 = {}", sum)
-} // 272
+    Answer::from(sum)
+}
 
-/// Diophantine equation
-pub fn p066() -> String {
-    // pell's equation x^2 -Dy^2
-    // sqrt(D) expansion and test
-    fn pell_min(d: usize) -> BigUint {
-        let d_big = d.to_biguint().unwrap();
-        let sqrt = eu::sqrt_terms(d);
-        if sqrt == None {
-            return 0.to_biguint().unwrap();
-        }
-        let (base, repeat) = sqrt.unwrap();
-        let mut ys_iter = repeat.iter().cycle();
-        let mut ys: Vec<usize> = Vec::new();
-        let one = 1.to_biguint().unwrap();
-        loop {
-            ys.push(*ys_iter.next().unwrap());
-            let (num, den) = eu_big::continued_fraction(base, ys.clone());
-            if &num * &num == &d_big * &den * &den + &one {
-                return num;
-            }
+// pell's equation x^2 -Dy^2
+// sqrt(D) expansion and test
+fn pell_min(d: usize) -> BigUint {
+    let d_big = d.to_biguint().unwrap();
+    let sqrt = eu::sqrt_terms(d);
+    if sqrt == None {
+        return 0.to_biguint().unwrap();
+    }
+    let (base, repeat) = sqrt.unwrap();
+    let mut ys_iter = repeat.iter().cycle();
+    let mut ys: Vec<usize> = Vec::new();
+    let one = 1.to_biguint().unwrap();
+    loop {
+        ys.push(*ys_iter.next().unwrap());
+        let (num, den) = eu_big::continued_fraction(base, ys.clone());
+        if &num * &num == &d_big * &den * &den + &one {
+            return num;
         }
     }
+}
 
-    assert!(pell_min(61).to_string() == 1766319049.to_string());
-    assert!(pell_min(67).to_string() == 48842.to_string());
+/// Checks the examples given in the statement of p066
+pub fn p066_examples() {
+    // the minimal solutions in x for D = 2, 3, 5, 6 and 7
+    let xs = [2, 3, 5, 6, 7].iter().map(|&d| pell_min(d).to_string()).collect::<Vec<_>>();
+    assert_eq!(xs, ["3", "2", "9", "5", "8"]);
+    assert_eq!(p066_with(7), Answer::from(5));
+}
+
+/// Diophantine equation
+pub fn p066() -> Answer {
+    p066_with(1000)
+}
 
+/// D <= `max_d` for which the minimal solution of x^2 - Dy^2 = 1 has the largest x
+///
+/// ```
+/// use euler_p061_070::p066_with;
+///
+/// // x = 649 for D = 13, x = 1766319049 for D = 61, out of reach of a search of x; D = 67 only has x = 48842
+/// assert_eq!(p066_with(13).to_string(), "13");
+/// assert_eq!(p066_with(67).to_string(), "61");
+/// ```
+pub fn p066_with(max_d: usize) -> Answer {
     let mut max = (0, 1.to_biguint().unwrap());
     // https://oeis.org/A033316
-    // largest D for min x < 542
-    for i in 541..1001 {
+    // 541 sets a record for min x, so no smaller D can win once it is in range
+    let first = if max_d >= 541 { 541 } else { 2 };
+    for i in first..max_d + 1 {
         let val = pell_min(i);
         if val > max.1 {
             max.0 = i;
@@ -224,36 +271,28 @@ pub fn p066() -> String {
 
     }
 
-    assert_eq!(max.0, 661);
-    format!("p066 = {}", max.0)
-} // 661
+    Answer::from(max.0)
+}
 
 /// Maximum path sum II
-pub fn p067() -> String {
-    fn get_data() -> Vec<Vec<u32>> {
-        let buffer = include_str!("../data/p067_triangle.txt");
-        buffer.lines()
-            .map(|x| {
-                x.split(' ')
-                    .map(|x| x.parse().unwrap())
-                    .collect::<Vec<u32>>()
-            })
-            .collect()
-    }
+pub fn p067() -> Answer {
+    p067_from(include_str!("../data/p067_triangle.txt")).unwrap()
+}
 
-    let mut xss = get_data();
+/// Maximum total from top to bottom of the triangle in `text`, rows of space separated numbers
+pub fn p067_from(text: &str) -> Result<Answer, ParseError> {
+    let mut xss = data::triangle::<u32>(text)?;
     for i in (0..xss.len()).rev() {
         for j in 0..i {
             xss[i - 1][j] += cmp::max(xss[i][j], xss[i][j + 1])
         }
     }
 
-    assert_eq!(xss[0][0], 7273);
-    format!("p067 = {}", xss[0][0])
-} // 7273
+    Ok(Answer::from(xss[0][0]))
+}
 
 /// Magic 5-gon ring
-pub fn p068() -> String {
+pub fn p068() -> Answer {
 
     #[derive(Debug, Clone, PartialEq, Ord, Eq, PartialOrd)]
     struct Ring {
@@ -336,58 +375,124 @@ pub fn p068() -> String {
 
     ress.sort();
     let res = ress.last().unwrap();
-    assert_eq!(*res, "6531031914842725".to_string());
-    format!("p068 = {}", res)
-} // 6531031914842725
+    Answer::from_digits(res)
+}
 
 /// Totient maximum
-pub fn p069() -> String {
+pub fn p069() -> Answer {
+    p069_with(1_000_000)
+}
+
+/// n <= `limit` for which n/φ(n) is a maximum, `limit` at least 1
+pub fn p069_with(limit: usize) -> Answer {
     let mut max = 1.0;
     let mut idx = 1;
-    for (i, x) in eu::phis(1_000_000).iter().enumerate().skip(1) {
+    for (i, x) in eu::phis(limit).iter().enumerate().skip(1) {
         if i as f64 / *x as f64 > max {
             max = i as f64 / *x as f64;
             idx = i;
         }
     }
 
-    assert_eq!(idx, 510510);
-    format!("p069 = {}", idx)
-} // 510510
+    Answer::from(idx)
+}
 
 /// Totient permutation
-pub fn p070() -> String {
-    let primes = primal::Primes::all().take(10_000).collect::<Vec<_>>();
+pub fn p070() -> Answer {
+    p070_with(10_000_000)
+}
 
-    let mut best = 1;
-    let mut best_ratio: f64 = 1_000.0;
-    let limit = 10_000_000;
-    for (i, vi) in primes.iter().enumerate().take(500).skip(1) {
-        for vj in primes.iter().take(500).skip(i + 1) {
-            let n = vi * vj;
-            if n > limit {
-                break;
+/// Totient permutation of `n` up to `limit` with the minimal ratio n/φ(n), the least such `n` on a tie
+///
+/// ```
+/// use euler_p061_070::{p070_with, p070a_with};
+///
+/// // 2817 = 3^2 * 313 is the answer up to 2990, a prime square factor winning over two-prime products
+/// for &limit in &[1, 20, 21, 100, 2816, 2817, 2990, 2991, 10_000, 123_456, 1_000_000, 3_000_000] {
+///     assert_eq!(p070_with(limit), p070a_with(limit), "limit {}", limit);
+/// }
+/// ```
+pub fn p070_with(limit: usize) -> Answer {
+    struct Search {
+        limit: usize,
+        sieve: primal::Sieve,
+        // (n, φ(n)) of the best permutation found, and its ratio
+        best: Option<(usize, usize)>,
+        best_ratio: f64,
+    }
+
+    impl Search {
+        // Visits every n * q^k with q a prime in `min_q..=max_q`, largest q first, and its multiples by
+        // larger primes. n/φ(n) = Π p/(p-1) over the distinct primes p of n only grows as primes are added
+        // or shrink, so a prime not beating the best ratio ends the search of n's multiples
+        fn visit(&mut self, n: usize, phi: usize, ratio: f64, min_q: usize, max_q: usize) {
+            for q in (min_q..max_q + 1).rev() {
+                let ratio = ratio * q as f64 / (q - 1) as f64;
+                // the slack keeps n whose ratio equals the best, computed with other roundings, for the tie
+                if ratio > self.best_ratio * (1.0 + 1e-12) {
+                    break;
+                }
+                if !self.sieve.is_prime(q) {
+                    continue;
+                }
+                let (mut m, mut f) = (n * q, phi * (q - 1));
+                loop {
+                    self.consider(m, f, ratio);
+                    self.visit(m, f, ratio, q + 1, self.limit / m);
+                    if m > self.limit / q {
+                        break;
+                    }
+                    m *= q;
+                    f *= q;
+                }
             }
-            let phi = (vi - 1) * (vj - 1);
-            let ratio = n as f64 / phi as f64;
-            if best_ratio > ratio && eu::is_perm(n, phi) {
-                best = n;
-                best_ratio = ratio;
+        }
+
+        fn consider(&mut self, n: usize, phi: usize, ratio: f64) {
+            // n/φ against best_n/best_φ, compared exactly
+            let better = match self.best {
+                Some((b, b_phi)) => {
+                    let (x, y) = (n as u128 * b_phi as u128, b as u128 * phi as u128);
+                    x < y || (x == y && n < b)
+                }
+                None => true,
+            };
+            if better && eu::is_perm(n, phi) {
+                self.best = Some((n, phi));
+                self.best_ratio = ratio;
             }
         }
     }
 
-    assert_eq!(best, 8319823);
-    format!("p070 = {}", best)
-} // 8319823
+    let mut search = Search {
+        limit,
+        sieve: primal::Sieve::new(limit / 2 + 1),
+        best: None,
+        best_ratio: f64::INFINITY,
+    };
+    // a prime p is never a permutation of p - 1, so n has a prime factor no larger than its square root
+    let root = (2..).take_while(|&p| p * p <= limit).last().unwrap_or(1);
+    search.visit(1, 1, 1.0, 2, root);
+
+    Answer::from(search.best.map_or(1, |(n, _)| n))
+}
 
 /// Totient permutation
 // first attempt; works, uses phis function and straight forward
 // but quite a bit slower
-pub fn p070a() -> String {
+pub fn p070a() -> Answer {
+    p070a_with(10_000_000)
+}
+
+/// Totient permutation like `p070_with`, searching every number up to `limit`, only the top fifth for the
+/// Euler problem's limit
+pub fn p070a_with(limit: usize) -> Answer {
+    // the minimum for 10^7 is known to lie in the top fifth; other limits have no such guarantee, and n = 1
+    // with its ratio of 1 is excluded by the problem
+    let skip = if limit == 10_000_000 { limit / 5 * 4 } else { 2 };
     let mut best = 1;
     let mut best_ratio: f64 = 1_000.0;
-    for (n, phi) in eu::phis(10_000_000).into_iter().enumerate().skip(8_000_000) {
+    for (n, phi) in eu::phis(limit).into_iter().enumerate().skip(skip) {
         let ratio = n as f64 / phi as f64;
         if best_ratio > ratio && eu::is_perm(n, phi) {
             best = n;
@@ -395,12 +500,66 @@ pub fn p070a() -> String {
         }
     }
 
-    assert_eq!(best, 8319823);
-    format!("p070a = {}", best)
-} // 8319823
+    Answer::from(best)
+}
 
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-    // Euler solutions in this crate.
-    (61, vec![p061, p062, p063, p064, p065, p066, p067, p068, p069, p070])
+solutions! {
+    #![euler(problems = 61..=70)]
+
+    #[euler(problem = 61, title = "Cyclical figurate numbers")]
+    p061
+        .params(
+            vec![Param::new("types", 6, "polygonal types from triangle up").at_least(3).at_most(6)],
+            |v| p061_with(v[0] as usize)
+        ),
+    #[euler(problem = 62, title = "Cubic permutations")]
+    p062
+        .params(
+            vec![Param::new("perms", 5, "exactly perms permutations are cubes").at_least(1).at_most(64)],
+            |v| p062_with(v[0] as usize)
+        ),
+    #[euler(problem = 63, title = "Powerful digit counts")]
+    p063,
+    #[euler(problem = 64, title = "Odd period square roots")]
+    p064
+        .params(
+            vec![Param::new("n", 10_000, "square roots of N <= n").at_most(1_000_000)],
+            |v| p064_with(v[0] as usize)
+        ),
+    #[euler(problem = 65, title = "Convergents of e")]
+    p065
+        .params(
+            vec![Param::new("n", 100, "nth convergent").at_least(1).at_most(10_000)],
+            |v| p065_with(v[0] as usize)
+        ),
+    #[euler(problem = 66, title = "Diophantine equation")]
+    p066
+        .examples(p066_examples)
+        .params(
+            vec![Param::new("max_d", 1000, "D up to max_d").at_most(5000)],
+            |v| p066_with(v[0] as usize)
+        ),
+    #[euler(problem = 67, title = "Maximum path sum II")]
+    p067.data("p067_triangle.txt", p067_from),
+    #[euler(problem = 68, title = "Magic 5-gon ring")]
+    p068,
+    #[euler(problem = 69, title = "Totient maximum")]
+    p069
+        .params(
+            vec![Param::new("limit", 1_000_000, "n up to limit").at_least(1).at_most(10_000_000)],
+            |v| p069_with(v[0] as usize)
+        ),
+    #[euler(problem = 70, title = "Totient permutation")]
+    p070
+        .params(
+            vec![Param::new("limit", 10_000_000, "search n up to limit").at_most(10_000_000_000)],
+            |v| p070_with(v[0] as usize)
+        ),
+    #[euler(problem = 70, title = "Totient permutation")]
+    p070a
+        .variant("a")
+        .params(
+            vec![Param::new("limit", 10_000_000, "search n up to limit").at_least(1).at_most(100_000_000)],
+            |v| p070a_with(v[0] as usize)
+        ),
 }
@@ -2,39 +2,81 @@
 //!
 //! This crate is designed to be used via crate `euler`.
 
+#[macro_use]
 extern crate euler_library;
+use euler_library::solution::{Answer, Param};
 use euler_library::common as eu;
 use euler_library::primes;
 use euler_library::big as eu_big;
 
 /// Ordered fractions
-pub fn p071() -> String {
-    let (mut answer, mut c) = (2, 5);
+pub fn p071() -> Answer {
+    p071_with(1_000_000)
+}
+
+/// Numerator of the fraction left of 3/7 among the reduced proper fractions with denominators up to
+/// `limit`, `limit` at least 5
+pub fn p071_with(limit: usize) -> Answer {
+    let (mut answer, mut c): (usize, usize) = (2, 5);
     let (b, d) = (3, 7);
-    while c + d <= 1_000_000 {
+    while c + d <= limit {
         answer += b;
         c += d;
     }
-    format!("p071 = {}", answer)
-} // 428570
+    Answer::from(answer)
+}
 
 /// Counting fractions
-pub fn p072() -> String {
-    let sum = eu::phis(1_000_000).iter().fold(0, |acc, x| acc + x);
-    format!("p072 = {}", sum - 1)
-} // 303963552391
+pub fn p072() -> Answer {
+    p072_with(1_000_000)
+}
+
+/// Number of reduced proper fractions with denominators up to `limit`, `limit` at least 1
+pub fn p072_with(limit: usize) -> Answer {
+    let sum = eu::phis(limit).iter().fold(0, |acc, x| acc + x);
+    Answer::from(sum - 1)
+}
 
 /// Counting fractions in a range
-pub fn p073() -> String {
-    let phi = eu::phis(12_000);
-    let c = phi.iter().skip(6).fold(0, |acc, x| acc + x);
-    // next line logic: c*1/2 - c*1/3 == c/6
-    let res = c / 6;
-    format!("p073 = {}", res - 2)
-} // 7295372
+pub fn p073() -> Answer {
+    p073_with(12_000)
+}
+
+/// Number of reduced proper fractions between 1/3 and 1/2 with denominators up to `limit`
+pub fn p073_with(limit: usize) -> Answer {
+    // fractions[m] counts the fractions n/d between 1/3 and 1/2 with d <= m, reduced or not
+    let mut fractions = vec![0; limit + 1];
+    for d in 1..limit + 1 {
+        fractions[d] = fractions[d - 1] + (d - 1) / 2 - d / 3;
+    }
+
+    // Möbius function by sieve
+    let mut mobius = vec![1i64; limit + 1];
+    let mut composite = vec![false; limit + 1];
+    for i in 2..limit + 1 {
+        if !composite[i] {
+            for j in (i..limit + 1).step_by(i) {
+                composite[j] = true;
+                mobius[j] = -mobius[j];
+            }
+            for j in (i * i..limit + 1).step_by(i * i) {
+                mobius[j] = 0;
+            }
+        }
+    }
+
+    // the fractions n/d of lowest terms n'/d' are the reduced ones with d' <= limit/k, k = d/d'
+    let res = (1..limit + 1).fold(0, |acc, k| acc + mobius[k] * fractions[limit / k] as i64);
+    Answer::from(res)
+}
 
 /// Digit factorial chains
-pub fn p074() -> String {
+pub fn p074() -> Answer {
+    p074_with(1_000_000, 60)
+}
+
+/// Number of digit factorial chains starting below `limit` with exactly `terms` non-repeating terms
+pub fn p074_with(limit: usize, terms: usize) -> Answer {
     static FACT_SMALL: [usize; 10] = [1, 1, 2, 6, 24, 120, 720, 5040, 40320, 362880];
 
     fn fact_sum(n: usize) -> usize {
@@ -47,9 +89,10 @@ pub fn p074() -> String {
         sum
     }
 
-    let mut cache: Vec<usize> = vec![0; 1_000_000];
+    // the cycles seeded below reach 363601
+    let mut cache: Vec<usize> = vec![0; limit.max(1_000_000)];
     cache[0] = 2;
-    cache[1] = 2;
+    cache[1] = 1;
     cache[2] = 1;
     cache[145] = 1;
     cache[169] = 3;
@@ -62,26 +105,33 @@ pub fn p074() -> String {
     cache[1454] = 3;
 
     let mut fact_non_repeat_cnt = |n: usize| -> usize {
+        if cache[n] != 0 {
+            return cache[n];
+        }
         let mut cnt = 0;
         let mut term = n;
         loop {
             term = fact_sum(term);
             cnt += 1;
-            if term < 1_000_000 && cache[term] != 0 {
+            if term < cache.len() && cache[term] != 0 {
                 cache[n] = cache[term] + cnt;
                 return cache[term] + cnt;
             }
         }
     };
 
-    let sum = (3..1_000_000).fold(0,
-                                  |acc, x| if fact_non_repeat_cnt(x) == 60 { acc + 1 } else { acc });
-    assert_eq!(sum, 402);
-    format!("p074 = {}", sum)
-} // 402
+    let sum = (1..limit).fold(0,
+                              |acc, x| if fact_non_repeat_cnt(x) == terms { acc + 1 } else { acc });
+    Answer::from(sum)
+}
 
 /// Singular integer right triangles
-pub fn p075() -> String {
+pub fn p075() -> Answer {
+    p075_with(1_500_000)
+}
+
+/// Number of wire lengths up to `n` that bend into exactly one integer sided right angle triangle
+pub fn p075_with(n: usize) -> Answer {
     fn pyth(n: usize) -> Vec<usize> {
         fn p(n: usize, a: usize, b: usize, c: usize) -> Vec<usize> {
             if a + b + c >= n {
@@ -109,7 +159,6 @@ pub fn p075() -> String {
         p(n, 3, 4, 5)
     }
 
-    let n: usize = 1_500_000;
     let list = pyth(n);
     let mut table: Vec<usize> = vec![0; n+1 as usize];
     for v in &list {
@@ -126,35 +175,48 @@ pub fn p075() -> String {
     }
 
     let res = table.into_iter().fold(0, |acc, x| if x == 1 { acc + x } else { acc });
-    assert_eq!(res, 161667);
-    format!("p075 = {}", res)
-} // 161667
+    Answer::from(res)
+}
 
-/// Counting summations
-pub fn p076() -> String {
-    fn solve(n: usize) -> usize {
-        if n == 0 {
-            return 0;
-        }
-        let mut sum = vec![0; n+1];
-        sum[0] = 1;
-        for i in 1..n {
-            for (j, _) in sum.clone().iter().enumerate().take(n + 1).skip(i) {
-                sum[j] += sum[j - i];
-            }
+// Number of ways n can be written as a sum of at least two positive integers
+fn count_sums(n: usize) -> usize {
+    if n == 0 {
+        return 0;
+    }
+    let mut sum = vec![0; n+1];
+    sum[0] = 1;
+    for i in 1..n {
+        for (j, _) in sum.clone().iter().enumerate().take(n + 1).skip(i) {
+            sum[j] += sum[j - i];
         }
-        sum[n]
     }
-    assert_eq!(solve(5), 6);
+    sum[n]
+}
+
+/// Checks the examples given in the statement of p076
+pub fn p076_examples() {
+    assert_eq!(count_sums(5), 6);
+}
 
-    let res = solve(100);
-    assert_eq!(res, 190569291);
-    format!("p076 = {:?}", res)
-} // 190569291
+/// Counting summations
+pub fn p076() -> Answer {
+    p076_with(100)
+}
+
+/// Number of ways `n` can be written as a sum of at least two positive integers
+pub fn p076_with(n: usize) -> Answer {
+    let res = count_sums(n);
+    Answer::from(res)
+}
 
 /// Prime summations
-pub fn p077() -> String {
-    fn prime_sumation() -> usize {
+pub fn p077() -> Answer {
+    p077_with(5000)
+}
+
+/// First value which can be written as the sum of primes in over `ways` different ways
+pub fn p077_with(ways: usize) -> Answer {
+    fn prime_sumation(ways: usize) -> usize {
         let mut ps: Vec<usize> = vec![0; 1001 as usize];
         for (i, _) in ps.clone().iter().enumerate().take(1000).skip(2) {
             let mut sum = 0;
@@ -162,50 +224,56 @@ pub fn p077() -> String {
                 sum += primes::sopf(j) * ps[i - j]
             }
             ps[i] = (primes::sopf(i) + sum) / i;
-            if ps[i] > 5000 {
+            if ps[i] > ways {
                 return i;
             }
         }
         0
     }
 
-    let res = prime_sumation();
-    assert_eq!(res, 71);
-    format!("p077 = {}", res)
-} // 71
+    let res = prime_sumation(ways);
+    Answer::from(res)
+}
 
 /// Coin partitions
-pub fn p078() -> String {
+pub fn p078() -> Answer {
+    p078_with(1_000_000)
+}
+
+/// Least n for which the number of partitions of n is divisible by `divisor`, `divisor` at least 1
+pub fn p078_with(divisor: usize) -> Answer {
     // custom version of euler_library::integer_partitions()
-    fn partitions() -> usize {
-        // list of pentagonal numbers
-        let k = (1..250)
-            .flat_map(|i| vec![i * (3 * i - 1) / 2, i * (3 * i - 1) / 2 + i])
-            .collect::<Vec<_>>();
+    fn partitions(divisor: i64) -> usize {
+        // list of generalized pentagonal numbers, extended as n grows
+        let mut k: Vec<usize> = vec![1, 2];
 
         let mut ps: Vec<i64> = vec![1];
         let sign: Vec<i64> = vec![1, 1, -1, -1];
         let mut n = 0;
         while ps[n] != 0 {
             n += 1;
+            if *k.last().unwrap() <= n {
+                let i = k.len() / 2 + 1;
+                k.push(i * (3 * i - 1) / 2);
+                k.push(i * (3 * i - 1) / 2 + i);
+            }
             let mut t: i64 = 0;
             let mut i = 0;
             while k[i] <= n {
                 t += (ps[n - k[i]] as i64) * sign[i % 4];
                 i += 1;
             }
-            ps.push(t % 1_000_000)
+            ps.push(t % divisor)
         }
         n
     }
 
-    let res = partitions();
-    assert_eq!(res, 55374);
-    format!("p078 = {}", res)
-} // 55374
+    let res = partitions(divisor as i64);
+    Answer::from(res)
+}
 
 /// Passcode derivation
-pub fn p079() -> String {
+pub fn p079() -> Answer {
     let mut xs = vec![319, 680, 180, 690, 129, 620, 762, 689, 762, 318, 368, 710, 720, 710, 629, 168, 160, 689, 716,
                       731, 736, 729, 316, 729, 729, 710, 769, 290, 719, 680, 318, 389, 162, 289, 162, 718, 729, 319,
                       790, 680, 890, 362, 319, 760, 316, 729, 380, 319, 728, 716];
@@ -245,26 +313,92 @@ pub fn p079() -> String {
     }
 
     let res = eu::from_bytes::<usize>(&set).unwrap();
-    assert_eq!(res, 73162890);
-    format!("p079 = {:?}", res)
-} // 73162890
+    Answer::from(res)
+}
 
 /// Square root digital expansion
-pub fn p080() -> String {
-    let perfect = vec![4, 9, 16, 25, 36, 49, 64, 81, 100];
-    let sum = (2..101)
-        .filter(|x| !perfect.contains(x))
+pub fn p080() -> Answer {
+    p080_with(100, 100)
+}
+
+/// Total of the digital sums of the first `digits` digits of the irrational square roots of the natural
+/// numbers up to `n`
+pub fn p080_with(n: usize, digits: usize) -> Answer {
+    let is_square = |x: usize| {
+        let r = (x as f64).sqrt() as usize;
+        (r.saturating_sub(1)..r + 2).any(|r| r * r == x)
+    };
+    let sum = (2..n + 1)
+        .filter(|&x| !is_square(x))
         .fold(0, |acc, x| {
-            let sqrt_str = eu_big::precision_sqrt(x, 100).to_string();
+            let sqrt_str = eu_big::precision_sqrt(x, digits).to_string();
             acc + eu::sum_of_digits(sqrt_str)
         });
 
-    assert_eq!(sum, 40886);
-    format!("p080 = {}", sum)
-} // 40886
+    Answer::from(sum)
+}
+
+solutions! {
+    #![euler(problems = 71..=80)]
 
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-    // Euler solutions in this crate.
-    (71, vec![p071, p072, p073, p074, p075, p076, p077, p078, p079, p080])
+    #[euler(problem = 71, title = "Ordered fractions")]
+    p071
+        .params(
+            vec![Param::new("limit", 1_000_000, "denominators up to limit").at_least(5).at_most(10_000_000_000)],
+            |v| p071_with(v[0] as usize)
+        ),
+    #[euler(problem = 72, title = "Counting fractions")]
+    p072
+        .params(
+            vec![Param::new("limit", 1_000_000, "denominators up to limit").at_least(1).at_most(10_000_000)],
+            |v| p072_with(v[0] as usize)
+        ),
+    #[euler(problem = 73, title = "Counting fractions in a range")]
+    p073
+        .params(
+            vec![Param::new("limit", 12_000, "denominators up to limit").at_most(10_000_000)],
+            |v| p073_with(v[0] as usize)
+        ),
+    #[euler(problem = 74, title = "Digit factorial chains")]
+    p074
+        .params(
+            vec![
+                Param::new("limit", 1_000_000, "chains starting below limit").at_most(10_000_000),
+                Param::new("terms", 60, "chains of exactly this many terms"),
+            ],
+            |v| p074_with(v[0] as usize, v[1] as usize)
+        ),
+    #[euler(problem = 75, title = "Singular integer right triangles")]
+    p075
+        .params(
+            vec![Param::new("n", 1_500_000, "wire lengths up to n").at_most(100_000_000)],
+            |v| p075_with(v[0] as usize)
+        ),
+    #[euler(problem = 76, title = "Counting summations")]
+    p076
+        .examples(p076_examples)
+        .params(vec![Param::new("n", 100, "write n as a sum").at_most(416)], |v| p076_with(v[0] as usize)),
+    #[euler(problem = 77, title = "Prime summations")]
+    p077
+        .params(
+            vec![Param::new("ways", 5000, "sums of primes in over this many ways").at_most(10_000_000_000_000_000)],
+            |v| p077_with(v[0] as usize)
+        ),
+    #[euler(problem = 78, title = "Coin partitions")]
+    p078
+        .params(
+            vec![Param::new("divisor", 1_000_000, "partitions divisible by divisor").at_least(1).at_most(1_000_000)],
+            |v| p078_with(v[0] as usize)
+        ),
+    #[euler(problem = 79, title = "Passcode derivation")]
+    p079,
+    #[euler(problem = 80, title = "Square root digital expansion")]
+    p080
+        .params(
+            vec![
+                Param::new("n", 100, "square roots of the numbers up to n").at_most(10_000),
+                Param::new("digits", 100, "digits of each square root").at_most(1000),
+            ],
+            |v| p080_with(v[0] as usize, v[1] as usize)
+        ),
 }
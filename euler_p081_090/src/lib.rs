@@ -2,7 +2,6 @@
 //!
 //! This crate is designed to be used via crate `euler`.
 use std::cmp;
-use std::f64::EPSILON;
 
 extern crate primal;
 
@@ -10,7 +9,7 @@ extern crate itertools;
 use itertools::Itertools;
 
 extern crate rand;
-use rand::{thread_rng, Rng};
+use rand::{thread_rng, Rng, SeedableRng, StdRng};
 
 extern crate numerals;
 use numerals::roman::Roman;
@@ -19,252 +18,216 @@ extern crate petgraph;
 use petgraph::*;
 use petgraph::algo::dijkstra;
 
-/// Returns data from matrix.txt. Used by p081, p082 and p083
-pub fn get_data() -> Vec<usize> {
-  let buffer = include_str!("../data/matrix.txt");
-  buffer
-    .lines()
-    .flat_map(|x| {
-      x.split(',')
-        .map(|x| x.parse().unwrap())
-        .collect::<Vec<usize>>()
-    })
-    .collect()
-}
+#[macro_use]
+extern crate euler_library;
+use euler_library::solution::{Answer, Param};
+use euler_library::data::{self, ParseError};
 
-/// Path sum: two ways
-pub fn p081() -> String {
-  fn get_edges(i: u32, ws: &[usize], cols: u32) -> Vec<(u32, u32, usize)> {
-    // first node
-    if i == 0 {
-      return vec![((i, i + 1, ws[i as usize]))];
-    }
-    let mut edges = Vec::new();
-    // right if not last col
-    if i % cols != 0 {
-      edges.push((i, i + 1, ws[i as usize]))
-    }
-    // down if not last row
-    if i + cols <= ws.len() as u32 {
-      edges.push((i, i + cols, ws[(i + cols - 1) as usize]))
-    }
-    edges
-  }
+/// Returns the square matrix in `text`, lines of comma separated numbers as in matrix.txt, row by row.
+/// Used by p081, p082 and p083
+pub fn get_data(text: &str) -> Result<Vec<usize>, ParseError> {
+  Ok(data::matrix(text)?.concat())
+}
 
-  fn make_graph(weights: Vec<usize>) -> Graph<usize, usize> {
-    let mut g = Graph::<usize, usize>::new();
-    for i in 0..weights.len() + 1 {
-      g.add_node(i);
-    }
-    let cols = (weights.len() as f64).sqrt() as u32;
-    let edges = (0..weights.len())
-      .flat_map(|i| get_edges(i as u32, &weights, cols))
-      .collect::<Vec<_>>();
+/// Returns the 5 by 5 example matrix of p081, p082 and p083
+pub fn get_example_data() -> Vec<usize> {
+  vec![
+    131, 673, 234, 103, 18,
+    201, 96, 342, 965, 150,
+    630, 803, 746, 422, 111,
+    537, 699, 497, 121, 956,
+    805, 732, 524, 37, 331,
+  ]
+}
 
-    g.extend_with_edges(&edges);
-    g
+// Node 0 is the start, node i the matrix element i - 1. Edges come from get_edges(i, weights, cols).
+fn make_graph(
+  weights: Vec<usize>,
+  get_edges: fn(u32, &[usize], u32) -> Vec<(u32, u32, usize)>,
+) -> Graph<usize, usize> {
+  let mut g = Graph::<usize, usize>::new();
+  for i in 0..weights.len() + 1 {
+    g.add_node(i);
   }
+  let cols = (weights.len() as f64).sqrt() as u32;
+  let edges = (0..weights.len())
+    .flat_map(|i| get_edges(i as u32, &weights, cols))
+    .collect::<Vec<_>>();
 
-  fn solve(g: Graph<usize, usize>, idxs: Vec<graph::NodeIndex>) -> usize {
-    let start_node = idxs[0];
-    let finish_node = idxs.last().unwrap();
-    let sol = dijkstra(
-      &g,                 // directed graph
-      start_node,         // start node
-      Some(*finish_node), // finishing node
-      |e| *e.weight(),    // edge cost
-    );
-
-    *sol.get(finish_node).unwrap()
-  }
+  g.extend_with_edges(&edges);
+  g
+}
 
-  // test
-  let xss = vec![
-    vec![131, 673, 234, 103, 18],
-    vec![201, 96, 342, 965, 150],
-    vec![630, 803, 746, 422, 111],
-    vec![537, 699, 497, 121, 956],
-    vec![805, 732, 524, 37, 331],
-  ];
-  let xs = xss.into_iter().flat_map(|x| x).collect();
-  let g = make_graph(xs);
+// Cost of the shortest path from the start node to the last node
+fn min_path_to_last(g: Graph<usize, usize>) -> usize {
   let idxs = g.node_indices().collect::<Vec<_>>();
-  let res = solve(g, idxs);
-  assert_eq!(res, 2427);
+  let start_node = idxs[0];
+  let finish_node = idxs.last().unwrap();
+  let sol = dijkstra(
+    &g,                 // directed graph
+    start_node,         // start node
+    Some(*finish_node), // finishing node
+    |e| *e.weight(),    // edge cost
+  );
+
+  *sol.get(finish_node).unwrap()
+}
 
-  let g = make_graph(get_data());
-  let idxs = g.node_indices().collect::<Vec<_>>();
-  let res = solve(g, idxs);
+// p081 moves: right and down
+fn p081_edges(i: u32, ws: &[usize], cols: u32) -> Vec<(u32, u32, usize)> {
+  // first node
+  if i == 0 {
+    return vec![((i, i + 1, ws[i as usize]))];
+  }
+  let mut edges = Vec::new();
+  // right if not last col
+  if i % cols != 0 {
+    edges.push((i, i + 1, ws[i as usize]))
+  }
+  // down if not last row
+  if i + cols <= ws.len() as u32 {
+    edges.push((i, i + cols, ws[(i + cols - 1) as usize]))
+  }
+  edges
+}
 
-  assert_eq!(res, 427337);
-  format!("p081 = {}", res)
-} // 427337
+/// Checks the examples given in the statement of p081
+pub fn p081_examples() {
+  assert_eq!(min_path_to_last(make_graph(get_example_data(), p081_edges)), 2427);
+}
 
-/// Path sum: three ways
-pub fn p082() -> String {
-  fn get_edges(i: u32, ws: &[usize], cols: u32) -> Vec<(u32, u32, usize)> {
-    // first nodes (whole first column)
-    if i == 0 {
-      return (0..ws.len())
-        .step(cols as usize)
-        .map(|idx| (0 as u32, (idx + 1) as u32, ws[idx]))
-        .collect_vec();
-    }
-    let mut edges = Vec::new();
-    // right if not last col
-    if i % cols != 0 {
-      edges.push((i, i + 1, ws[i as usize]))
-    }
-    // down if not last row
-    if i + cols <= ws.len() as u32 {
-      edges.push((i, i + cols, ws[(i + cols - 1) as usize]))
-    }
-    // up if not first row
-    if i > cols {
-      edges.push((i, i - cols, ws[(i - cols - 1) as usize]))
-    }
-    // up for last element
-    if i + 1 == ws.len() as u32 {
-      edges.push((i + 1, i + 1 - cols, ws[(i + 1 - cols - 1) as usize]))
-    }
-    edges
-  }
+/// Path sum: two ways
+pub fn p081() -> Answer {
+  p081_from(include_str!("../data/matrix.txt")).unwrap()
+}
 
-  fn make_graph(weights: Vec<usize>) -> Graph<usize, usize> {
-    let mut g = Graph::<usize, usize>::new();
-    for i in 0..weights.len() + 1 {
-      g.add_node(i);
-    }
-    let cols = (weights.len() as f64).sqrt() as u32;
-    let edges = (0..weights.len())
-      .flat_map(|i| get_edges(i as u32, &weights, cols))
-      .collect::<Vec<_>>();
+/// Minimal path sum moving right and down in the matrix in `text`, lines of comma separated numbers
+pub fn p081_from(text: &str) -> Result<Answer, ParseError> {
+  let res = min_path_to_last(make_graph(get_data(text)?, p081_edges));
+  Ok(Answer::from(res))
+}
 
-    g.extend_with_edges(&edges);
-    g
+// p082 moves: up, down and right, starting anywhere in the first column
+fn p082_edges(i: u32, ws: &[usize], cols: u32) -> Vec<(u32, u32, usize)> {
+  // first nodes (whole first column)
+  if i == 0 {
+    return (0..ws.len())
+      .step(cols as usize)
+      .map(|idx| (0 as u32, (idx + 1) as u32, ws[idx]))
+      .collect_vec();
   }
-
-  fn solve(g: Graph<usize, usize>, idxs: Vec<graph::NodeIndex>) -> (usize, usize) {
-    let start_node = idxs[0];
-    let solution = dijkstra(
-      &g,              // directed graph
-      start_node,      // start node
-      None,            // finishing node
-      |e| *e.weight(), // edge cost
-    );
-
-    let col = ((solution.len() - 1) as f64).sqrt() as usize;
-    let sol_vec = solution
-      .into_iter()
-      .filter_map(|(n, s)| {
-        if g[n] % col == 0 && s != 0 {
-          Some((s, g[n]))
-        } else {
-          None
-        }
-      })
-      .sorted();
-    sol_vec.into_iter().min().unwrap()
+  let mut edges = Vec::new();
+  // right if not last col
+  if i % cols != 0 {
+    edges.push((i, i + 1, ws[i as usize]))
+  }
+  // down if not last row
+  if i + cols <= ws.len() as u32 {
+    edges.push((i, i + cols, ws[(i + cols - 1) as usize]))
+  }
+  // up if not first row
+  if i > cols {
+    edges.push((i, i - cols, ws[(i - cols - 1) as usize]))
   }
+  // up for last element
+  if i + 1 == ws.len() as u32 {
+    edges.push((i + 1, i + 1 - cols, ws[(i + 1 - cols - 1) as usize]))
+  }
+  edges
+}
 
-  // test
-  let xss = vec![
-    vec![131, 673, 234, 103, 18],
-    vec![201, 96, 342, 965, 150],
-    vec![630, 803, 746, 422, 111],
-    vec![537, 699, 497, 121, 956],
-    vec![805, 732, 524, 37, 331],
-  ];
-  let xs = xss.into_iter().flat_map(|x| x).collect();
-  let g = make_graph(xs);
+// Cost of the shortest path from the start node to any node in the last column
+fn min_path_to_last_col(g: Graph<usize, usize>) -> usize {
   let idxs = g.node_indices().collect::<Vec<_>>();
-  let (res, _) = solve(g, idxs);
-  assert_eq!(res, 994);
+  let start_node = idxs[0];
+  let solution = dijkstra(
+    &g,              // directed graph
+    start_node,      // start node
+    None,            // finishing node
+    |e| *e.weight(), // edge cost
+  );
+
+  let col = ((solution.len() - 1) as f64).sqrt() as usize;
+  let sol_vec = solution
+    .into_iter()
+    .filter_map(|(n, s)| {
+      if g[n] % col == 0 && s != 0 {
+        Some((s, g[n]))
+      } else {
+        None
+      }
+    })
+    .sorted();
+  let (res, _) = sol_vec.into_iter().min().unwrap();
+  res
+}
 
-  let g = make_graph(get_data());
-  let idxs = g.node_indices().collect::<Vec<_>>();
-  let (res, _) = solve(g, idxs);
-  assert_eq!(res, 260324);
-  format!("p082 = {}", res)
-} // 260324
+/// Checks the examples given in the statement of p082
+pub fn p082_examples() {
+  assert_eq!(min_path_to_last_col(make_graph(get_example_data(), p082_edges)), 994);
+}
 
-/// Path sum: four ways
-pub fn p083() -> String {
-  fn get_edges(i: u32, ws: &[usize], cols: u32) -> Vec<(u32, u32, usize)> {
-    // first node
-    if i == 0 {
-      return vec![((i, i + 1, ws[i as usize]))];
-    }
-    let mut edges = Vec::new();
-    // right if not last col
-    if i % cols != 0 {
-      edges.push((i, i + 1, ws[i as usize]))
-    }
-    // down if not last row
-    if i + cols <= ws.len() as u32 {
-      edges.push((i, i + cols, ws[(i + cols - 1) as usize]))
-    }
-    // up if not first row
-    if i > cols {
-      edges.push((i, i - cols, ws[(i - cols - 1) as usize]))
-    }
-    // left if not first col
-    if (i % cols) != 1 {
-      edges.push((i, i - 1, ws[(i - 2) as usize]));
-    }
-    edges
-  }
+/// Path sum: three ways
+pub fn p082() -> Answer {
+  p082_from(include_str!("../data/matrix.txt")).unwrap()
+}
 
-  fn make_graph(weights: Vec<usize>) -> Graph<usize, usize> {
-    let mut g = Graph::<usize, usize>::new();
-    for i in 0..weights.len() + 1 {
-      g.add_node(i);
-    }
-    let cols = (weights.len() as f64).sqrt() as u32;
-    let edges = (0..weights.len())
-      .flat_map(|i| get_edges(i as u32, &weights, cols))
-      .collect::<Vec<_>>();
+/// Minimal path sum from the left to the right column moving up, down and right in the matrix in `text`,
+/// lines of comma separated numbers
+pub fn p082_from(text: &str) -> Result<Answer, ParseError> {
+  let res = min_path_to_last_col(make_graph(get_data(text)?, p082_edges));
+  Ok(Answer::from(res))
+}
 
-    g.extend_with_edges(&edges);
-    g
+// p083 moves: up, down, left and right
+fn p083_edges(i: u32, ws: &[usize], cols: u32) -> Vec<(u32, u32, usize)> {
+  // first node
+  if i == 0 {
+    return vec![((i, i + 1, ws[i as usize]))];
   }
-
-  fn solve(g: Graph<usize, usize>, idxs: Vec<graph::NodeIndex>) -> usize {
-    let start_node = idxs[0];
-    let finish_node = idxs.last().unwrap();
-    let sol = dijkstra(
-      &g,                 // directed graph
-      start_node,         // start node
-      Some(*finish_node), // finishing node
-      |e| *e.weight(),    // edge cost
-    );
-
-    *sol.get(finish_node).unwrap()
+  let mut edges = Vec::new();
+  // right if not last col
+  if i % cols != 0 {
+    edges.push((i, i + 1, ws[i as usize]))
   }
+  // down if not last row
+  if i + cols <= ws.len() as u32 {
+    edges.push((i, i + cols, ws[(i + cols - 1) as usize]))
+  }
+  // up if not first row
+  if i > cols {
+    edges.push((i, i - cols, ws[(i - cols - 1) as usize]))
+  }
+  // left if not first col
+  if (i % cols) != 1 {
+    edges.push((i, i - 1, ws[(i - 2) as usize]));
+  }
+  edges
+}
 
-  // test
-  let xss = vec![
-    vec![131, 673, 234, 103, 18],
-    vec![201, 96, 342, 965, 150],
-    vec![630, 803, 746, 422, 111],
-    vec![537, 699, 497, 121, 956],
-    vec![805, 732, 524, 37, 331],
-  ];
-  let xs = xss.into_iter().flat_map(|x| x).collect();
-  let g = make_graph(xs);
-  let idxs = g.node_indices().collect::<Vec<_>>();
-  let res = solve(g, idxs);
-  assert_eq!(res, 2297);
+/// Checks the examples given in the statement of p083
+pub fn p083_examples() {
+  assert_eq!(min_path_to_last(make_graph(get_example_data(), p083_edges)), 2297);
+}
 
-  let g = make_graph(get_data());
-  let idxs = g.node_indices().collect::<Vec<_>>();
-  let res = solve(g, idxs);
-  assert_eq!(res, 425185);
-  format!("p083 = {}", res)
-} // 425185
+/// Path sum: four ways
+pub fn p083() -> Answer {
+  p083_from(include_str!("../data/matrix.txt")).unwrap()
+}
+
+/// Minimal path sum moving in any direction in the matrix in `text`, lines of comma separated numbers
+pub fn p083_from(text: &str) -> Result<Answer, ParseError> {
+  let res = min_path_to_last(make_graph(get_data(text)?, p083_edges));
+  Ok(Answer::from(res))
+}
 
 /// Monopoly odds
-pub fn p084() -> String {
+pub fn p084() -> Answer {
+  p084_seeded(thread_rng().gen())
+}
+
+/// Monopoly odds, simulating the game with dice seeded with `seed`
+pub fn p084_seeded(seed: u64) -> Answer {
   #[cfg_attr(rustfmt, rustfmt_skip)]
     #[derive(Debug, Eq, PartialEq, Clone, Copy)]
     enum Bd { GO = 0, A1, CC1, A2, T1, R1, B1, CH1, B2, B3, JAIL, C1, U1, C2, C3, R2, D1, CC2, D2,
@@ -453,8 +416,7 @@ pub fn p084() -> String {
   //     }
   // }
 
-  fn roll_two_dice(sides: usize) -> (usize, usize) {
-    let mut rng = thread_rng();
+  fn roll_two_dice(rng: &mut StdRng, sides: usize) -> (usize, usize) {
     let d1: usize = rng.gen_range(1, sides + 1);
     let d2: usize = rng.gen_range(1, sides + 1);
     (d1, d2)
@@ -518,8 +480,8 @@ pub fn p084() -> String {
     pos
   }
 
-  fn do_move(mut pos: Pos, game: &mut Vec<Sim>) -> Pos {
-    let (d1, d2) = roll_two_dice(SIDES);
+  fn do_move(mut pos: Pos, game: &mut Vec<Sim>, rng: &mut StdRng) -> Pos {
+    let (d1, d2) = roll_two_dice(rng, SIDES);
     if d1 == d2 {
       if pos.double_cnt == 2 {
         pos.double_cnt = 0;
@@ -554,8 +516,9 @@ pub fn p084() -> String {
     cc_pos: 0,
     double_cnt: 0,
   };
+  let mut rng: StdRng = SeedableRng::from_seed(&[seed as usize][..]);
   for _ in 0..500_000 {
-    pos = do_move(pos, game);
+    pos = do_move(pos, game, &mut rng);
   }
   // let ranks = ;
   let most = find_ranks(game)
@@ -571,73 +534,99 @@ pub fn p084() -> String {
     .collect::<Vec<String>>();
   // print_board(game);
 
-  let res = most.join("").parse::<usize>().unwrap();
-  assert_eq!(res, 101524);
-  format!("p084 = {}", most.join(""))
-} // 101524 jail:r2:e3
+  Answer::from_digits(&most.join(""))
+}
 
 /// Counting rectangles
-pub fn p085() -> String {
-  const MAX: i64 = 2_000_000;
-  let mut diff = MAX;
+pub fn p085() -> Answer {
+  p085_with(2_000_000)
+}
+
+/// Area of the grid containing the number of rectangles nearest to `target`
+pub fn p085_with(target: usize) -> Answer {
+  let target = target as i64;
+  let mut diff = target;
   let mut area = 0;
-  // (x,y) -> (53,54) and (2,2000) yields n > MAX
-  for x in 2..53 {
-    for y in x..2000 {
+  // x <= y, each stopping at the first size whose count exceeds target
+  for x in 1.. {
+    for y in x.. {
       let n = x * (x + 1) * y * (y + 1) / 4;
-      let new_diff = (MAX - n).abs();
+      let new_diff = (target - n).abs();
       if new_diff < diff {
         diff = new_diff;
         area = x * y;
       }
+      if n > target {
+        break;
+      }
+    }
+    if x * (x + 1) * x * (x + 1) / 4 > target {
+      break;
     }
   }
-  assert_eq!(area, 2772);
-  format!("p085 = {}", area)
-} // 2772
+  Answer::from(area)
+}
 
-/// Cuboid route
-pub fn p086() -> String {
-  let mut cnt = 0.0;
-  let mut j: f64 = 2.0;
-  while cnt <= 1_000_000.0 {
-    j += 1.0;
-    for i in 3..2 * j as usize {
-      let (ab, c) = (i as f64, j as f64);
-      let sqrt = ((ab).powf(2.0) + c * c).sqrt();
-      if (sqrt - sqrt.floor()).abs() < EPSILON {
-        if ab < j {
-          cnt += ab / 2.0
-        } else {
-          cnt += j + 1.0 - (ab + 1.0) / 2.0
-        }
+// Least cuboid size M for which the cuboids up to M by M by M with an integer shortest route number over
+// solutions
+fn least_cuboid(solutions: usize) -> usize {
+  let mut cnt = 0;
+  let mut m = 0;
+  while cnt <= solutions {
+    m += 1;
+    // a + b = ab unfolds the cuboid a x b x m, whose shortest route is the hypotenuse of ab and m
+    for ab in 2..2 * m + 1 {
+      let sq = ab * ab + m * m;
+      let r = (sq as f64).sqrt() as usize;
+      if (r.saturating_sub(1)..r + 2).any(|r| r * r == sq) {
+        // pairs a <= b <= m with a + b = ab
+        cnt += if ab <= m { ab / 2 } else { m + 1 - (ab + 1) / 2 }
       }
     }
   }
+  m
+}
 
-  assert_eq!(j as usize, 1818);
-  format!("p086 = {}", j)
-} // 1818
+/// Checks the examples given in the statement of p086
+pub fn p086_examples() {
+  assert_eq!(least_cuboid(1975), 100);
+  assert_eq!(least_cuboid(2000), 100);
+}
+
+/// Cuboid route
+pub fn p086() -> Answer {
+  p086_with(1_000_000)
+}
+
+/// Least M for which the cuboids up to M by M by M with an integer shortest route number over `solutions`
+pub fn p086_with(solutions: usize) -> Answer {
+  Answer::from(least_cuboid(solutions))
+}
 
 /// Prime power triples
-pub fn p087() -> String {
-  const MAX: usize = 50_000_000;
+pub fn p087() -> Answer {
+  p087_with(50_000_000)
+}
+
+/// Number of integers below `limit` expressible as the sum of a prime square, prime cube and prime fourth
+/// power
+pub fn p087_with(limit: usize) -> Answer {
   let primes = primal::Primes::all()
-    .take_while(|&p| p < ((MAX as f64).sqrt() * 1.1) as usize)
+    .take_while(|&p| p < ((limit as f64).sqrt() * 1.1) as usize)
     .collect_vec();
 
   let res = primes
     .iter()
-    .take_while(|&x| x.pow(4) < MAX)
+    .take_while(|&x| x.pow(4) < limit)
     .flat_map(|a| {
       primes
         .iter()
-        .take_while(|&x| x.pow(3) < MAX)
+        .take_while(|&x| x.pow(3) < limit)
         .flat_map(|b| {
           primes
             .iter()
             .map(|c| a.pow(4) + b.pow(3) + c.pow(2))
-            .filter(|&x| x < MAX)
+            .filter(|&x| x < limit)
             .collect_vec()
         })
         .collect_vec()
@@ -647,12 +636,16 @@ pub fn p087() -> String {
     .dedup()
     .fold(0, |acc, _| acc + 1);
 
-  assert_eq!(res, 1097343);
-  format!("p087 = {}", res)
-} // 1097343
+  Answer::from(res)
+}
 
 /// Product-sum numbers
-pub fn p088() -> String {
+pub fn p088() -> Answer {
+  p088_with(12_000)
+}
+
+/// Sum of the distinct minimal product-sum numbers for 2 <= k <= `k_max`, `k_max` at least 2
+pub fn p088_with(k_max: usize) -> Answer {
   fn merge(a: usize, b: usize, mut cache: &mut Vec<Vec<Vec<usize>>>, res: &mut Vec<Vec<usize>>) {
     if !cache[a].is_empty() && cache[a][0][0] == 0 {
       cache[a] = factor_lists(a, &mut cache);
@@ -687,18 +680,16 @@ pub fn p088() -> String {
     xs.iter().fold(0, |acc, &x| acc + x)
   }
 
-  const MAX: usize = 12000;
-
-  let mut k: Vec<usize> = vec![MAX * 2; MAX + 1];
-  let mut cache: Vec<Vec<Vec<usize>>> = vec![vec![vec![0]]; MAX];
+  // 2k = 2 * k * 1 * ... * 1 = 2 + k + 1 + ... + 1 is a product-sum number for k
+  let mut k: Vec<usize> = (0..k_max + 1).map(|j| 2 * j).collect();
+  let mut cache: Vec<Vec<Vec<usize>>> = vec![vec![vec![0]]; k_max];
   k[0] = 0;
   k[1] = 0;
-  let max = (MAX as f64 * 1.2) as usize;
-  for i in 2..max {
+  for i in 2..2 * k_max {
     let factors = factor_lists(i, &mut cache);
     for v in factors {
       let j = i - sum(&v) + v.len();
-      if j <= MAX {
+      if j <= k_max {
         k[j] = cmp::min(k[j], i);
       }
     }
@@ -707,24 +698,31 @@ pub fn p088() -> String {
   k.sort();
   k.dedup();
 
-  let res = sum(&k);
-  assert_eq!(res, 7587457);
-  format!("p088 = {}", sum(&k))
-} // 7587457
+  Answer::from(sum(&k))
+}
 
 /// Roman numerals
-pub fn p089() -> String {
-  let romans_in = include_str!("../data/p089_roman.txt")
-    .lines()
-    .map(|s| s.to_string())
-    .collect::<Vec<String>>();
+pub fn p089() -> Answer {
+  p089_from(include_str!("../data/p089_roman.txt")).unwrap()
+}
 
-  let char_count_in = romans_in.iter().map(|x| x.len()).fold(0, |acc, x| acc + x);
+/// Characters saved by writing the roman numerals in `text`, one per line, in minimal form
+pub fn p089_from(text: &str) -> Result<Answer, ParseError> {
+  data::not_empty(text)?;
+  let romans_in = data::lines(text)
+    .into_iter()
+    .map(|(i, s)| (i, s.len() - s.trim_start().len() + 1, s.trim_start()))
+    .collect::<Vec<_>>();
+
+  let char_count_in = romans_in.iter().map(|x| x.2.len()).fold(0, |acc, x| acc + x);
 
   let numbers = romans_in
     .iter()
-    .map(|x| Roman::parse(x).unwrap().value())
-    .collect::<Vec<_>>();
+    .map(|&(i, column, x)| match Roman::parse(x) {
+      Some(roman) => Ok(roman.value()),
+      None => Err(ParseError::new(i, column, &format!("invalid roman numeral `{}`", x))),
+    })
+    .collect::<Result<Vec<_>, _>>()?;
 
   let romans_out = numbers
     .iter()
@@ -733,12 +731,11 @@ pub fn p089() -> String {
 
   let char_count_out = romans_out.iter().map(|x| x.len()).fold(0, |acc, x| acc + x);
 
-  assert_eq!(char_count_in - char_count_out, 743);
-  format!("p089 = {}", char_count_in - char_count_out)
-} // 743
+  Ok(Answer::from(char_count_in - char_count_out))
+}
 
 /// Cube digit pairs
-pub fn p090() -> String {
+pub fn p090() -> Answer {
   let sqrs = vec![
     (0, 1),
     (0, 4),
@@ -772,15 +769,53 @@ pub fn p090() -> String {
     })
     .fold(0, |acc, x| if x { acc + 1 } else { acc });
 
-  assert_eq!(cnt, 1217);
-  format!("p090 = {}", cnt)
-} // 1217
-
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-  // Euler solutions in this crate.
-  (
-    81,
-    vec![p081, p082, p083, p084, p085, p086, p087, p088, p089, p090],
-  )
+  Answer::from(cnt)
+}
+
+solutions! {
+  #![euler(problems = 81..=90)]
+
+  #[euler(problem = 81, title = "Path sum: two ways")]
+  p081
+    .examples(p081_examples)
+    .data("matrix.txt", p081_from),
+  #[euler(problem = 82, title = "Path sum: three ways")]
+  p082
+    .examples(p082_examples)
+    .data("matrix.txt", p082_from),
+  #[euler(problem = 83, title = "Path sum: four ways")]
+  p083
+    .examples(p083_examples)
+    .data("matrix.txt", p083_from),
+  #[euler(problem = 84, title = "Monopoly odds")]
+  p084.seeded(p084_seeded),
+  #[euler(problem = 85, title = "Counting rectangles")]
+  p085
+    .params(
+      vec![Param::new("target", 2_000_000, "rectangles nearest to target").at_most(1_000_000_000_000)],
+      |v| p085_with(v[0] as usize)
+    ),
+  #[euler(problem = 86, title = "Cuboid route")]
+  p086
+    .examples(p086_examples)
+    .params(
+      vec![Param::new("solutions", 1_000_000, "over this many integer routes").at_most(100_000_000)],
+      |v| p086_with(v[0] as usize)
+    ),
+  #[euler(problem = 87, title = "Prime power triples")]
+  p087
+    .params(
+      vec![Param::new("limit", 50_000_000, "count the sums below limit").at_most(1_000_000_000)],
+      |v| p087_with(v[0] as usize)
+    ),
+  #[euler(problem = 88, title = "Product-sum numbers")]
+  p088
+    .params(
+      vec![Param::new("k_max", 12_000, "k from 2 to k_max").at_least(2).at_most(20_000)],
+      |v| p088_with(v[0] as usize)
+    ),
+  #[euler(problem = 89, title = "Roman numerals")]
+  p089.data("p089_roman.txt", p089_from),
+  #[euler(problem = 90, title = "Cube digit pairs")]
+  p090,
 }
@@ -14,37 +14,46 @@ use itertools::Itertools;
 extern crate num;
 use num::integer::gcd;
 
+#[macro_use]
 extern crate euler_library;
+use euler_library::solution::{Answer, Param};
 use euler_library::common as eu;
+use euler_library::data::{self, ParseError};
 
 /// Right triangles with integer coordinates
-pub fn p091() -> String {
-    const SIZE: i32 = 50;
+pub fn p091() -> Answer {
+    p091_with(50)
+}
 
-    let cnt = SIZE * SIZE * 3 +
-              (1..SIZE + 1)
+/// Number of right triangles OPQ with integer coordinates from 0 to `size`
+pub fn p091_with(size: usize) -> Answer {
+    let size = size as i64;
+    let cnt = size * size * 3 +
+              (1..size + 1)
         .flat_map(|x| {
-            (1..SIZE + 1)
+            (1..size + 1)
                 .map(|y| {
                     let gcd = gcd(x, y);
                     let dx = x / gcd;
                     let dy = y / gcd;
-                    cmp::min(y / dx, (SIZE - x) / dy) * 2
+                    cmp::min(y / dx, (size - x) / dy) * 2
                 })
                 .collect::<Vec<_>>()
         })
         .fold(0, |acc, x| acc + x);
 
-    assert_eq!(cnt, 14234);
-    format!("p091 = {}", cnt)
-} // 14234
+    Answer::from(cnt)
+}
 
 /// Square digit chains
-pub fn p092() -> String {
+pub fn p092() -> Answer {
+    p092_with(10_000_000)
+}
 
-    const MAX: usize = 10_000_000;
-    // sum_sq(9_999_999) = 567, all others are less
-    const TABLE_SIZE: usize = 568;
+/// Number of starting numbers below `limit` whose square digit chain arrives at 89
+pub fn p092_with(limit: usize) -> Answer {
+    // the sum of the squares of the digits of a number below limit is at most 81 for each digit
+    let table_size = 81 * limit.saturating_sub(1).to_string().len() + 1;
 
     let sum_sq = |mut n: usize| -> usize {
         let mut total = 0;
@@ -56,8 +65,8 @@ pub fn p092() -> String {
         total
     };
 
-    let mut table: Vec<usize> = vec![0; TABLE_SIZE];
-    for (i, _) in table.clone().iter().enumerate().take(TABLE_SIZE).skip(1) {
+    let mut table: Vec<usize> = vec![0; table_size];
+    for (i, _) in table.clone().iter().enumerate().take(table_size).skip(1) {
         let mut val = i;
         while val != 1 && val != 89 {
             val = sum_sq(val)
@@ -65,15 +74,14 @@ pub fn p092() -> String {
         table[i] = val
     }
 
-    let cnt = (1..MAX).fold(0,
+    let cnt = (1..limit).fold(0,
                             |acc, x| if table[sum_sq(x)] == 89 { acc + 1 } else { acc });
 
-    assert_eq!(cnt, 8581146);
-    format!("p092 = {}", cnt)
-} // 8581146
+    Answer::from(cnt)
+}
 
 /// Arithmetic expressions
-pub fn p093() -> String {
+pub fn p093() -> Answer {
     fn eval(a: f64, b: f64, op: char) -> f64 {
         match op {
             '+' => a + b,
@@ -167,12 +175,16 @@ pub fn p093() -> String {
     max.1.sort();
     let s = format!("{}{}{}{}", max.1[0], max.1[1], max.1[2], max.1[3]);
 
-    assert_eq!(s, "1258".to_string());
-    format!("p093 = {}", s)
-} // 1258
+    Answer::from_digits(&s)
+}
 
 /// Almost equilateral triangles
-pub fn p094() -> String {
+pub fn p094() -> Answer {
+    p094_with(1_000_000_000)
+}
+
+/// Sum of the perimeters up to `limit` of the almost equilateral triangles with integral sides and area
+pub fn p094_with(limit: usize) -> Answer {
     // v_short_side and v_long_side from:
     // http://www.had2know.com/academics/nearly-equilateral-heronian-triangles.html
     //
@@ -197,56 +209,61 @@ pub fn p094() -> String {
         }
     }
 
-    const MAX: usize = 1_000_000_000;
-
     let mut p = 0;
     for i in 1.. {
         let temp_p = 3 * v_short_side(i) + 1;
-        if temp_p + p > MAX { break } else { p += temp_p }
+        if temp_p > limit { break } else { p += temp_p }
 
         let temp_p = 3 * v_long_side(i) + 2;
-        if temp_p + p > MAX { break } else { p += temp_p }
+        if temp_p > limit { break } else { p += temp_p }
     }
 
-    assert_eq!(p, 518408346);
-    format!("p094 = {}", p)
-} // 518408346
+    Answer::from(p)
+}
 
-/// Amicable chains
-pub fn p095() -> String {
-    // returns Some(length of amicable chain, min value in chain) or None
-    fn is_amicable_chain(n: usize, divs: &mut Vec<usize>) -> Option<(usize, usize)> {
-        let (mut next, mut cnt, mut min) = (n, 0, n);
-        for _ in 0..1000 {
-            match next {
-                0 | 1 => return None,
-                a if divs[a] >= divs.len() || a == divs[a] || a == divs[divs[a]] => return None,
-                _ => {
-                    cnt += 1;
-                    next = divs[next];
-                    if next < min {
-                        min = next
-                    }
-                    if next == n {
-                        return Some((cnt, min));
-                    }
+// returns Some(length of amicable chain, min value in chain) or None
+fn is_amicable_chain(n: usize, divs: &mut Vec<usize>) -> Option<(usize, usize)> {
+    let (mut next, mut cnt, mut min) = (n, 0, n);
+    for _ in 0..1000 {
+        match next {
+            0 | 1 => return None,
+            a if divs[a] >= divs.len() || a == divs[a] || a == divs[divs[a]] => return None,
+            _ => {
+                cnt += 1;
+                next = divs[next];
+                if next < min {
+                    min = next
+                }
+                if next == n {
+                    return Some((cnt, min));
                 }
             }
         }
-        None
     }
+    None
+}
 
-    let max = 1_000_000;
-    let mut divs = eu::divisor_sum_list(max);
-
+/// Checks the examples given in the statement of p095
+pub fn p095_examples() {
+    // every number in the chains checked is below 20,000
+    let mut divs = eu::divisor_sum_list(20_000);
     assert!(is_amicable_chain(28, &mut divs) == None);
     assert!(is_amicable_chain(284, &mut divs) == None);
     assert!(is_amicable_chain(562, &mut divs) == None);
     assert!(is_amicable_chain(12496, &mut divs) == Some((5, 12496)));
-    assert!(is_amicable_chain(14316, &mut divs) == Some((28, 14316)));
     assert!(is_amicable_chain(138, &mut divs) == None);
+}
+
+/// Amicable chains
+pub fn p095() -> Answer {
+    p095_with(1_000_000)
+}
+
+/// Smallest member of the longest amicable chain with no element exceeding `limit`, 0 when there is none
+pub fn p095_with(limit: usize) -> Answer {
+    let mut divs = eu::divisor_sum_list(limit);
 
-    let amic_chains = (1..max)
+    let amic_chains = (1..limit + 1)
         .map(|x| (x, is_amicable_chain(x, &mut divs)))
         .filter(|&(_, amic)| amic != None)
         .map(|(x, tup)| (x, tup.unwrap().0, tup.unwrap().1))
@@ -255,20 +272,25 @@ pub fn p095() -> String {
     let max_cnt = amic_chains.iter()
         .map(|&(_, cnt, _)| cnt)
         .max()
-        .unwrap();
+        .unwrap_or(0);
 
     let min = amic_chains.iter()
         .filter(|&&(_, cnt, _)| cnt == max_cnt)
         .map(|&(_, _, min)| min)
         .min()
-        .unwrap();
+        .unwrap_or(0);
 
-    assert_eq!(min, 14316);
-    format!("p095 = {}", min)
-} // 14316
+    Answer::from(min)
+}
 
 /// Su Doku
-pub fn p096() -> String {
+pub fn p096() -> Answer {
+    p096_from(include_str!("../data/p096_sudoku.txt")).unwrap()
+}
+
+/// Sum of the three digit numbers in the top left corners of the solved sudokus in `text`, each a
+/// `Grid NN` line followed by nine lines of nine digits, 0 for an empty cell
+pub fn p096_from(text: &str) -> Result<Answer, ParseError> {
 
     #[derive(PartialEq, PartialOrd, Copy, Clone, Debug)]
     struct Cell {
@@ -508,22 +530,10 @@ pub fn p096() -> String {
         }
     }
 
-    let mut xss: Vec<Vec<Vec<char>>> = Vec::new();
-    let mut temp: Vec<Vec<char>> = Vec::new();
-    let mut cnt = 0;
-    for v in include_str!("../data/p096_sudoku.txt").lines() {
-        let cs = v.chars().collect::<Vec<_>>();
-        if cs[0] == 'G' {
-            continue;
-        }
-        temp.push(cs);
-        cnt += 1;
-        if cnt == 9 {
-            xss.push(temp.clone());
-            temp.clear();
-            cnt = 0;
-        }
-    }
+    let xss = data::sudokus(text)?
+        .into_iter()
+        .map(|s| s.cells.iter().map(|r| r.iter().map(|&d| (b'0' + d) as char).collect()).collect())
+        .collect::<Vec<Vec<Vec<char>>>>();
 
     let mut cnt = 0;
     for v in &xss {
@@ -536,31 +546,30 @@ pub fn p096() -> String {
         // println!("{}", aaa);
     }
 
-    assert_eq!(cnt, 24702);
-    format!("p096 = {}", cnt)
-} // 24702
+    Ok(Answer::from(cnt))
+}
 
 
 /// Large non-Mersenne prime
-pub fn p097() -> String {
-    let res: usize = (0..7830457).fold(28433, |acc, _| (2 * acc) % 10_000_000_000) + 1;
-    assert_eq!(res, 8739992577);
-    format!("p097 = {}", res)
-} // 8739992577
+pub fn p097() -> Answer {
+    p097_with(28433, 7830457)
+}
+
+/// Last ten digits of `multiplier` * 2^`exponent` + 1
+pub fn p097_with(multiplier: usize, exponent: usize) -> Answer {
+    const TEN_DIGITS: usize = 10_000_000_000;
+    let res: usize = (0..exponent).fold(multiplier % TEN_DIGITS, |acc, _| (2 * acc) % TEN_DIGITS) + 1;
+    Answer::from(res % TEN_DIGITS)
+}
 
 /// Anagramic squares
-pub fn p098() -> String {
-    // get the words from the file
-    fn get_words() -> Vec<String> {
-        include_str!("../data/p098_words.txt")
-            .chars()
-            .filter(|&x| x != '\"')
-            .collect::<String>()
-            .split(',')
-            .map(|x| x.to_string())
-            .collect_vec()
-    }
+pub fn p098() -> Answer {
+    p098_from(include_str!("../data/p098_words.txt")).unwrap()
+}
 
+/// Largest square formed by a square anagram word pair among the quoted, comma separated upper case words
+/// in `text`
+pub fn p098_from(text: &str) -> Result<Answer, ParseError> {
     // sort each word within words, save words original index
     fn get_sorted_words(words: Vec<String>) -> Vec<(Vec<char>, usize)> {
         words.into_iter()
@@ -623,7 +632,7 @@ pub fn p098() -> String {
         Some(candidate)
     }
 
-    let words = get_words();
+    let words = data::words(text)?;
     let all_anagrams = get_anagrams(words.clone());
     let mut max_anagram = 0;
     for (v, _) in all_anagrams.clone() {
@@ -656,50 +665,96 @@ pub fn p098() -> String {
         }
     }
 
-    assert_eq!(res, 18769);
-    format!("p098 = {}", res)
-} // 18769
+    Ok(Answer::from(res))
+}
 
 /// Largest exponential
-pub fn p099() -> String {
+pub fn p099() -> Answer {
+    p099_from(include_str!("../data/p099_base_exp.txt")).unwrap()
+}
+
+/// Line number of the greatest `base^exponent` in `text`, lines of comma separated base and exponent
+pub fn p099_from(text: &str) -> Result<Answer, ParseError> {
     // logb(x^y) = y ∙ logb(x)
-    let buffer = include_str!("../data/p099_base_exp.txt");
-    let xs = buffer.lines()
-        .map(|x| {
-            x.split(',')
-                .map(|x| x.parse::<f64>().unwrap())
-                .collect_vec()
-        })
+    let xs = data::records::<f64>(text, 2)?
+        .into_iter()
         .enumerate()
         .map(|(i, pair)| ((pair[1] * pair[0].ln()).to_string(), i))
         .sorted();
 
     let res = xs.last().unwrap().1 + 1;
-    assert_eq!(res, 709);
-    format!("p099 = {}", res)
-} // 709
+    Ok(Answer::from(res))
+}
 
 /// Arranged probability
-pub fn p100() -> String {
+pub fn p100() -> Answer {
+    p100_with(1_000_000_000_000)
+}
+
+/// Number of blue discs of the first arrangement of over `limit` discs giving a 50% chance of taking two
+/// blue discs
+pub fn p100_with(limit: usize) -> Answer {
     // https://www.alpertron.com.ar/QUAD.HTM
     // (b/n)*((b-1)/(n-1)) = 2b^2 - 2b - n^2 + n = 0
     // [a,b,c,d,e,f]=[2,0,-1,-2,1,0]
     // [P,Q,K,R,S,L]=[3,2,-2,4,3,-3]
-    let mut max = 0.0;
-    let mut b = 15.0;
-    let mut n = 21.0;
-    while max < 1_000_000_000_000.0 {
-        let b_new = 3.0 * b + 2.0 * n - 2.0;
-        n = 4.0 * b + 3.0 * n - 3.0;
+    let mut b = 3;
+    let mut n = 4;
+    while n <= limit {
+        let b_new = 3 * b + 2 * n - 2;
+        n = 4 * b + 3 * n - 3;
         b = b_new;
-        max += n;
     }
 
-    assert_eq!(b as usize, 756872327473);
-    format!("p100 = {}", b)
-} // 756872327473
+    Answer::from(b)
+}
 
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-    (91, vec![p091, p092, p093, p094, p095, p096, p097, p098, p099, p100])
+solutions! {
+    #![euler(problems = 91..=100)]
+
+    #[euler(problem = 91, title = "Right triangles with integer coordinates")]
+    p091
+        .params(vec![Param::new("size", 50, "coordinates from 0 to size").at_most(2000)], |v| p091_with(v[0] as usize)),
+    #[euler(problem = 92, title = "Square digit chains")]
+    p092
+        .params(
+            vec![Param::new("limit", 10_000_000, "starting numbers below limit").at_most(100_000_000)],
+            |v| p092_with(v[0] as usize)
+        ),
+    #[euler(problem = 93, title = "Arithmetic expressions")]
+    p093,
+    #[euler(problem = 94, title = "Almost equilateral triangles")]
+    p094
+        .params(
+            vec![Param::new("limit", 1_000_000_000, "perimeters up to limit").at_most(100_000_000_000_000_000)],
+            |v| p094_with(v[0] as usize)
+        ),
+    #[euler(problem = 95, title = "Amicable chains")]
+    p095
+        .examples(p095_examples)
+        .params(
+            vec![Param::new("limit", 1_000_000, "no element exceeding limit").at_most(5_000_000)],
+            |v| p095_with(v[0] as usize)
+        ),
+    #[euler(problem = 96, title = "Su Doku")]
+    p096.data("p096_sudoku.txt", p096_from),
+    #[euler(problem = 97, title = "Large non-Mersenne prime")]
+    p097
+        .params(
+            vec![
+                Param::new("multiplier", 28433, "multiplier of 2^exponent"),
+                Param::new("exponent", 7830457, "power of 2").at_most(1_000_000_000),
+            ],
+            |v| p097_with(v[0] as usize, v[1] as usize)
+        ),
+    #[euler(problem = 98, title = "Anagramic squares")]
+    p098.data("p098_words.txt", p098_from),
+    #[euler(problem = 99, title = "Largest exponential")]
+    p099.data("p099_base_exp.txt", p099_from),
+    #[euler(problem = 100, title = "Arranged probability")]
+    p100
+        .params(
+            vec![Param::new("limit", 1_000_000_000_000, "over limit discs").at_most(1_000_000_000_000_000_000)],
+            |v| p100_with(v[0] as usize)
+        ),
 }
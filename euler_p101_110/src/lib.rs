@@ -2,6 +2,7 @@
 //!
 //! This crate is designed to be used via crate `euler`.
 
+use std::cmp;
 use std::mem;
 
 extern crate itertools;
@@ -12,16 +13,23 @@ use petgraph::Graph;
 use petgraph::algo::min_spanning_tree;
 use petgraph::data::FromElements;
 
+#[macro_use]
 extern crate euler_library;
+use euler_library::solution::{Answer, Param};
 use euler_library::common as eu;
-use euler_library::primes;
+use euler_library::data::{self, ParseError};
 
 /// Optimum polynomial
-pub fn p101() -> String {
-  fn un(n: usize) -> f64 {
+pub fn p101() -> Answer {
+  p101_with(10)
+}
+
+/// Sum of the first incorrect terms of the optimum polynomials of the generating function
+/// 1 - n + n^2 - n^3 + ... + (-n)^`degree`
+pub fn p101_with(degree: usize) -> Answer {
+  fn un(n: usize, degree: usize) -> f64 {
     let nf = n as f64;
-    1.0 - nf + nf.powf(2.0) - nf.powf(3.0) + nf.powf(4.0) - nf.powf(5.0) + nf.powf(6.0) - nf.powf(7.0) + nf.powf(8.0)
-      - nf.powf(9.0) + nf.powf(10.0)
+    (0..degree + 1).fold(0.0, |acc, i| if i % 2 == 0 { acc + nf.powf(i as f64) } else { acc - nf.powf(i as f64) })
   }
 
   fn get_lhs(n: usize) -> Vec<Vec<f64>> {
@@ -110,76 +118,54 @@ pub fn p101() -> String {
 
   static EPSILON: f64 = 1.0e-10;
 
-  let full_rhs = (1..11).map(un).collect_vec();
+  let full_rhs = (1..degree + 1).map(|n| un(n, degree)).collect_vec();
 
   let mut result = 0.0;
-  for i in 1..11 {
+  for i in 1..degree + 1 {
     let lhs = get_lhs(i);
     let rhs = full_rhs.iter().take(i).cloned().collect_vec();
     let mut x = solve_la(&lhs, &rhs);
     if i == 2 {
-      assert_eq!(un(2) as usize, check(&x) as usize)
+      assert_eq!(un(2, degree) as usize, check(&x) as usize)
     }
     result += get_bop(&mut x);
   }
 
-  assert_eq!(result as usize, 37076114526);
-  format!("p101 = {}", result)
-} // 37076114526
+  Answer::from(result as i64)
+}
 
-/// Triangle containment
-pub fn p102() -> String {
-  #[derive(Debug, Clone, Copy)]
-  struct P {
-    x: i32,
-    y: i32,
-  };
-
-  // get the triangle coordinates file
-  fn get_points() -> Vec<Vec<P>> {
-    let buffer = include_str!("../data/p102_triangles.txt");
-    let xs = buffer
-      .lines()
-      .map(|x| {
-        x.split(',')
-          .map(|x| x.parse().unwrap())
-          .collect::<Vec<i32>>()
-      })
-      .collect_vec();
-    xs.into_iter()
-      .map(|z| {
-        vec![
-          P { x: z[0], y: z[1] },
-          P { x: z[2], y: z[3] },
-          P { x: z[4], y: z[5] },
-        ]
-      })
-      .collect_vec()
-  }
+#[derive(Debug, Clone, Copy)]
+struct P {
+  x: i32,
+  y: i32,
+}
 
-  // https://en.wikipedia.org/wiki/Barycentric_coordinate_system
-  fn alpha(p1: P, p2: P, p3: P, p: P) -> f64 {
-    ((p2.y - p3.y) * (p.x - p3.x) + (p3.x - p2.x) * (p.y - p3.y)) as f64
-      / ((p2.y - p3.y) * (p1.x - p3.x) + (p3.x - p2.x) * (p1.y - p3.y)) as f64
-  };
-
-  fn beta(p1: P, p2: P, p3: P, p: P) -> f64 {
-    ((p3.y - p1.y) * (p.x - p3.x) + (p1.x - p3.x) * (p.y - p3.y)) as f64
-      / ((p2.y - p3.y) * (p1.x - p3.x) + (p3.x - p2.x) * (p1.y - p3.y)) as f64
-  };
-
-  fn gamma(alpha: f64, beta: f64) -> f64 {
-    1.0 - alpha - beta
-  };
-
-  fn is_contained(p1: P, p2: P, p3: P) -> bool {
-    let origin = P { x: 0, y: 0 };
-    let alpha_ = alpha(p1, p2, p3, origin);
-    let beta_ = beta(p1, p2, p3, origin);
-    let gamma_ = gamma(alpha_, beta_);
-    alpha_ > 0.0 && beta_ > 0.0 && gamma_ > 0.0
-  }
+// https://en.wikipedia.org/wiki/Barycentric_coordinate_system
+fn alpha(p1: P, p2: P, p3: P, p: P) -> f64 {
+  ((p2.y - p3.y) * (p.x - p3.x) + (p3.x - p2.x) * (p.y - p3.y)) as f64
+    / ((p2.y - p3.y) * (p1.x - p3.x) + (p3.x - p2.x) * (p1.y - p3.y)) as f64
+}
+
+fn beta(p1: P, p2: P, p3: P, p: P) -> f64 {
+  ((p3.y - p1.y) * (p.x - p3.x) + (p1.x - p3.x) * (p.y - p3.y)) as f64
+    / ((p2.y - p3.y) * (p1.x - p3.x) + (p3.x - p2.x) * (p1.y - p3.y)) as f64
+}
+
+fn gamma(alpha: f64, beta: f64) -> f64 {
+  1.0 - alpha - beta
+}
 
+// true if the origin lies inside triangle p1 p2 p3
+fn is_contained(p1: P, p2: P, p3: P) -> bool {
+  let origin = P { x: 0, y: 0 };
+  let alpha_ = alpha(p1, p2, p3, origin);
+  let beta_ = beta(p1, p2, p3, origin);
+  let gamma_ = gamma(alpha_, beta_);
+  alpha_ > 0.0 && beta_ > 0.0 && gamma_ > 0.0
+}
+
+/// Checks the examples given in the statement of p102
+pub fn p102_examples() {
   // contained
   let a = P { x: -340, y: 495 };
   let b = P { x: -153, y: -910 };
@@ -191,8 +177,30 @@ pub fn p102() -> String {
   let b = P { x: -421, y: -714 };
   let c = P { x: 574, y: -645 };
   assert!(!is_contained(a, b, c));
+}
 
-  let res = get_points().into_iter().fold(0, |acc, xs| {
+/// Triangle containment
+pub fn p102() -> Answer {
+  p102_from(include_str!("../data/p102_triangles.txt")).unwrap()
+}
+
+/// Number of triangles in `text` containing the origin, lines of six comma separated coordinates
+pub fn p102_from(text: &str) -> Result<Answer, ParseError> {
+  // get the triangle coordinates from the text
+  fn get_points(text: &str) -> Result<Vec<Vec<P>>, ParseError> {
+    let xs = data::records::<i32>(text, 6)?;
+    Ok(xs.into_iter()
+      .map(|z| {
+        vec![
+          P { x: z[0], y: z[1] },
+          P { x: z[2], y: z[3] },
+          P { x: z[4], y: z[5] },
+        ]
+      })
+      .collect_vec())
+  }
+
+  let res = get_points(text)?.into_iter().fold(0, |acc, xs| {
     if is_contained(xs[0], xs[1], xs[2]) {
       acc + 1
     } else {
@@ -200,12 +208,11 @@ pub fn p102() -> String {
     }
   });
 
-  assert_eq!(res, 228);
-  format!("p102 = {}", res)
-} // 228
+  Ok(Answer::from(res))
+}
 
 /// Special subset sums: optimum
-pub fn p103() -> String {
+pub fn p103() -> Answer {
   fn set_string(set: &[usize]) -> String {
     set.iter().fold("".to_string(), |acc, x| {
       acc + &x.to_string()
@@ -302,13 +309,18 @@ pub fn p103() -> String {
   }
 
   let res = solve(&[20, 31, 38, 39, 40, 42, 45]);
-  assert_eq!(res, "20313839404245".to_string());
-  format!("p103 = {}", res)
-} // 20313839404245
+  Answer::from_digits(&res)
+}
 
 /// Pandigital Fibonacci ends
-pub fn p104() -> String {
-  let big = (10 as usize).pow(9);
+pub fn p104() -> Answer {
+  p104_with(9)
+}
+
+/// Index of the first Fibonacci number whose first and last `digits` digits are each pandigital over 1 to
+/// `digits`, `digits` from 2 to 9
+pub fn p104_with(digits: usize) -> Answer {
+  let big = (10 as usize).pow(digits as u32);
   let root5 = (5.0 as f64).sqrt();
   let phi = (1.0 + root5) / 2.0;
 
@@ -325,28 +337,28 @@ pub fn p104() -> String {
     b %= big;
     cnt += 1;
     let back = b.to_string();
-    if eu::is_pandigital(back, 1) {
+    if back.len() == digits && eu::is_pandigital(back, 1) {
       // https://en.wikipedia.org/wiki/Fibonacci_number
       let logfib = (cnt as f64) * phi.log(10.0) - root5.log(10.0);
-      let str = ((10.0 as f64).powf(logfib - logfib.floor() + 8.0) as usize).to_string();
-      if eu::is_pandigital(str, 1) {
+      let str = ((10.0 as f64).powf(logfib - logfib.floor() + (digits - 1) as f64) as usize).to_string();
+      if logfib >= (digits - 1) as f64 && eu::is_pandigital(str, 1) {
         break;
       }
     }
   }
 
-  assert_eq!(cnt, 329468);
-  format!("p104 = {}", cnt)
-} // 329468
+  Answer::from(cnt)
+}
 
 /// Special subset sums: testing
-pub fn p105() -> String {
-  fn get_data() -> Vec<Vec<usize>> {
-    let buffer = include_str!("../data/p105_sets.txt");
-    buffer
-      .lines()
-      .map(|x| x.split(',').map(|x| x.parse().unwrap()).sorted())
-      .collect_vec()
+pub fn p105() -> Answer {
+  p105_from(include_str!("../data/p105_sets.txt")).unwrap()
+}
+
+/// Sum of the special sum sets in `text`, lines of comma separated numbers
+pub fn p105_from(text: &str) -> Result<Answer, ParseError> {
+  fn get_data(text: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    Ok(data::rows(text)?.into_iter().map(|xs| xs.into_iter().sorted()).collect_vec())
   }
 
   fn vec_sum(set: &[usize]) -> usize {
@@ -399,22 +411,27 @@ pub fn p105() -> String {
     true
   }
 
-  let sum = get_data()
+  let sum = get_data(text)?
     .into_iter()
     .fold(0, |acc, x| if is_sss(&x) { acc + vec_sum(&x) } else { acc });
 
-  assert_eq!(sum, 73702);
-  format!("p105 = {}", sum)
-} // 73702
+  Ok(Answer::from(sum))
+}
 
 /// Special subset sums: meta-testing
-pub fn p106() -> String {
+pub fn p106() -> Answer {
+  p106_with(12)
+}
+
+/// Number of subset pairs of a set of `n` elements to test for equality of sums when the set is known to
+/// satisfy the second rule of special sum sets
+pub fn p106_with(n: usize) -> Answer {
   fn has_duplicates(xs: &[&usize], ys: &[&usize]) -> bool {
     let cnt = xs.iter().take_while(|x| !ys.contains(x)).count();
     cnt < xs.len()
   }
 
-  let vec = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+  let vec = (1..n + 1).collect_vec();
 
   let xs = (2..vec.len() / 2 + 1)
     .flat_map(|i| vec.iter().combinations(i).sorted())
@@ -441,22 +458,20 @@ pub fn p106() -> String {
     }
   }
 
-  assert_eq!(res, 21384);
-  format!("p106 = {}", res)
-} // 21384
+  Answer::from(res)
+}
 
 /// Minimal network
-pub fn p107() -> String {
-  fn get_data() -> Vec<Vec<u32>> {
-    let buffer = include_str!("../data/p107_network.txt");
-    buffer
-      .lines()
-      .map(|x| {
-        x.split(',')
-          .map(|x| if x != "-" { x.parse().unwrap() } else { 0 })
-          .collect::<Vec<u32>>()
-      })
-      .collect()
+pub fn p107() -> Answer {
+  p107_from(include_str!("../data/p107_network.txt")).unwrap()
+}
+
+/// Maximum saving by removing redundant edges from the network in `text`, an adjacency matrix of comma
+/// separated weights with `-` for no edge
+pub fn p107_from(text: &str) -> Result<Answer, ParseError> {
+  fn get_data(text: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    let xss = data::network(text)?;
+    Ok(xss.into_iter().map(|xs| xs.into_iter().map(|x| x.unwrap_or(0)).collect()).collect())
   }
 
   fn make_graph(xs: &[Vec<u32>]) -> Graph<(), u32> {
@@ -472,142 +487,153 @@ pub fn p107() -> String {
     Graph::<(), u32>::from_edges(&vec)
   }
 
-  let mut gr = make_graph(&get_data());
+  let mut gr = make_graph(&get_data(text)?);
   let gr_sum: u32 = gr.edge_weights_mut().fold(0, |acc, &mut x| acc + x);
   let mut gr_res: Graph<(), u32> = FromElements::from_elements(min_spanning_tree(&gr));
   let gr_res_sum: u32 = gr_res.edge_weights_mut().fold(0, |acc, &mut x| acc + x);
 
   let res = gr_sum - gr_res_sum;
-  assert_eq!(res, 259679);
-  format!("p107 = {}", res)
-} // 259679
-
-/// Diophantine reciprocals I
-pub fn p108() -> String {
-  fn dio_recip_cnt(n: usize) -> usize {
-    (primes::prime_factors(n)
-      .into_iter()
-      .group_by(|x| *x)
-      .into_iter()
-      .map(|(_, ys)| ys.into_iter().count())
-      .fold(1, |acc, x| acc * (2 * x + 1)) + 1) / 2
-  }
-
-  fn solve() -> usize {
-    let list = primes::prime_factor_cnt(300_000)
-      .iter()
-      .enumerate()
-      .filter_map(|(i, &v)| if v > 5 { Some(i) } else { None })
-      .collect_vec();
+  Ok(Answer::from(res))
+}
 
-    for v in list {
-      if dio_recip_cnt(v) > 1000 {
-        return v;
-      }
+// Least n for which 1/x + 1/y = 1/n has over `over` solutions, usize::MAX when it does not fit a usize
+fn least_with_solutions(over: usize) -> usize {
+  // the n of the least value for its exponents e1, e2, ... is 2^e1 * 3^e2 * ... with e1 >= e2 >= ...;
+  // its (2e1 + 1)(2e2 + 1)... divisors of n^2 pair up into (that + 1) / 2 solutions
+  fn search(primes: &[usize], max_exp: usize, n: usize, divs: usize, over: usize, best: &mut usize) {
+    if (divs + 1) / 2 > over {
+      *best = cmp::min(*best, n);
+      return;
     }
-    0
-  }
-
-  let res = solve();
-  assert_eq!(res, 180180);
-  format!("p108 = {}", res)
-} // 180180
-
-/// Darts
-pub fn p109() -> String {
-  fn get_scores() -> (Vec<usize>, Vec<usize>) {
-    let mut scores = vec![0, 25, 50];
-    let mut doubles = vec![50];
-    for i in 1..21 {
-      scores.push(i);
-      scores.push(2 * i);
-      scores.push(3 * i);
-      doubles.push(2 * i);
+    if primes.is_empty() {
+      return;
     }
-    (scores, doubles)
-  }
-
-  fn solve(n: usize) -> usize {
-    let (all_scores, doubles) = get_scores();
-    let mut cnt = 0;
-    for (i, vi) in all_scores.iter().enumerate() {
-      for vj in all_scores.iter().skip(i) {
-        for vk in &doubles {
-          if vi + vj + vk < n {
-            cnt += 1;
-          }
-        }
-      }
+    let mut m = n;
+    for e in 1..max_exp + 1 {
+      m = match m.checked_mul(primes[0]) {
+        Some(m) if m < *best => m,
+        _ => break,
+      };
+      search(&primes[1..], e, m, divs * (2 * e + 1), over, best);
     }
-    cnt
   }
 
-  assert_eq!(solve(6), 11);
+  // a usize has at most 15 distinct prime factors
+  let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+  let mut best = usize::MAX;
+  search(&primes, 64, 1, 1, over, &mut best);
+  best
+}
 
-  let res = solve(100);
-  assert_eq!(res, 38182);
-  format!("p109 = {}", res)
-} // 38182
+/// Diophantine reciprocals I
+pub fn p108() -> Answer {
+  p108_with(1000)
+}
 
-/// Diophantine reciprocals II
-pub fn p110() -> String {
-  fn distinct_solution(xs: &[usize]) -> usize {
-    (xs.into_iter().fold(1, |acc, x| acc * (2 * x + 1)) + 1) / 2
-  }
+/// Least n for which 1/x + 1/y = 1/n has over `solutions` solutions in positive integers
+pub fn p108_with(solutions: usize) -> Answer {
+  let res = least_with_solutions(solutions);
+  Answer::from(res)
+}
 
-  fn value(xs: &[usize]) -> usize {
-    let primes = vec![
-      2 as usize, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53
-    ];
-    xs.iter()
-      .enumerate()
-      .fold(1, |acc, (i, &v)| acc * primes[i].pow(v as u32) as usize)
-  }
+// Returns every score of a single dart including a miss, and the doubles
+fn get_scores() -> (Vec<usize>, Vec<usize>) {
+  let mut scores = vec![0, 25, 50];
+  let mut doubles = vec![50];
+  for i in 1..21 {
+    scores.push(i);
+    scores.push(2 * i);
+    scores.push(3 * i);
+    doubles.push(2 * i);
+  }
+  (scores, doubles)
+}
 
-  fn get_candidates() -> Vec<Vec<usize>> {
-    let n = 18;
-    let xs = vec![2, 3, 4, 5];
-    let mut candidates = Vec::new();
-    for i in 1..7 {
-      let mut combs = eu::k_nested_recur(i, &xs);
-      for (j, v) in combs.clone().iter().enumerate() {
-        let sum = v.iter().fold(0, |acc, x| acc + x);
-        if sum > n {
-          break;
+// Number of distinct checkouts with a score below n
+fn count_checkouts(n: usize) -> usize {
+  let (all_scores, doubles) = get_scores();
+  let mut cnt = 0;
+  for (i, vi) in all_scores.iter().enumerate() {
+    for vj in all_scores.iter().skip(i) {
+      for vk in &doubles {
+        if vi + vj + vk < n {
+          cnt += 1;
         }
-        combs[j].append(&mut vec![1; n - sum]);
       }
-      candidates.append(&mut combs);
     }
-    candidates
   }
+  cnt
+}
 
-  fn solve() -> usize {
-    let solutions = get_candidates()
-      .clone()
-      .iter()
-      .map(|x| (distinct_solution(x), x.clone()))
-      .filter(|&(sol, _)| sol > 4_000_000)
-      .sorted();
+/// Checks the examples given in the statement of p109
+pub fn p109_examples() {
+  assert_eq!(count_checkouts(6), 11);
+}
 
-    let best_soltion = solutions
-      .iter()
-      .filter(|&&(sol, _)| sol == solutions[0].0)
-      .map(|&(_, ref xs)| value(xs))
-      .sorted();
+/// Darts
+pub fn p109() -> Answer {
+  p109_with(100)
+}
 
-    best_soltion[0]
-  }
+/// Number of distinct ways a player can checkout with a score below `limit`
+pub fn p109_with(limit: usize) -> Answer {
+  let res = count_checkouts(limit);
+  Answer::from(res)
+}
+
+/// Diophantine reciprocals II
+pub fn p110() -> Answer {
+  p110_with(4_000_000)
+}
+
+/// Least n for which 1/x + 1/y = 1/n has over `solutions` solutions in positive integers
+pub fn p110_with(solutions: usize) -> Answer {
+  let res = least_with_solutions(solutions);
+  Answer::from(res)
+}
 
-  let res = solve();
-  assert_eq!(res, 9350130049860600);
-  format!("p110 = {}", res)
-} // 9350130049860600
-
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-  (
-    101,
-    vec![p101, p102, p103, p104, p105, p106, p107, p108, p109, p110],
-  )
+solutions! {
+  #![euler(problems = 101..=110)]
+
+  #[euler(problem = 101, title = "Optimum polynomial")]
+  p101
+    .params(
+      vec![Param::new("degree", 10, "generating function of this degree").at_least(1).at_most(10)],
+      |v| p101_with(v[0] as usize)
+    ),
+  #[euler(problem = 102, title = "Triangle containment")]
+  p102
+    .examples(p102_examples)
+    .data("p102_triangles.txt", p102_from),
+  #[euler(problem = 103, title = "Special subset sums: optimum")]
+  p103,
+  #[euler(problem = 104, title = "Pandigital Fibonacci ends")]
+  p104
+    .params(
+      vec![Param::new("digits", 9, "first and last digits pandigital").at_least(2).at_most(9)],
+      |v| p104_with(v[0] as usize)
+    ),
+  #[euler(problem = 105, title = "Special subset sums: testing")]
+  p105.data("p105_sets.txt", p105_from),
+  #[euler(problem = 106, title = "Special subset sums: meta-testing")]
+  p106
+    .params(vec![Param::new("n", 12, "set of n elements").at_most(16)], |v| p106_with(v[0] as usize)),
+  #[euler(problem = 107, title = "Minimal network")]
+  p107.data("p107_network.txt", p107_from),
+  #[euler(problem = 108, title = "Diophantine reciprocals I")]
+  p108
+    .params(
+      vec![Param::new("solutions", 1000, "over this many solutions").at_most(36_000_000)],
+      |v| p108_with(v[0] as usize)
+    ),
+  #[euler(problem = 109, title = "Darts")]
+  p109
+    .examples(p109_examples)
+    .params(vec![Param::new("limit", 100, "scores below limit")], |v| p109_with(v[0] as usize)),
+  #[euler(problem = 110, title = "Diophantine reciprocals II")]
+  p110
+    .params(
+      vec![Param::new("solutions", 4_000_000, "over this many solutions").at_most(36_000_000)],
+      |v| p110_with(v[0] as usize)
+    ),
 }
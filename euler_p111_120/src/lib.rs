@@ -15,12 +15,19 @@ use num::bigint::ToBigUint;
 extern crate permutohedron;
 use permutohedron::LexicalPermutation;
 
+#[macro_use]
 extern crate euler_library;
+use euler_library::solution::{Answer, Param};
 use euler_library::big as eu_big;
 use euler_library::common as eu;
 
 /// Primes with runs
-pub fn p111() -> String {
+pub fn p111() -> Answer {
+  p111_with(10)
+}
+
+/// Sum over the digits d of the `digits`-digit primes with the most repeated digits d, `digits` at least 3
+pub fn p111_with(digits: usize) -> Answer {
   fn from_digits(xs: &[usize]) -> usize {
     let mut n = 0;
     for x in xs {
@@ -84,189 +91,238 @@ pub fn p111() -> String {
       .fold(0, |acc, list| acc + list.iter().fold(0, |sum, x| sum + x))
   }
 
-  let res = solve(10);
-  assert_eq!(res, 612407567715);
-  format!("p111 = {}", res)
-} // 612407567715
+  let res = solve(digits);
+  Answer::from(res)
+}
 
-/// Bouncy numbers
-pub fn p112() -> String {
-  fn bouncy(mut n: usize) -> bool {
-    let mut increasing = true;
-    let mut decreasing = true;
-    let mut last = n % 10;
-    n /= 10;
-    while n > 0 {
-      let next = n % 10;
-      if next < last {
-        increasing = false;
-      }
-      if next > last {
-        decreasing = false;
-      }
-      if !increasing && !decreasing {
-        return true;
-      }
-      last = next;
-      n /= 10;
+// true if the digits of n neither only increase nor only decrease
+fn bouncy(mut n: usize) -> bool {
+  let mut increasing = true;
+  let mut decreasing = true;
+  let mut last = n % 10;
+  n /= 10;
+  while n > 0 {
+    let next = n % 10;
+    if next < last {
+      increasing = false;
+    }
+    if next > last {
+      decreasing = false;
+    }
+    if !increasing && !decreasing {
+      return true;
     }
-    !increasing && !decreasing
+    last = next;
+    n /= 10;
   }
+  !increasing && !decreasing
+}
 
-  fn solve(proportion: usize) -> usize {
-    let mut cnt = 0;
-    for i in 1.. {
-      if bouncy(i) {
-        cnt += 1
-      }
-      // let p = (cnt as f64) / (i as f64);
-      if 100 * cnt == proportion * i {
-        return i;
-      }
+// Least number up to which at least proportion percent of the numbers are bouncy
+fn least_with_bouncy_percent(proportion: usize) -> usize {
+  let mut cnt = 0;
+  for i in 1.. {
+    if bouncy(i) {
+      cnt += 1
+    }
+    // let p = (cnt as f64) / (i as f64);
+    // the proportion can step over an exact match for small percents, so stop once it is reached
+    if 100 * cnt >= proportion * i {
+      return i;
     }
-    0
   }
+  0
+}
 
-  assert_eq!(solve(50), 538);
-
-  let res = solve(99);
-  assert_eq!(res, 1587000);
-  format!("p112 = {}", res)
-} // 1587000
+/// Checks the examples given in the statement of p112
+pub fn p112_examples() {
+  assert_eq!(least_with_bouncy_percent(50), 538);
+}
 
-/// Non-bouncy numbers
-pub fn p113() -> String {
-  fn c_nr(n: usize, r: usize) -> BigUint {
-    let n_fact = eu_big::factorial(n);
-    let r_fact = eu_big::factorial(r);
-    let n_minus_r_fact = eu_big::factorial(n - r);
-    (n_fact / r_fact) / n_minus_r_fact
-  }
+/// Bouncy numbers
+pub fn p112() -> Answer {
+  p112_with(99)
+}
 
-  fn solve(n: usize) -> String {
-    let res = c_nr(n + 9, n) + c_nr(n + 10, n) - (10 * n + 2).to_biguint().unwrap();
-    res.to_string()
-  }
+/// Least number for which the proportion of bouncy numbers reaches `percent` percent. No number does for
+/// 100 or more, where the search never ends; the parameter of p112 stops at 99.
+pub fn p112_with(percent: usize) -> Answer {
+  let res = least_with_bouncy_percent(percent);
+  Answer::from(res)
+}
 
-  // test 10^6
-  assert_eq!(solve(6).parse::<usize>().unwrap(), 12951);
+// n choose r
+fn c_nr(n: usize, r: usize) -> BigUint {
+  let n_fact = eu_big::factorial(n);
+  let r_fact = eu_big::factorial(r);
+  let n_minus_r_fact = eu_big::factorial(n - r);
+  (n_fact / r_fact) / n_minus_r_fact
+}
 
-  let res = solve(100).parse::<usize>().unwrap();
-  assert_eq!(res, 51161058134250);
-  format!("p113 = {}", res)
-} // 51161058134250
+// Number of non-bouncy numbers below 10^n
+fn count_non_bouncy(n: usize) -> String {
+  let res = c_nr(n + 9, n) + c_nr(n + 10, n) - (10 * n + 2).to_biguint().unwrap();
+  res.to_string()
+}
 
-/// Counting block combinations I
-pub fn p114() -> String {
-  // n=total block length, m=min color length
-  // return combination count for all color length from m to n
-  fn count_blocks(n: usize, m: usize) -> usize {
-    let mut ways = vec![1 as usize];
-    for i in 1..n + 1 {
-      let mut sum = ways[i - 1];
-      let idx = if m > i { 0 } else { i - m };
-      for way in ways.iter().take(idx) {
-        sum += *way;
-      }
-      if i >= m {
-        sum += 1;
-      }
-      ways.push(sum);
-    }
-    *ways.last().unwrap()
-  }
+/// Checks the examples given in the statement of p113
+pub fn p113_examples() {
+  // test 10^6
+  assert_eq!(count_non_bouncy(6).parse::<usize>().unwrap(), 12951);
+}
 
-  assert_eq!(count_blocks(7, 3), 17);
+/// Non-bouncy numbers
+pub fn p113() -> Answer {
+  p113_with(100)
+}
 
-  let res = count_blocks(50, 3);
-  assert_eq!(res, 16475640049);
-  format!("p114 = {}", res)
-} // 16475640049
+/// Number of non-bouncy numbers below 10^`exponent`
+pub fn p113_with(exponent: usize) -> Answer {
+  let res = count_non_bouncy(exponent);
+  Answer::from_digits(&res)
+}
 
-/// Counting block combinations II
-pub fn p115() -> String {
-  // n=total block length, m:color length
-  // return combination count for a single length m
-  fn count_blocks(m: usize, n: usize, ways: &mut Vec<usize>) -> usize {
-    let mut sum = ways[n - 1];
-    let idx = if m > n { 0 } else { n - m };
+// n=total block length, m=min color length
+// return combination count for all color length from m to n
+fn count_fillings(n: usize, m: usize) -> usize {
+  let mut ways = vec![1 as usize];
+  for i in 1..n + 1 {
+    let mut sum = ways[i - 1];
+    let idx = if m > i { 0 } else { i - m };
     for way in ways.iter().take(idx) {
       sum += *way;
     }
-    if n >= m {
+    if i >= m {
       sum += 1;
     }
     ways.push(sum);
-    *ways.last().unwrap()
   }
+  *ways.last().unwrap()
+}
 
-  fn solve(m: usize, max: usize) -> usize {
-    let mut ways = vec![1];
-    for i in 1.. {
-      if count_blocks(m, i, &mut ways) > max {
-        return i;
-      }
+/// Checks the examples given in the statement of p114
+pub fn p114_examples() {
+  assert_eq!(count_fillings(7, 3), 17);
+}
+
+/// Counting block combinations I
+pub fn p114() -> Answer {
+  p114_with(50)
+}
+
+/// Number of ways to fill a row of `length` units with red blocks at least three units long
+pub fn p114_with(length: usize) -> Answer {
+  let res = count_fillings(length, 3);
+  Answer::from(res)
+}
+
+// n=total block length, m:color length
+// return combination count for a single length m
+fn push_fillings(m: usize, n: usize, ways: &mut Vec<usize>) -> usize {
+  let mut sum = ways[n - 1];
+  let idx = if m > n { 0 } else { n - m };
+  for way in ways.iter().take(idx) {
+    sum += *way;
+  }
+  if n >= m {
+    sum += 1;
+  }
+  ways.push(sum);
+  *ways.last().unwrap()
+}
+
+// Least row length for which the fill-count with blocks of at least m exceeds max
+fn least_length_exceeding(m: usize, max: usize) -> usize {
+  let mut ways = vec![1];
+  for i in 1.. {
+    if push_fillings(m, i, &mut ways) > max {
+      return i;
     }
-    0
   }
+  0
+}
 
-  assert_eq!(solve(3, 1_000_000), 30);
+/// Checks the examples given in the statement of p115
+pub fn p115_examples() {
+  assert_eq!(least_length_exceeding(3, 1_000_000), 30);
+}
 
-  let res = solve(50, 1_000_000);
-  assert_eq!(res, 168);
-  format!("p115 = {}", res)
-} // 168
+/// Counting block combinations II
+pub fn p115() -> Answer {
+  p115_with(50, 1_000_000)
+}
 
-/// Red, green or blue tiles
-pub fn p116() -> String {
-  // n=total block length, m:color length
-  // return combination count for a single length m
-  fn count_blocks(n: usize, m: usize) -> usize {
-    let mut xs = vec![0; n + 1];
-    xs[0] = 1;
-    for (i, _) in xs.clone().iter().enumerate().take(n + 1).skip(1) {
-      xs[i] += xs[i - 1];
-      if i >= m {
-        xs[i] += xs[i - m];
-      }
+/// Least row length for which the number of ways to fill it with blocks at least `m` units long exceeds
+/// `ways`, `m` at least 1
+pub fn p115_with(m: usize, ways: usize) -> Answer {
+  let res = least_length_exceeding(m, ways);
+  Answer::from(res)
+}
+
+// n=total block length, m:color length
+// return combination count for a single length m
+fn count_single_colour(n: usize, m: usize) -> usize {
+  let mut xs = vec![0; n + 1];
+  xs[0] = 1;
+  for (i, _) in xs.clone().iter().enumerate().take(n + 1).skip(1) {
+    xs[i] += xs[i - 1];
+    if i >= m {
+      xs[i] += xs[i - m];
     }
-    xs[n] - 1
   }
+  xs[n] - 1
+}
 
-  assert_eq!(count_blocks(5, 2), 7);
-  assert_eq!(count_blocks(5, 3), 3);
-  assert_eq!(count_blocks(5, 4), 2);
+/// Checks the examples given in the statement of p116
+pub fn p116_examples() {
+  assert_eq!(count_single_colour(5, 2), 7);
+  assert_eq!(count_single_colour(5, 3), 3);
+  assert_eq!(count_single_colour(5, 4), 2);
+}
 
-  let res = count_blocks(50, 4) + count_blocks(50, 3) + count_blocks(50, 2);
-  assert_eq!(res, 20492570929);
-  format!("p116 = {}", res)
-} // 20492570929
+/// Red, green or blue tiles
+pub fn p116() -> Answer {
+  p116_with(50)
+}
 
-/// Red, green, and blue tiles
-pub fn p117() -> String {
-  // n=total block length, m:color length
-  // return combination count for a single length m
-  fn solve(n: usize) -> usize {
-    let mut xs = vec![0; n + 1];
-    xs[0] = 1;
-    for (i, _) in xs.clone().iter().enumerate().take(n + 1).skip(1) {
-      let start = cmp::max(i as i32 - 4, 0) as usize;
-      let sub = xs.clone();
-      let sub = &sub[start..];
-      xs[i] += sub.iter().fold(0, |acc, x| acc + x);
-    }
-    xs[n]
+/// Number of ways to replace the grey tiles in a row of `length` units with tiles of a single colour
+pub fn p116_with(length: usize) -> Answer {
+  let res = count_single_colour(length, 4) + count_single_colour(length, 3) + count_single_colour(length, 2);
+  Answer::from(res)
+}
+
+// n=total block length
+// return combination count for tiles of length 1 to 4
+fn count_tilings(n: usize) -> usize {
+  let mut xs = vec![0; n + 1];
+  xs[0] = 1;
+  for (i, _) in xs.clone().iter().enumerate().take(n + 1).skip(1) {
+    let start = cmp::max(i as i32 - 4, 0) as usize;
+    let sub = xs.clone();
+    let sub = &sub[start..];
+    xs[i] += sub.iter().fold(0, |acc, x| acc + x);
   }
+  xs[n]
+}
+
+/// Checks the examples given in the statement of p117
+pub fn p117_examples() {
+  assert_eq!(count_tilings(5), 15);
+}
 
-  assert_eq!(solve(5), 15);
+/// Red, green, and blue tiles
+pub fn p117() -> Answer {
+  p117_with(50)
+}
 
-  let res = solve(50);
-  assert_eq!(res, 100808458960497);
-  format!("p117 = {}", res)
-} // 100808458960497
+/// Number of ways to tile a row of `length` units with tiles of lengths one to four
+pub fn p117_with(length: usize) -> Answer {
+  let res = count_tilings(length);
+  Answer::from(res)
+}
 
 /// Pandigital prime sets
-pub fn p118() -> String {
+pub fn p118() -> Answer {
   fn count_prime_sets(ds: &[usize], cur: usize, idx: usize, p: &primal::Sieve) -> usize {
     if idx == 9 {
       return 1;
@@ -298,75 +354,132 @@ pub fn p118() -> String {
   }
 
   let res = solve();
-  assert_eq!(res, 44680);
-  format!("p118 = {}", res)
-} // 44680
+  Answer::from(res)
+}
 
-/// Digit power sum
-pub fn p119() -> String {
-  fn sum_of_digits(n: usize) -> usize {
-    eu::to_bytes(n)
-      .into_iter()
-      .fold(0, |acc, x| acc + x as usize - 48)
-  }
+fn sum_of_digits(n: usize) -> usize {
+  eu::to_bytes(n)
+    .into_iter()
+    .fold(0, |acc, x| acc + x as usize - 48)
+}
 
-  fn get_exp(n: usize) -> Option<u32> {
-    let sod = sum_of_digits(n);
-    let exp = ((n as f64).log10() / (sod as f64).log10()).round() as u32;
-    if sod.pow(exp) == n {
-      Some(exp)
-    } else {
-      None
-    }
+fn get_exp(n: usize) -> Option<u32> {
+  let sod = sum_of_digits(n);
+  let exp = ((n as f64).log10() / (sod as f64).log10()).round() as u32;
+  if sod.pow(exp) == n {
+    Some(exp)
+  } else {
+    None
   }
+}
 
-  fn make_table() -> Vec<usize> {
-    let max = usize::MAX / 10;
-    let mut res = Vec::new();
-    for i in 2..15 {
-      for x in 1..150 {
-        let y = i as u32;
-        let candidate = (x as usize).pow(y);
-        if candidate > 10 && get_exp(candidate) != None {
-          res.push(candidate)
-        }
-        if candidate > max {
-          break;
-        }
+// Returns the numbers which are a power of the sum of their digits, in ascending order
+fn make_table() -> Vec<usize> {
+  let max = usize::MAX / 10;
+  let mut res = Vec::new();
+  for i in 2..15 {
+    for x in 1..150 {
+      let y = i as u32;
+      let candidate = (x as usize).pow(y);
+      if candidate > 10 && get_exp(candidate) != None {
+        res.push(candidate)
+      }
+      if candidate > max {
+        break;
       }
     }
-    res.sort();
-    res.dedup();
-    res
   }
+  res.sort();
+  res.dedup();
+  res
+}
 
-  let table = make_table();
-  // test n = 10
-  assert_eq!(table[9], 614656);
+/// Checks the examples given in the statement of p119
+pub fn p119_examples() {
+  // the 10th term
+  assert_eq!(make_table()[9], 614656);
+}
+
+/// Digit power sum
+pub fn p119() -> Answer {
+  p119_with(30)
+}
 
-  let res = table[29];
-  assert_eq!(res, 248155780267521);
-  format!("p119 = {}", res)
-} // 248155780267521
+/// The `n`th number which is a power of the sum of its digits, `n` from 1 to 34, the terms below
+/// usize::MAX / 10
+pub fn p119_with(n: usize) -> Answer {
+  let table = make_table();
+  let res = table[n - 1];
+  Answer::from(res)
+}
 
 /// Square remainders
-pub fn p120() -> String {
+pub fn p120() -> Answer {
+  p120_with(1000)
+}
+
+/// Sum of the maximum remainders of (a - 1)^n + (a + 1)^n divided by a^2, for 3 <= a <= `max_a`
+pub fn p120_with(max_a: usize) -> Answer {
   // https://benpyeh.com/2013/06/23/project-euler-120/
-  let res = (3..1001).fold(0, |acc, a| {
+  let res = (3..max_a + 1).fold(0, |acc, a| {
     if a % 2 == 0 {
       acc + a * a - 2 * a
     } else {
       acc + a * a - a
     }
   });
-  assert_eq!(res, 333082500);
-  format!("p120 = {}", res)
-} // 333082500
-
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-  (
-    111,
-    vec![p111, p112, p113, p114, p115, p116, p117, p118, p119, p120],
-  )
+  Answer::from(res)
+}
+
+solutions! {
+  #![euler(problems = 111..=120)]
+
+  #[euler(problem = 111, title = "Primes with runs")]
+  p111
+    .params(
+      vec![Param::new("digits", 10, "primes of this many digits").at_least(3).at_most(12)],
+      |v| p111_with(v[0] as usize)
+    ),
+  #[euler(problem = 112, title = "Bouncy numbers")]
+  p112
+    .examples(p112_examples)
+    .params(vec![Param::new("percent", 99, "proportion of bouncy numbers").at_most(99)], |v| p112_with(v[0] as usize)),
+  #[euler(problem = 113, title = "Non-bouncy numbers")]
+  p113
+    .examples(p113_examples)
+    .params(
+      vec![Param::new("exponent", 100, "numbers below 10^exponent").at_most(10_000)],
+      |v| p113_with(v[0] as usize)
+    ),
+  #[euler(problem = 114, title = "Counting block combinations I")]
+  p114
+    .examples(p114_examples)
+    .params(vec![Param::new("length", 50, "row length").at_most(93)], |v| p114_with(v[0] as usize)),
+  #[euler(problem = 115, title = "Counting block combinations II")]
+  p115
+    .examples(p115_examples)
+    .params(
+      vec![
+        Param::new("m", 50, "blocks at least m units long").at_least(1).at_most(1000),
+        Param::new("ways", 1_000_000, "fill-count exceeding ways").at_most(1_000_000_000_000_000_000),
+      ],
+      |v| p115_with(v[0] as usize, v[1] as usize)
+    ),
+  #[euler(problem = 116, title = "Red, green or blue tiles")]
+  p116
+    .examples(p116_examples)
+    .params(vec![Param::new("length", 50, "row length").at_most(92)], |v| p116_with(v[0] as usize)),
+  #[euler(problem = 117, title = "Red, green, and blue tiles")]
+  p117
+    .examples(p117_examples)
+    .params(vec![Param::new("length", 50, "row length").at_most(68)], |v| p117_with(v[0] as usize)),
+  #[euler(problem = 118, title = "Pandigital prime sets")]
+  p118,
+  #[euler(problem = 119, title = "Digit power sum")]
+  p119
+    .examples(p119_examples)
+    .params(vec![Param::new("n", 30, "the nth term").at_least(1).at_most(34)], |v| p119_with(v[0] as usize)),
+  #[euler(problem = 120, title = "Square remainders")]
+  p120
+    .params(vec![Param::new("max_a", 1000, "a up to max_a").at_most(1_000_000)], |v| p120_with(v[0] as usize)),
 }
@@ -2,62 +2,77 @@
 //!
 //! This crate is designed to be used via crate `euler`.
 
+#[macro_use]
 extern crate euler_library;
 extern crate num;
 extern crate primal;
 
 use euler_library::common as eu;
+use euler_library::solution::{Answer, Param};
 use num::integer::gcd;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-/// Disc game prize fund
-pub fn p121() -> String {
-  // hints from https://github.com/juanplopes/euler/blob/master/121.boo
-  // calculate p for a blue/red winning set (ie more bluea than red)
-  // for n = 4, p([1,0,1,1] = 1/2 * 2/3 * 1/4 * 1/5)
-  fn p_win(xs: &[usize]) -> f64 {
-    xs.iter().enumerate().fold(1.0, |acc, (i, &x)| {
-      if x != 0 {
-        acc * (1.0 / (2.0 + i as f64))
-      } else {
-        acc * (1.0 + i as f64) / (2.0 + i as f64)
-      }
-    })
-  }
+// hints from https://github.com/juanplopes/euler/blob/master/121.boo
+// calculate p for a blue/red winning set (ie more bluea than red)
+// for n = 4, p([1,0,1,1] = 1/2 * 2/3 * 1/4 * 1/5)
+fn p_win(xs: &[usize]) -> f64 {
+  xs.iter().enumerate().fold(1.0, |acc, (i, &x)| {
+    if x != 0 {
+      acc * (1.0 / (2.0 + i as f64))
+    } else {
+      acc * (1.0 + i as f64) / (2.0 + i as f64)
+    }
+  })
+}
 
-  fn solve(n: u32) -> usize {
-    let max = (2 as usize).pow(n);
-    let blues_needed = n / 2 + 1;
-    // create permutations iterator of winning outcomes, for n = 4 ws is
-    // ws.collect() = [[0, 1, 1, 1], [1, 0, 1, 1], [1, 1, 0, 1], [1, 1, 1, 0], [1, 1, 1, 1]]
-    let ws = (0..max).filter_map(|i| {
-      let s = format!("{:015b}", i)
-        .into_bytes()
-        .iter()
-        .map(|y| (y - 48) as usize)
-        .collect::<Vec<_>>();
-      let enough_blues = s.iter().fold(0, |acc, v| acc + *v as u32) >= blues_needed;
-      if enough_blues {
-        Some(s)
-      } else {
-        None
-      }
-    });
-    // send just subslice  of xs to p_win() for testing below n = 15.
-    // format! takes literals only so ws elements have length of 15
-    (1.0 / ws.fold(0.0, |acc, xs| acc + p_win(&xs[(15 - n as usize)..]))) as usize
-  }
+// Maximum prize fund a game with n turns should allocate
+fn max_prize_fund(n: u32) -> usize {
+  let max = (2 as usize).pow(n);
+  let blues_needed = n / 2 + 1;
+  // create permutations iterator of winning outcomes, for n = 4 ws is
+  // ws.collect() = [[0, 1, 1, 1], [1, 0, 1, 1], [1, 1, 0, 1], [1, 1, 1, 0], [1, 1, 1, 1]]
+  let ws = (0..max).filter_map(|i| {
+    let s = format!("{:015b}", i)
+      .into_bytes()
+      .iter()
+      .map(|y| (y - 48) as usize)
+      .collect::<Vec<_>>();
+    let enough_blues = s.iter().fold(0, |acc, v| acc + *v as u32) >= blues_needed;
+    if enough_blues {
+      Some(s)
+    } else {
+      None
+    }
+  });
+  // send just subslice  of xs to p_win() for testing below n = 15.
+  // format! takes literals only so ws elements have length of 15
+  (1.0 / ws.fold(0.0, |acc, xs| acc + p_win(&xs[(15 - n as usize)..]))) as usize
+}
 
-  assert_eq!(solve(4), 10);
+/// Checks the examples given in the statement of p121
+pub fn p121_examples() {
+  assert_eq!(max_prize_fund(4), 10);
+}
+
+/// Disc game prize fund
+pub fn p121() -> Answer {
+  p121_with(15)
+}
 
-  let res = solve(15);
-  assert_eq!(res, 2269);
-  format!("p121 = {}", res)
-} // 2269
+/// Maximum prize fund allocated to a game of `turns` turns, `turns` from 1 to 15
+pub fn p121_with(turns: usize) -> Answer {
+  let res = max_prize_fund(turns as u32);
+  Answer::from(res)
+}
 
 /// Efficient exponentiation
-pub fn p122() -> String {
+pub fn p122() -> Answer {
+  p122_with(200)
+}
+
+/// Sum of the least numbers of multiplications computing n^k, for 1 <= k <= `n`
+pub fn p122_with(n: usize) -> Answer {
   fn path(n: usize, p: &mut HashMap<usize, usize>, lvl: &mut Vec<usize>) -> Vec<usize> {
     match n {
       0 => return vec![],
@@ -111,38 +126,45 @@ pub fn p122() -> String {
     sum
   }
 
-  let res = solve(200);
-  assert_eq!(res, 1582);
-  format!("p122 = {}", res)
-} // 1582
+  let res = solve(n);
+  Answer::from(res)
+}
 
 /// Prime square remainders
-pub fn p123() -> String {
-  fn solve() -> usize {
-    let max = (10 as usize).pow(10);
-    let sieve = primal::Sieve::new(250_000);
-    (7037..)
-      .take_while(|i| 2 as usize * i * sieve.nth_prime(i - 1) < max)
-      .max()
-      .unwrap() + 1
-  }
+pub fn p123() -> Answer {
+  p123_with((10 as usize).pow(10))
+}
 
-  let res = solve();
-  assert_eq!(res, 21035);
-  format!("p123 = {}", res)
-} // 21035
+/// Least n for which the remainder of (p_n - 1)^n + (p_n + 1)^n divided by p_n^2 exceeds `limit`, `limit`
+/// at least 2
+pub fn p123_with(limit: usize) -> Answer {
+  // the remainder is 2 for even n and 2 n p_n for odd n >= 3, where 2n < p_n
+  let res = primal::Primes::all()
+    .enumerate()
+    .skip(2)
+    .step_by(2)
+    .map(|(i, p)| (i + 1, p))
+    .find(|&(n, p)| 2 * n * p > limit)
+    .unwrap()
+    .0;
+  Answer::from(res)
+}
 
 /// Ordered radicals
-pub fn p124() -> String {
-  const MAX: usize = 100_001;
+pub fn p124() -> Answer {
+  p124_with(100_000, 10_000)
+}
 
-  fn get_rads() -> Vec<(usize, usize)> {
-    let sieve = primal::Sieve::new(MAX);
-    let mut rads = [1 as usize; MAX];
-    for n in 1..sieve.prime_pi(MAX) + 1 {
+/// The `k`th n of 1 <= n <= `limit` sorted by rad(n) then n, 0 when k exceeds limit
+pub fn p124_with(limit: usize, k: usize) -> Answer {
+  fn get_rads(limit: usize) -> Vec<(usize, usize)> {
+    let max = limit + 1;
+    let sieve = primal::Sieve::new(max);
+    let mut rads = vec![1 as usize; max];
+    for n in 1..sieve.prime_pi(max) + 1 {
       let p = sieve.nth_prime(n);
       let mut i = p;
-      while i < MAX {
+      while i < max {
         rads[i] *= p;
         i += p;
       }
@@ -156,58 +178,73 @@ pub fn p124() -> String {
     rads
   }
 
-  let (_, res) = get_rads()[10_000];
-  assert_eq!(res, 21417);
-  format!("p124 = {}", res)
-} // 21417
+  // rad(0) = 1 sorts 0 first, so the kth n is at index k
+  let res = get_rads(limit).get(k).map_or(0, |&(_, n)| n);
+  Answer::from(res)
+}
 
-/// Palindromic sums
-pub fn p125() -> String {
-  fn sof_sqrs(n: usize) -> Vec<usize> {
-    let limit = (n as f64).sqrt() as usize + 1;
-    (0..limit)
-      .scan(0, |state, x| {
-        *state += x * x;
-        Some(*state)
-      })
-      .collect()
-  }
+// Returns the sums of the first squares 0, 0 + 1, 0 + 1 + 4, ... up to sqrt(n)
+fn sof_sqrs(n: usize) -> Vec<usize> {
+  let limit = (n as f64).sqrt() as usize + 1;
+  (0..limit)
+    .scan(0, |state, x| {
+      *state += x * x;
+      Some(*state)
+    })
+    .collect()
+}
 
-  fn palindromic_sof_sqrs(n: usize) -> HashSet<usize> {
-    let sofs = sof_sqrs(n);
-    let mut res = HashSet::new();
-    for (i, vi) in sofs.iter().enumerate().take(sofs.len() - 2) {
-      for vj in sofs.iter().skip(i + 2) {
-        let v = vj - vi;
-        if v >= n {
-          break;
-        }
-        if eu::is_palindrome(v) {
-          res.insert(v);
-        }
+// Returns the palindromes below n that are a sum of at least two consecutive squares
+fn palindromic_sof_sqrs(n: usize) -> HashSet<usize> {
+  let sofs = sof_sqrs(n);
+  let mut res = HashSet::new();
+  for (i, vi) in sofs.iter().enumerate().take(sofs.len().saturating_sub(2)) {
+    for vj in sofs.iter().skip(i + 2) {
+      let v = vj - vi;
+      if v >= n {
+        break;
+      }
+      if eu::is_palindrome(v) {
+        res.insert(v);
       }
     }
-    res
   }
+  res
+}
 
+/// Checks the examples given in the statement of p125
+pub fn p125_examples() {
   let res = palindromic_sof_sqrs(1000).iter().fold(0, |acc, x| acc + x);
   assert_eq!(res, 4164);
+}
 
-  let res = palindromic_sof_sqrs((10 as usize).pow(8))
+/// Palindromic sums
+pub fn p125() -> Answer {
+  p125_with((10 as usize).pow(8))
+}
+
+/// Sum of the palindromes below `limit` that are a sum of consecutive squares
+pub fn p125_with(limit: usize) -> Answer {
+  let res = palindromic_sof_sqrs(limit)
     .iter()
     .fold(0, |acc, x| acc + x);
-  assert_eq!(res, 2906969179);
-  format!("p125 = {}", res)
-} // 2906969179
+  Answer::from(res)
+}
 
 /// Cuboid layers
-pub fn p126() -> String {
+pub fn p126() -> Answer {
+  p126_with(1000)
+}
+
+/// Least number of cubes in a layer of a cuboid for which there are exactly `n` cuboid layers of that size,
+/// `n` at least 1
+pub fn p126_with(n: usize) -> Answer {
   fn f(x: u32, y: u32, z: u32, l: u32) -> u32 {
     2 * (x * y + x * z + y * z) + 4 * (l - 1) * (x + y + z + l - 2)
   }
 
-  fn solve(n: u32) -> usize {
-    let limit = n * 20;
+  // counts of the layers of up to limit cubes, None when none has exactly n
+  fn solve(n: u32, limit: u32) -> Option<usize> {
     let mut xs = vec![0; limit as usize + 1];
 
     for x in 1..limit {
@@ -229,17 +266,27 @@ pub fn p126() -> String {
         }
       }
     }
-    xs.iter().position(|&x| x == n).unwrap()
+    xs.iter().position(|&x| x == n)
   }
 
-  let res = solve(1000);
-  assert_eq!(res, 18522);
-  format!("p126 = {}", res)
-} // 18522
+  // the counts up to limit are complete, so the limit is doubled until one of them is n
+  let mut limit = n as u32 * 20;
+  loop {
+    if let Some(res) = solve(n as u32, limit) {
+      return Answer::from(res);
+    }
+    limit *= 2
+  }
+}
 
-/// abc-hits - unimplemented
-pub fn p127() -> String {
-  const MAX: usize = 120001;
+/// abc-hits
+pub fn p127() -> Answer {
+  p127_with(120_000)
+}
+
+/// Sum of c of the abc-hits with c < `limit`
+pub fn p127_with(limit: usize) -> Answer {
+  let max = limit.max(3);
 
   #[derive(Debug, Clone, Copy)]
   struct Rad {
@@ -247,14 +294,14 @@ pub fn p127() -> String {
     rad: usize,
   }
 
-  fn get_rads() -> Vec<Rad> {
-    let sieve = primal::Sieve::new(MAX);
-    let mut rads = vec![Rad { n: 1, rad: 1 }; MAX];
+  fn get_rads(max: usize) -> Vec<Rad> {
+    let sieve = primal::Sieve::new(max);
+    let mut rads = vec![Rad { n: 1, rad: 1 }; max];
 
-    for n in 1..sieve.prime_pi(MAX) + 1 {
+    for n in 1..sieve.prime_pi(max) + 1 {
       let p = sieve.nth_prime(n);
       let mut i = p;
-      while i < MAX {
+      while i < max {
         rads[i].rad *= p;
         i += p;
       }
@@ -267,7 +314,7 @@ pub fn p127() -> String {
     rads
   }
 
-  let rads = get_rads();
+  let rads = get_rads(max);
   let mut rads_sorted = rads.clone();
   rads_sorted.sort_by(|a, b| a.rad.cmp(&b.rad));
   let mut res = 0;
@@ -288,26 +335,66 @@ pub fn p127() -> String {
     }
   }
 
-  assert_eq!(res, 18407904);
-  format!("p127 = {}", res)
-} // 18407904
+  Answer::from(res)
+}
 
 /// Hexagonal tile differences - unimplemented
-pub fn p128() -> String {
-  "p128 = unimplemented".to_string()
+pub fn p128() -> Answer {
+  Answer::from("unimplemented")
 }
 
 /// Repunit divisibility - unimplemented
-pub fn p129() -> String {
-  "p129 = unimplemented".to_string()
+pub fn p129() -> Answer {
+  Answer::from("unimplemented")
 }
 
 /// Composites with prime repunit property - unimplemented
-pub fn p130() -> String {
-  "p130 = unimplemented".to_string()
+pub fn p130() -> Answer {
+  Answer::from("unimplemented")
 }
 
-/// Returns (start, Vec of solution functions) for all solutions in this crate.
-pub fn get_functions() -> (u32, Vec<fn() -> String>) {
-  (121, vec![p121, p122, p123, p124, p125, p126, p127])
+solutions! {
+  #![euler(problems = 121..=130)]
+
+  #[euler(problem = 121, title = "Disc game prize fund")]
+  p121
+    .examples(p121_examples)
+    .params(vec![Param::new("turns", 15, "game of this many turns").at_least(1).at_most(15)], |v| p121_with(v[0] as usize)),
+  #[euler(problem = 122, title = "Efficient exponentiation")]
+  p122
+    .params(vec![Param::new("n", 200, "powers n^k for k up to n").at_most(200)], |v| p122_with(v[0] as usize)),
+  #[euler(problem = 123, title = "Prime square remainders")]
+  p123
+    .params(
+      vec![Param::new("limit", 10_000_000_000, "remainder exceeding limit").at_least(2).at_most(1_000_000_000_000_000)],
+      |v| p123_with(v[0] as usize)
+    ),
+  #[euler(problem = 124, title = "Ordered radicals")]
+  p124
+    .params(
+      vec![
+        Param::new("limit", 100_000, "n up to limit").at_most(1_000_000),
+        Param::new("k", 10_000, "the kth n").at_least(1),
+      ],
+      |v| p124_with(v[0] as usize, v[1] as usize)
+    ),
+  #[euler(problem = 125, title = "Palindromic sums")]
+  p125
+    .examples(p125_examples)
+    .params(
+      vec![Param::new("limit", 100_000_000, "sum the palindromes below limit").at_most(10_000_000_000_000)],
+      |v| p125_with(v[0] as usize)
+    ),
+  #[euler(problem = 126, title = "Cuboid layers")]
+  p126
+    .params(vec![Param::new("n", 1000, "exactly n cuboid layers").at_least(1).at_most(10_000)], |v| p126_with(v[0] as usize)),
+  #[euler(problem = 127, title = "abc-hits")]
+  p127
+    .params(vec![Param::new("limit", 120_000, "c below limit").at_most(1_000_000)], |v| p127_with(v[0] as usize)),
+  #[euler(problem = 128, title = "Hexagonal tile differences")]
+  p128.unimplemented(),
+  #[euler(problem = 129, title = "Repunit divisibility")]
+  p129.unimplemented(),
+  #[euler(problem = 130, title = "Composites with prime repunit property")]
+  p130.unimplemented(),
 }
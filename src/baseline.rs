@@ -0,0 +1,162 @@
+//! Saved per-problem timings and regression detection against them.
+//!
+//! A baseline is a JSON file mapping problem ids to execution times in fractional seconds. Benchmark runs
+//! store the median time, normal runs the single measured time.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::baseline::Baseline;
+//!
+//! fn main() {
+//!     let baseline = Baseline::new(vec![("p060".to_string(), 1.0), ("p092".to_string(), 0.5)]);
+//!     let current = vec![("p060".to_string(), 1.5), ("p092".to_string(), 0.52), ("p001".to_string(), 0.1)];
+//!
+//!     let comparison = baseline.compare(&current, 10.0);
+//!     assert_eq!(comparison.changes.len(), 2);
+//!     assert_eq!(comparison.regressions().len(), 1);
+//!     assert_eq!(comparison.regressions()[0].id, "p060");
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use output::SCHEMA_VERSION;
+use serde_json::Value;
+
+/// Increases smaller than this many seconds are treated as timer noise and never flagged.
+pub const NOISE_FLOOR: f64 = 0.001;
+
+/// Per-problem execution times in fractional seconds, keyed by problem id.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Baseline {
+  pub timings: BTreeMap<String, f64>,
+}
+
+impl Baseline {
+  /// Returns a baseline of `(problem id, seconds)` pairs.
+  pub fn new(timings: Vec<(String, f64)>) -> Baseline {
+    Baseline { timings: timings.into_iter().collect() }
+  }
+
+  /// Reads a baseline written by `save`.
+  pub fn load(path: &str) -> Result<Baseline, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("cannot read baseline {}: {}", path, e))?;
+    let value: Value = serde_json::from_str(&text).map_err(|e| format!("invalid baseline {}: {}", path, e))?;
+    let timings = value["timings_s"]
+      .as_object()
+      .ok_or(format!("invalid baseline {}: missing timings_s", path))?
+      .iter()
+      .filter_map(|(id, t)| t.as_f64().map(|t| (id.clone(), t)))
+      .collect();
+    Ok(Baseline { timings })
+  }
+
+  /// Writes the baseline to `path` as JSON.
+  pub fn save(&self, path: &str) -> Result<(), String> {
+    let value = json!({ "schema_version": SCHEMA_VERSION, "timings_s": self.timings });
+    fs::write(path, serde_json::to_string_pretty(&value).unwrap() + "\n")
+      .map_err(|e| format!("cannot write baseline {}: {}", path, e))
+  }
+
+  /// Compares `current` timings with the baseline; an increase of more than `threshold` percent is a
+  /// regression. Problems missing from either side are left out.
+  pub fn compare(&self, current: &[(String, f64)], threshold: f64) -> Comparison {
+    let changes = current
+      .iter()
+      .filter_map(|&(ref id, t)| {
+        self.timings.get(id).map(|&base| Change {
+          id: id.clone(),
+          baseline: base,
+          current: t,
+          regression: t > base * (1.0 + threshold / 100.0) && t - base > NOISE_FLOOR,
+        })
+      })
+      .collect();
+    Comparison { threshold, changes }
+  }
+}
+
+/// Timing of one problem against its baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+  pub id: String,
+  pub baseline: f64,
+  pub current: f64,
+  pub regression: bool,
+}
+
+impl Change {
+  /// Returns the relative change in percent, positive when slower.
+  pub fn percent(&self) -> f64 {
+    if self.baseline > 0.0 {
+      (self.current / self.baseline - 1.0) * 100.0
+    } else {
+      0.0
+    }
+  }
+}
+
+/// Result of comparing a run with a baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+  /// Percentage increase above which a change is a regression.
+  pub threshold: f64,
+  pub changes: Vec<Change>,
+}
+
+impl Comparison {
+  /// Returns the changes flagged as regressions.
+  pub fn regressions(&self) -> Vec<&Change> {
+    self.changes.iter().filter(|c| c.regression).collect()
+  }
+
+  /// Returns the comparison as a JSON value.
+  pub fn to_json(&self) -> Value {
+    let changes = self
+      .changes
+      .iter()
+      .map(|c| {
+        json!({
+          "id": c.id,
+          "baseline_s": c.baseline,
+          "current_s": c.current,
+          "change_percent": c.percent(),
+          "regression": c.regression,
+        })
+      })
+      .collect::<Vec<_>>();
+    json!({
+      "threshold_percent": self.threshold,
+      "regressions": self.regressions().len(),
+      "changes": changes,
+    })
+  }
+
+  /// Returns the comparison as text, listing regressions only.
+  pub fn to_text(&self) -> String {
+    let regressions = self.regressions();
+    let mut s = format!(
+      "\n{} of {} problems regressed by more than {}% against the baseline\n",
+      regressions.len(),
+      self.changes.len(),
+      self.threshold
+    );
+    for c in regressions {
+      s += &format!("  {:7} {:.6} s -> {:.6} s ({:+.1}%)\n", c.id, c.baseline, c.current, c.percent());
+    }
+    s
+  }
+
+  /// Returns the regressions as CSV comment lines.
+  pub fn to_csv_comments(&self) -> String {
+    let mut s = format!("# baseline_threshold_percent: {}\n", self.threshold);
+    for c in self.regressions() {
+      s += &format!("# regression: {},{:.6},{:.6},{:+.1}\n", c.id, c.baseline, c.current, c.percent());
+    }
+    s
+  }
+}
@@ -0,0 +1,215 @@
+//! Statistical benchmarking of solutions.
+//!
+//! Unlike a normal run, which times each solution once while it competes with the others for the rayon
+//! pool, a benchmark runs the solutions one after another on the calling thread. Each solution runs
+//! `warmup` untimed times, then `iterations` timed times, each run limited to `timeout` if set.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::bench::{self, BenchConfig, Stats};
+//!
+//! # #[cfg(feature = "euler_p001_010")]
+//! fn main() {
+//!     let solutions = euler_rust::euler_p001_010::get_functions();
+//!     let config = BenchConfig { warmup: 1, iterations: 5, ..BenchConfig::default() };
+//!     let results = bench::bench(&solutions[..2], &config);
+//!     assert_eq!(results.len(), 2);
+//!     assert_eq!(results[0].samples.len(), 5);
+//!
+//!     let stats = Stats::new(&[1.0, 2.0, 3.0, 4.0, 100.0]);
+//!     assert_eq!(stats.min, 1.0);
+//!     assert_eq!(stats.median, 3.0);
+//!     assert_eq!(stats.mean, 22.0);
+//!     assert_eq!(stats.outliers_severe, 1);
+//! }
+//! # #[cfg(not(feature = "euler_p001_010"))]
+//! # fn main() {}
+//! ```
+
+use std::time::Duration;
+
+use euler_library::solution::{Solution, SolutionResult};
+use output::{self, Format, SCHEMA_VERSION};
+use serde_json::Value;
+
+/// Number of untimed and timed runs of each solution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchConfig {
+  pub warmup: usize,
+  pub iterations: usize,
+  /// Time limit of each single run.
+  pub timeout: Option<Duration>,
+}
+
+impl Default for BenchConfig {
+  fn default() -> BenchConfig {
+    BenchConfig { warmup: 1, iterations: 10, timeout: None }
+  }
+}
+
+/// Summary statistics of a set of timings in fractional seconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+  pub min: f64,
+  pub median: f64,
+  pub mean: f64,
+  /// Sample standard deviation, 0 for fewer than two samples.
+  pub stddev: f64,
+  /// Samples more than 1.5 but at most 3 interquartile ranges outside the quartiles.
+  pub outliers_mild: usize,
+  /// Samples more than 3 interquartile ranges outside the quartiles.
+  pub outliers_severe: usize,
+}
+
+impl Stats {
+  /// Returns the statistics of `samples`, which must not be empty.
+  pub fn new(samples: &[f64]) -> Stats {
+    let mut xs = samples.to_vec();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = xs.len() as f64;
+    let mean = xs.iter().sum::<f64>() / n;
+    let stddev = if xs.len() < 2 {
+      0.0
+    } else {
+      (xs.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0)).sqrt()
+    };
+
+    let (q1, q3) = (quantile(&xs, 0.25), quantile(&xs, 0.75));
+    let iqr = q3 - q1;
+    let outside = |k: f64| xs.iter().filter(|&&x| x < q1 - k * iqr || x > q3 + k * iqr).count();
+    let (mild_or_severe, severe) = (outside(1.5), outside(3.0));
+
+    Stats {
+      min: xs[0],
+      median: quantile(&xs, 0.5),
+      mean,
+      stddev,
+      outliers_mild: mild_or_severe - severe,
+      outliers_severe: severe,
+    }
+  }
+}
+
+// Returns quantile q of sorted xs, interpolating linearly between samples
+fn quantile(xs: &[f64], q: f64) -> f64 {
+  let pos = q * (xs.len() - 1) as f64;
+  let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+  xs[lo] + (xs[hi] - xs[lo]) * (pos - lo as f64)
+}
+
+/// Timings of one benchmarked solution.
+///
+/// If a run fails or times out the benchmark of that solution stops and `samples` ends with the failed run.
+#[derive(Clone, Debug)]
+pub struct BenchResult {
+  /// Result of the last timed run.
+  pub result: SolutionResult,
+  pub samples: Vec<f64>,
+  pub stats: Stats,
+}
+
+/// Benchmarks `solutions` sequentially on the calling thread; `config.iterations` must not be 0.
+pub fn bench(solutions: &[Solution], config: &BenchConfig) -> Vec<BenchResult> {
+  solutions.iter().map(|s| bench_one(s, config)).collect()
+}
+
+// A failed or timed out run ends the benchmark of that solution and becomes its result
+fn bench_one(solution: &Solution, config: &BenchConfig) -> BenchResult {
+  let mut samples = Vec::new();
+  let mut result = None;
+  for i in 0..config.warmup + config.iterations {
+    let res = ::run_one(solution, config.timeout);
+    if i >= config.warmup || !res.status.is_ok() {
+      samples.push(res.elapsed);
+    }
+    let failed = !res.status.is_ok();
+    result = Some(res);
+    if failed {
+      break;
+    }
+  }
+  let stats = Stats::new(&samples);
+  BenchResult { result: result.unwrap(), samples, stats }
+}
+
+/// Returns benchmark results rendered in `format`.
+pub fn render(results: &[BenchResult], config: &BenchConfig, format: Format) -> String {
+  match format {
+    Format::Text => to_text(results, config),
+    Format::Json => serde_json::to_string_pretty(&to_json(results, config)).unwrap() + "\n",
+    Format::Csv => to_csv(results, config),
+  }
+}
+
+/// Returns benchmark results as a JSON value.
+pub fn to_json(results: &[BenchResult], config: &BenchConfig) -> Value {
+  let xs = results
+    .iter()
+    .map(|b| {
+      let mut v = output::result_to_json(&b.result);
+      v["samples_s"] = json!(b.samples);
+      v["min_s"] = json!(b.stats.min);
+      v["median_s"] = json!(b.stats.median);
+      v["mean_s"] = json!(b.stats.mean);
+      v["stddev_s"] = json!(b.stats.stddev);
+      v["outliers_mild"] = json!(b.stats.outliers_mild);
+      v["outliers_severe"] = json!(b.stats.outliers_severe);
+      v
+    })
+    .collect::<Vec<_>>();
+  json!({
+    "schema_version": SCHEMA_VERSION,
+    "mode": "bench",
+    "warmup": config.warmup,
+    "iterations": config.iterations,
+    "results": xs,
+  })
+}
+
+fn to_text(results: &[BenchResult], config: &BenchConfig) -> String {
+  let mut s = format!(
+    "Benchmarked {} Euler functions sequentially, {} warmup and {} timed runs each\n\n",
+    results.len(),
+    config.warmup,
+    config.iterations
+  );
+  s += &format!(
+    "{:25}  {:>10}  {:>10}  {:>10}  {:>10}  outliers (mild/severe)\n",
+    "", "min s", "median s", "mean s", "stddev s"
+  );
+  for b in results {
+    s += &format!(
+      "{:25}  {:10.6}  {:10.6}  {:10.6}  {:10.6}  {}/{}\n",
+      b.result, b.stats.min, b.stats.median, b.stats.mean, b.stats.stddev, b.stats.outliers_mild, b.stats.outliers_severe
+    );
+  }
+  s + &output::failures_text(&results.iter().map(|b| b.result.clone()).collect::<Vec<_>>())
+}
+
+fn to_csv(results: &[BenchResult], config: &BenchConfig) -> String {
+  let mut s = format!("# schema_version: {}\n", SCHEMA_VERSION);
+  s += &format!("# warmup: {}\n# iterations: {}\n", config.warmup, config.iterations);
+  s += "id,problem,variant,status,answer,min_s,median_s,mean_s,stddev_s,outliers_mild,outliers_severe,task\n";
+  for b in results {
+    let r = &b.result;
+    s += &format!(
+      "{},{},{},{},{},{:.6},{:.6},{:.6},{:.6},{},{},{}\n",
+      r.id(),
+      r.problem,
+      r.variant.unwrap_or(""),
+      r.status,
+      output::csv_field(&r.answer.as_ref().map_or(String::new(), |a| a.to_string())),
+      b.stats.min,
+      b.stats.median,
+      b.stats.mean,
+      b.stats.stddev,
+      b.stats.outliers_mild,
+      b.stats.outliers_severe,
+      r.task
+    );
+  }
+  s
+}
@@ -0,0 +1,538 @@
+//! Command line options of the runner.
+//!
+//! ```text
+//! euler_rust [SELECTION]... [--format json|csv|text] [--bench] [--warmup N] [--iterations N]
+//!            [--save-baseline FILE] [--baseline FILE] [--threshold PERCENT] [--timeout SECONDS]
+//!            [--jobs N | --sequential] [--order time|selection|problem] [--verify] [--answers FILE]
+//!            [--mode answers|examples|both] [--param NAME=VALUE]... [--data FILE] [--seed N]
+//!            [--isolate] [--memory-limit SIZE] [--cpu-limit SECONDS] [--progress | --no-progress]
+//!            [--run-log FILE | --no-run-log] [--race]
+//! euler_rust history [SELECTION]... [--run-log FILE] [--format json|csv|text] [--last N]
+//! euler_rust coverage [SELECTION]... [--format json|csv|text]
+//! euler_rust serve [--port N] [--timeout SECONDS]
+//! euler_rust report [SELECTION]... [--html FILE] [--markdown FILE] [--mask-answers] [--timeout SECONDS]
+//! ```
+//!
+//! Selection terms are joined with commas, see module [`selection`](../selection/index.html).
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::cli::{Command, Mode, Options, ServeOptions};
+//! #[cfg(unix)]
+//! use euler_rust::isolate::Limits;
+//! use euler_rust::output::Format;
+//! use euler_rust::schedule::Order;
+//! use euler_rust::server::Server;
+//!
+//! fn main() {
+//!     let args = vec!["1-50", "!7", "--format", "json"].into_iter().map(String::from);
+//!     let options = Options::parse(args).unwrap();
+//!     assert_eq!(options.selection, Some("1-50,!7".to_string()));
+//!     assert_eq!(options.format, Format::Json);
+//!
+//!     let options = Options::parse(vec!["--iterations=20".to_string()]).unwrap();
+//!     assert_eq!(options.bench.unwrap().iterations, 20);
+//!
+//!     let args = vec!["--baseline", "base.json", "--threshold", "25"].into_iter().map(String::from);
+//!     let options = Options::parse(args).unwrap();
+//!     assert_eq!(options.baseline, Some("base.json".to_string()));
+//!     assert_eq!(options.threshold, 25.0);
+//!     for bad in &["NaN", "inf", "-5"] {
+//!         let e = Options::parse(vec!["--threshold".to_string(), bad.to_string()]).unwrap_err();
+//!         assert_eq!(e, format!("invalid value for --threshold: {}", bad));
+//!     }
+//!
+//!     let options = Options::parse(vec!["--timeout=2.5".to_string()]).unwrap();
+//!     assert_eq!(options.timeout, Some(std::time::Duration::from_millis(2500)));
+//!     for bad in &["NaN", "0", "1e300"] {
+//!         let e = Options::parse(vec!["--timeout".to_string(), bad.to_string()]).unwrap_err();
+//!         assert_eq!(e, format!("invalid value for --timeout: {}", bad));
+//!     }
+//!
+//!     assert_eq!(Options::parse(vec!["--jobs=3".to_string()]).unwrap().jobs, Some(3));
+//!
+//!     let options = Options::parse(vec!["--order=selection".to_string()]).unwrap();
+//!     assert_eq!(options.order, Order::Selection);
+//!
+//!     let options = Options::parse(vec!["--answers=answers.json".to_string()]).unwrap();
+//!     assert!(options.verify);
+//!
+//!     assert_eq!(Options::parse(vec!["--mode=examples".to_string()]).unwrap().mode, Mode::Examples);
+//!
+//!     let args = vec!["87", "--param", "limit=10^6"].into_iter().map(String::from);
+//!     assert_eq!(Options::parse(args).unwrap().params, vec![("limit".to_string(), 1_000_000)]);
+//!
+//!     let args = vec!["67", "--data", "-"].into_iter().map(String::from);
+//!     assert_eq!(Options::parse(args).unwrap().data, Some("-".to_string()));
+//!     assert_eq!(Options::parse(vec!["--seed=42".to_string()]).unwrap().seed, Some(42));
+//!
+//!     let args = vec!["--memory-limit", "512M", "--cpu-limit", "30"].into_iter().map(String::from);
+//!     #[cfg(unix)]
+//!     assert_eq!(Options::parse(args).unwrap().isolate, Some(Limits { memory: Some(512 << 20), cpu: Some(30) }));
+//!     #[cfg(not(unix))]
+//!     assert!(Options::parse(args).is_err());
+//!
+//!     assert!(Options::parse(vec!["--progress".to_string()]).unwrap().progress);
+//!     assert!(!Options::parse(vec!["--no-progress".to_string()]).unwrap().progress);
+//!
+//!     assert_eq!(Options::parse(vec!["--no-run-log".to_string()]).unwrap().run_log, None);
+//!     let args = vec!["history", "67", "--last", "5"].into_iter().map(String::from);
+//!     match Command::parse(args).unwrap() {
+//!         Command::History(h) => assert_eq!((h.selection, h.last), (Some("67".to_string()), Some(5))),
+//!         _ => panic!("expected the history command"),
+//!     }
+//!     assert!(Options::parse(vec!["--race".to_string()]).unwrap().race);
+//!
+//!     let args = vec!["serve", "--port", "9000"].into_iter().map(String::from);
+//!     let serve = ServeOptions { port: 9000, timeout: Server::DEFAULT_TIMEOUT };
+//!     assert_eq!(Command::parse(args).unwrap(), Command::Serve(serve));
+//!     let args = vec!["report", "--markdown", "-", "--mask-answers"].into_iter().map(String::from);
+//!     match Command::parse(args).unwrap() {
+//!         Command::Report(r) => assert_eq!((r.html, r.markdown, r.mask), (None, Some("-".to_string()), true)),
+//!         _ => panic!("expected the report command"),
+//!     }
+//!
+//!     assert!(Options::parse(vec!["--format=xml".to_string()]).is_err());
+//! }
+//! ```
+
+use std::io::{self, IsTerminal};
+use std::str::FromStr;
+use std::time::Duration;
+
+use bench::BenchConfig;
+use euler_library::solution;
+#[cfg(unix)]
+use isolate::{self, Limits};
+use output::Format;
+use runlog;
+use schedule::Order;
+use server::Server;
+
+/// What the command line asks for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+  /// Run a selection of solutions, unless another command is named.
+  Run(Box<Options>),
+  /// Show the timing trend of a selection of problems across logged runs, `euler_rust history`.
+  History(HistoryOptions),
+  /// Show how many of the selected problems are solved, `euler_rust coverage`.
+  Coverage(CoverageOptions),
+  /// Serve the registered solutions over HTTP on localhost, `euler_rust serve`.
+  Serve(ServeOptions),
+  /// Run a selection of problems and write HTML and Markdown reports of it, `euler_rust report`.
+  Report(ReportOptions),
+}
+
+impl Command {
+  /// Parses command line arguments, not including the program name.
+  pub fn parse<I>(args: I) -> Result<Command, String>
+  where
+    I: IntoIterator<Item = String>,
+  {
+    let mut args = args.into_iter().peekable();
+    match args.peek().map(String::as_str) {
+      Some("history") => {
+        args.next();
+        HistoryOptions::parse(args).map(Command::History)
+      }
+      Some("coverage") => {
+        args.next();
+        CoverageOptions::parse(args).map(Command::Coverage)
+      }
+      Some("serve") => {
+        args.next();
+        ServeOptions::parse(args).map(Command::Serve)
+      }
+      Some("report") => {
+        args.next();
+        ReportOptions::parse(args).map(Command::Report)
+      }
+      _ => Options::parse(args).map(|options| Command::Run(Box::new(options))),
+    }
+  }
+}
+
+/// Options of the `history` command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryOptions {
+  /// Selection expression, `None` selects every solution with logged runs.
+  pub selection: Option<String>,
+  /// Run log read, `None` when there is none, see `runlog::default_log`.
+  pub run_log: Option<String>,
+  pub format: Format,
+  /// Number of most recent runs shown of each problem, `None` for all of them.
+  pub last: Option<usize>,
+}
+
+impl HistoryOptions {
+  /// Parses the arguments following `history`.
+  pub fn parse<I>(args: I) -> Result<HistoryOptions, String>
+  where
+    I: IntoIterator<Item = String>,
+  {
+    let mut options =
+      HistoryOptions { selection: None, run_log: runlog::default_log(), format: Format::Text, last: None };
+    options.selection = scan_args(args, Some("history"), |flag, value| {
+      match flag {
+        "--run-log" => options.run_log = Some(value()?),
+        "--format" => options.format = value()?.parse()?,
+        "--last" => match parse_number(flag, value()?)? {
+          0 => return Err("--last must be at least 1".to_string()),
+          n => options.last = Some(n),
+        },
+        _ => return Err(format!("unknown option for history: {}", flag)),
+      }
+      Ok(())
+    })?;
+    Ok(options)
+  }
+}
+
+/// Options of the `coverage` command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoverageOptions {
+  /// Selection expression, `None` selects every registered solution.
+  pub selection: Option<String>,
+  pub format: Format,
+}
+
+impl CoverageOptions {
+  /// Parses the arguments following `coverage`.
+  pub fn parse<I>(args: I) -> Result<CoverageOptions, String>
+  where
+    I: IntoIterator<Item = String>,
+  {
+    let mut format = Format::Text;
+    let selection = scan_args(args, Some("coverage"), |flag, value| {
+      match flag {
+        "--format" => format = value()?.parse()?,
+        _ => return Err(format!("unknown option for coverage: {}", flag)),
+      }
+      Ok(())
+    })?;
+    Ok(CoverageOptions { selection, format })
+  }
+}
+
+/// Options of the `serve` command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServeOptions {
+  /// Port listened on at `127.0.0.1`.
+  pub port: u16,
+  /// Time after which a running solution is reported as timed out, by default
+  /// [`Server::DEFAULT_TIMEOUT`](../server/struct.Server.html#associatedconstant.DEFAULT_TIMEOUT).
+  pub timeout: Duration,
+}
+
+impl ServeOptions {
+  /// Parses the arguments following `serve`.
+  pub fn parse<I>(args: I) -> Result<ServeOptions, String>
+  where
+    I: IntoIterator<Item = String>,
+  {
+    let mut options = ServeOptions { port: 8080, timeout: Server::DEFAULT_TIMEOUT };
+    let selection = scan_args(args, Some("serve"), |flag, value| {
+      match flag {
+        "--port" => {
+          let v = value()?;
+          options.port = v.parse().map_err(|_| format!("invalid value for {}: {}", flag, v))?;
+        }
+        "--timeout" => options.timeout = parse_timeout(flag, value()?)?,
+        _ => return Err(format!("unknown option for serve: {}", flag)),
+      }
+      Ok(())
+    })?;
+    match selection {
+      Some(terms) => Err(format!("unexpected argument for serve: {}", terms)),
+      None => Ok(options),
+    }
+  }
+}
+
+/// Options of the `report` command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportOptions {
+  /// Selection expression, `None` selects every main solution.
+  pub selection: Option<String>,
+  /// File the HTML page is written to, `-` for standard output, `None` for no page.
+  pub html: Option<String>,
+  /// File the Markdown table is written to, `-` for standard output, `None` for no table.
+  pub markdown: Option<String>,
+  /// Hide the answers.
+  pub mask: bool,
+  /// Time after which a running solution is reported as timed out.
+  pub timeout: Option<Duration>,
+}
+
+impl ReportOptions {
+  /// Parses the arguments following `report`. Both reports are written, to `report.html` and `report.md`,
+  /// unless `--html` or `--markdown` names the one to write.
+  pub fn parse<I>(args: I) -> Result<ReportOptions, String>
+  where
+    I: IntoIterator<Item = String>,
+  {
+    let mut options = ReportOptions { selection: None, html: None, markdown: None, mask: false, timeout: None };
+    options.selection = scan_args(args, Some("report"), |flag, value| {
+      match flag {
+        "--html" => options.html = Some(value()?),
+        "--markdown" => options.markdown = Some(value()?),
+        "--mask-answers" => options.mask = true,
+        "--timeout" => options.timeout = Some(parse_timeout(flag, value()?)?),
+        _ => return Err(format!("unknown option for report: {}", flag)),
+      }
+      Ok(())
+    })?;
+    if options.html.is_none() && options.markdown.is_none() {
+      options.html = Some("report.html".to_string());
+      options.markdown = Some("report.md".to_string());
+    }
+    Ok(options)
+  }
+}
+
+// Parses the arguments of `command`, or of a run when `None`, handing every `--flag` with a way to take its
+// value to `option`, and returns the selection terms joined with commas
+fn scan_args<I, F>(args: I, command: Option<&str>, mut option: F) -> Result<Option<String>, String>
+where
+  I: IntoIterator<Item = String>,
+  F: FnMut(&str, &mut dyn FnMut() -> Result<String, String>) -> Result<(), String>,
+{
+  let mut terms: Vec<String> = Vec::new();
+  let mut args = args.into_iter();
+
+  while let Some(arg) = args.next() {
+    if !arg.starts_with("--") {
+      terms.push(arg);
+      continue;
+    }
+    let (flag, inline) = match arg.find('=') {
+      Some(pos) => (arg[..pos].to_string(), Some(arg[pos + 1..].to_string())),
+      None => (arg.clone(), None),
+    };
+    let mut value = || {
+      let missing = match command {
+        Some(command) => format!("missing value for {} {}", command, flag),
+        None => format!("missing value for {}", flag),
+      };
+      inline.clone().or_else(|| args.next()).ok_or(missing)
+    };
+    option(&flag, &mut value)?;
+  }
+
+  Ok(if terms.is_empty() { None } else { Some(terms.join(",")) })
+}
+
+/// What to run of each selected problem.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+  /// Compute the full-size answers.
+  Answers,
+  /// Only check the small examples from the problem statements, a fast smoke test; problems without
+  /// examples are skipped.
+  Examples,
+  /// Check the examples of each problem, then compute its answer.
+  Both,
+}
+
+impl FromStr for Mode {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Mode, String> {
+    match s {
+      "answers" => Ok(Mode::Answers),
+      "examples" => Ok(Mode::Examples),
+      "both" => Ok(Mode::Both),
+      _ => Err(format!("unknown mode: {} (expected answers, examples or both)", s)),
+    }
+  }
+}
+
+/// Options controlling what the runner executes and how it reports.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+  /// Selection expression, `None` selects every main solution.
+  pub selection: Option<String>,
+  pub format: Format,
+  /// Benchmark instead of running once, set by `--bench`, `--warmup` or `--iterations`.
+  pub bench: Option<BenchConfig>,
+  /// File the run's timings are saved to as a new baseline.
+  pub save_baseline: Option<String>,
+  /// Baseline file the run's timings are compared with.
+  pub baseline: Option<String>,
+  /// Percentage slowdown against the baseline counted as a regression.
+  pub threshold: f64,
+  /// Time limit of each solution; slower solutions are reported as timed out.
+  pub timeout: Option<Duration>,
+  /// Number of solutions run at the same time, `None` for one per logical CPU.
+  pub jobs: Option<usize>,
+  /// Run the solutions one after another on the calling thread.
+  pub sequential: bool,
+  /// Order results are reported in.
+  pub order: Order,
+  /// Check answers against the answers manifest.
+  pub verify: bool,
+  /// Answers manifest used instead of the embedded one, implies `verify`.
+  pub answers: Option<String>,
+  pub mode: Mode,
+  /// Parameter values replacing the Euler problem's in every selected solution having that parameter.
+  pub params: Vec<(String, u64)>,
+  /// File read by every selected solution reading data instead of its embedded data file, `-` for
+  /// standard input.
+  pub data: Option<String>,
+  /// Seed of the random numbers every selected stochastic solution draws, a new one for each run when
+  /// `None`.
+  pub seed: Option<u64>,
+  /// Run each solution in a child process with these limits instead of on a thread of this one, set by
+  /// `--isolate`, `--memory-limit` or `--cpu-limit`. Unix only.
+  #[cfg(unix)]
+  pub isolate: Option<Limits>,
+  /// Stream results to standard error as solutions complete. `false` by default, `Options::parse`
+  /// defaults to whether standard error is a terminal.
+  pub progress: bool,
+  /// Run log the run's timings are appended to and the slowest solutions are started first by, see
+  /// modules [`runlog`](../runlog/index.html) and [`schedule`](../schedule/index.html). `None` by default,
+  /// `Options::parse` defaults to `runlog::default_log()`.
+  pub run_log: Option<String>,
+  /// Also run every variant of the selected problems and compare their timings and answers.
+  pub race: bool,
+}
+
+impl Default for Options {
+  fn default() -> Options {
+    Options {
+      selection: None,
+      format: Format::Text,
+      bench: None,
+      save_baseline: None,
+      baseline: None,
+      threshold: 10.0,
+      timeout: None,
+      jobs: None,
+      sequential: false,
+      order: Order::Time,
+      verify: false,
+      answers: None,
+      mode: Mode::Answers,
+      params: Vec::new(),
+      data: None,
+      seed: None,
+      #[cfg(unix)]
+      isolate: None,
+      progress: false,
+      run_log: None,
+      race: false,
+    }
+  }
+}
+
+impl Options {
+  /// Parses command line arguments, not including the program name.
+  pub fn parse<I>(args: I) -> Result<Options, String>
+  where
+    I: IntoIterator<Item = String>,
+  {
+    let mut options = Options {
+      progress: io::stderr().is_terminal(),
+      run_log: runlog::default_log(),
+      ..Options::default()
+    };
+    options.selection = scan_args(args, None, |flag, value| {
+      match flag {
+        "--format" => options.format = value()?.parse()?,
+        "--bench" => {
+          options.bench.get_or_insert_with(BenchConfig::default);
+        }
+        "--warmup" => options.bench.get_or_insert_with(BenchConfig::default).warmup = parse_number(flag, value()?)?,
+        "--iterations" => {
+          options.bench.get_or_insert_with(BenchConfig::default).iterations = parse_number(flag, value()?)?
+        }
+        "--save-baseline" => options.save_baseline = Some(value()?),
+        "--baseline" => options.baseline = Some(value()?),
+        "--threshold" => options.threshold = parse_threshold(flag, value()?)?,
+        "--timeout" => options.timeout = Some(parse_timeout(flag, value()?)?),
+        "--jobs" => match parse_number(flag, value()?)? {
+          0 => return Err("--jobs must be at least 1".to_string()),
+          n => options.jobs = Some(n),
+        },
+        "--sequential" => options.sequential = true,
+        "--order" => options.order = value()?.parse()?,
+        "--verify" => options.verify = true,
+        "--mode" => options.mode = value()?.parse()?,
+        "--param" => {
+          let v = value()?;
+          let pos = v.find('=').ok_or(format!("invalid value for {}: {} (expected NAME=VALUE)", flag, v))?;
+          let number = solution::parse_param_value(&v[pos + 1..]);
+          let number = number.map_err(|e| format!("invalid value for {}: {}", flag, e))?;
+          options.params.push((v[..pos].to_string(), number));
+        }
+        "--data" => options.data = Some(value()?),
+        "--seed" => {
+          let v = value()?;
+          options.seed = Some(v.parse().map_err(|_| format!("invalid value for {}: {}", flag, v))?);
+        }
+        #[cfg(unix)]
+        "--isolate" => {
+          options.isolate.get_or_insert_with(Limits::default);
+        }
+        #[cfg(unix)]
+        "--memory-limit" => {
+          let bytes = isolate::parse_size(&value()?).map_err(|e| format!("invalid value for {}: {}", flag, e))?;
+          options.isolate.get_or_insert_with(Limits::default).memory = Some(bytes);
+        }
+        #[cfg(unix)]
+        "--cpu-limit" => match parse_number(flag, value()?)? {
+          0 => return Err("--cpu-limit must be at least 1".to_string()),
+          n => options.isolate.get_or_insert_with(Limits::default).cpu = Some(n as u64),
+        },
+        #[cfg(not(unix))]
+        "--isolate" | "--memory-limit" | "--cpu-limit" => return Err(format!("{} is only supported on Unix", flag)),
+        "--progress" => options.progress = true,
+        "--no-progress" => options.progress = false,
+        "--run-log" => options.run_log = Some(value()?),
+        "--no-run-log" => options.run_log = None,
+        "--race" => options.race = true,
+        "--answers" => {
+          options.answers = Some(value()?);
+          options.verify = true;
+        }
+        _ => return Err(format!("unknown option: {}", flag)),
+      }
+      Ok(())
+    })?;
+
+    if options.sequential && options.jobs.is_some() {
+      return Err("--jobs and --sequential cannot be combined".to_string());
+    }
+    #[cfg(unix)]
+    {
+      if options.bench.is_some() && options.isolate.is_some() {
+        return Err("--isolate cannot be combined with benchmarks".to_string());
+      }
+    }
+    if options.bench.is_some_and(|b| b.iterations == 0) {
+      return Err("--iterations must be at least 1".to_string());
+    }
+    Ok(options)
+  }
+}
+
+fn parse_number(flag: &str, value: String) -> Result<usize, String> {
+  value.parse().map_err(|_| format!("invalid value for {}: {}", flag, value))
+}
+
+fn parse_threshold(flag: &str, value: String) -> Result<f64, String> {
+  match value.parse::<f64>() {
+    Ok(percent) if percent >= 0.0 && percent.is_finite() => Ok(percent),
+    _ => Err(format!("invalid value for {}: {}", flag, value)),
+  }
+}
+
+fn parse_timeout(flag: &str, value: String) -> Result<Duration, String> {
+  let secs = value.parse::<f64>().ok().filter(|&secs| secs > 0.0);
+  let duration = secs.and_then(|secs| Duration::try_from_secs_f64(secs).ok());
+  duration.ok_or_else(|| format!("invalid value for {}: {}", flag, value))
+}
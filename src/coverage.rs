@@ -0,0 +1,136 @@
+//! How many of the registered problems are solved, by crate.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::coverage::Coverage;
+//! use euler_rust::output::Format;
+//!
+//! # #[cfg(feature = "euler_p121_130")]
+//! fn main() {
+//!     let coverage = Coverage::new(&euler_rust::euler_p121_130::get_functions());
+//!     assert_eq!((coverage.problems(), coverage.solved()), (10, 7));
+//!     assert_eq!(coverage.unimplemented(), ["p128", "p129", "p130"]);
+//!     assert!(coverage.render(Format::Text).starts_with("7 of 10 problems solved (70.0%)"));
+//! }
+//! # #[cfg(not(feature = "euler_p121_130"))]
+//! # fn main() {}
+//! ```
+
+use euler_library::solution::{Implementation, Solution};
+use output::{self, Format, SCHEMA_VERSION};
+
+/// Registered solutions with how each stands towards its problem.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coverage {
+  /// `(id, crate, title, implementation)` of every solution, in registration order.
+  pub entries: Vec<(String, &'static str, &'static str, Implementation)>,
+}
+
+impl Coverage {
+  /// Returns the coverage of `solutions`.
+  pub fn new(solutions: &[Solution]) -> Coverage {
+    let entries = solutions.iter().map(|s| (s.id(), s.crate_name, s.title, s.implementation.clone())).collect();
+    Coverage { entries }
+  }
+
+  /// Returns the number of problems, counting those only registered as unimplemented.
+  pub fn problems(&self) -> usize {
+    self.entries.iter().filter(|e| !matches!(e.3, Implementation::Variant(_))).count()
+  }
+
+  /// Returns the number of problems with a main solution computing their answer.
+  pub fn solved(&self) -> usize {
+    self.entries.iter().filter(|e| e.3 == Implementation::Solved).count()
+  }
+
+  /// Returns the ids of the unimplemented placeholders.
+  pub fn unimplemented(&self) -> Vec<&str> {
+    self.entries.iter().filter(|e| e.3 == Implementation::Unimplemented).map(|e| e.0.as_str()).collect()
+  }
+
+  /// Returns `(id, main solution id)` of every variant.
+  pub fn variants(&self) -> Vec<(&str, &str)> {
+    self
+      .entries
+      .iter()
+      .filter_map(|e| match e.3 {
+        Implementation::Variant(ref of) => Some((e.0.as_str(), of.as_str())),
+        _ => None,
+      })
+      .collect()
+  }
+
+  /// Returns `(crate, problems, solved)` of every crate, in registration order.
+  pub fn crates(&self) -> Vec<(&'static str, usize, usize)> {
+    let mut xs: Vec<(&'static str, usize, usize)> = Vec::new();
+    for e in &self.entries {
+      if matches!(e.3, Implementation::Variant(_)) {
+        continue;
+      }
+      let solved = (e.3 == Implementation::Solved) as usize;
+      match xs.iter_mut().find(|x| x.0 == e.1) {
+        Some(x) => {
+          x.1 += 1;
+          x.2 += solved;
+        }
+        None => xs.push((e.1, 1, solved)),
+      }
+    }
+    xs
+  }
+
+  /// Returns the coverage rendered in `format`; CSV lists every solution.
+  pub fn render(&self, format: Format) -> String {
+    match format {
+      Format::Text => self.to_text(),
+      Format::Json => {
+        let value = json!({
+          "schema_version": SCHEMA_VERSION,
+          "problems": self.problems(),
+          "solved": self.solved(),
+          "unimplemented": self.unimplemented(),
+          "variants": self.variants().iter().map(|&(id, of)| json!({ "id": id, "of": of })).collect::<Vec<_>>(),
+          "crates": self
+            .crates()
+            .iter()
+            .map(|&(krate, problems, solved)| json!({ "crate": krate, "problems": problems, "solved": solved }))
+            .collect::<Vec<_>>(),
+        });
+        serde_json::to_string_pretty(&value).unwrap() + "\n"
+      }
+      Format::Csv => {
+        let mut s = format!("# schema_version: {}\n", SCHEMA_VERSION);
+        s += "id,crate,title,implementation\n";
+        for e in &self.entries {
+          let row = [e.0.clone(), e.1.to_string(), e.2.to_string(), e.3.to_string()];
+          s += &row.iter().map(|x| output::csv_field(x)).collect::<Vec<_>>().join(",");
+          s += "\n";
+        }
+        s
+      }
+    }
+  }
+
+  fn to_text(&self) -> String {
+    let (problems, solved) = (self.problems(), self.solved());
+    let percent = if problems > 0 { solved as f64 * 100.0 / problems as f64 } else { 0.0 };
+    let mut s = format!("{} of {} problems solved ({:.1}%)\n", solved, problems, percent);
+    let unimplemented = self.unimplemented();
+    if !unimplemented.is_empty() {
+      s += &format!("  unimplemented: {}\n", unimplemented.join(", "));
+    }
+    let variants = self.variants();
+    if !variants.is_empty() {
+      let xs = variants.iter().map(|&(id, of)| format!("{} of {}", id, of)).collect::<Vec<_>>();
+      s += &format!("  variants: {}\n", xs.join(", "));
+    }
+    s += "\n";
+    for (krate, problems, solved) in self.crates() {
+      s += &format!("  {:16} {:>3} of {:>3} solved\n", krate, solved, problems);
+    }
+    s
+  }
+}
@@ -0,0 +1,285 @@
+//! Runs each solution in a child process of its own, limited in address space and CPU time.
+//!
+//! The parent starts the running executable again with `--child`, writes a request naming the solution,
+//! its parameters and data to the child's standard input and reads the result back from its standard
+//! output. A solution exhausting its memory or CPU time then only takes its own process down, a timed out
+//! solution is actually killed, and the peak resident set size of the process is that of the solution
+//! alone. Requests are served by `run_all`, so the running program must be the `euler_rust` binary or
+//! another one calling `run_all`. Unix only.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::isolate::{self, Limits};
+//!
+//! fn main() {
+//!     assert_eq!(isolate::parse_size("512M"), Ok(512 << 20));
+//!     assert_eq!(isolate::parse_size("2G"), Ok(2 << 30));
+//!     assert!(isolate::parse_size("lots").is_err());
+//!
+//!     let limits = Limits { memory: Some(1 << 30), cpu: Some(60) };
+//!     assert_eq!(limits.to_string(), "1.0 GiB of address space, 60 s of CPU time");
+//!     assert_eq!(Limits::default().to_string(), "no limits");
+//! }
+//! ```
+
+use std::env;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::mem;
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::process::{Command, Output, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use euler_library::memory::{self, Usage};
+use euler_library::solution::{self, Answer, Input, Solution, SolutionResult, Status, Task};
+use libc;
+use output;
+use registry::Registry;
+use serde_json::Value;
+
+/// First argument making `run_all` serve a request from its parent instead of running a selection.
+pub const CHILD_FLAG: &str = "--child";
+
+/// Resource limits of each child process, `None` for no limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Limits {
+  /// Address space in bytes, which includes the executable and reserved but unused memory.
+  pub memory: Option<u64>,
+  /// CPU time in whole seconds.
+  pub cpu: Option<u64>,
+}
+
+impl fmt::Display for Limits {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut xs = Vec::new();
+    if let Some(bytes) = self.memory {
+      xs.push(format!("{} of address space", memory::format_bytes(bytes as usize)));
+    }
+    if let Some(secs) = self.cpu {
+      xs.push(format!("{} s of CPU time", secs));
+    }
+    match xs.len() {
+      0 => f.pad("no limits"),
+      _ => f.pad(&xs.join(", ")),
+    }
+  }
+}
+
+/// Parses a number of bytes, optionally followed by a binary unit `K`, `M`, `G` or `T`, e.g. `512M`.
+pub fn parse_size(s: &str) -> Result<u64, String> {
+  let (digits, shift) = match s.char_indices().last() {
+    Some((i, 'K')) | Some((i, 'k')) => (&s[..i], 10),
+    Some((i, 'M')) | Some((i, 'm')) => (&s[..i], 20),
+    Some((i, 'G')) | Some((i, 'g')) => (&s[..i], 30),
+    Some((i, 'T')) | Some((i, 't')) => (&s[..i], 40),
+    _ => (s, 0),
+  };
+  match digits.parse::<u64>() {
+    Ok(n) if n > 0 && n.leading_zeros() >= shift => Ok(n << shift),
+    _ => Err(format!("invalid size: {} (expected bytes, optionally followed by K, M, G or T)", s)),
+  }
+}
+
+/// Runs `solution` in a child process limited by `limits`, killing it once it has run for `timeout`.
+///
+/// The child dying, e.g. on a failed allocation past its memory limit or on running out of CPU time, is
+/// reported as `Status::Failed`.
+pub fn run(solution: &Solution, limits: &Limits, timeout: Option<Duration>) -> SolutionResult {
+  let failed = |message: String, elapsed| solution.result(None, elapsed, Status::Failed { message, location: None });
+  let exe = match env::current_exe() {
+    Ok(path) => path,
+    Err(e) => return failed(format!("cannot find the running executable: {}", e), 0.0),
+  };
+  let mut command = Command::new(exe);
+  command.arg(CHILD_FLAG).stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+  let limits = *limits;
+  // Only async-signal-safe calls are allowed between fork and exec
+  unsafe {
+    command.pre_exec(move || set_limits(&limits));
+  }
+
+  let instant = Instant::now();
+  let mut child = match command.spawn() {
+    Ok(child) => child,
+    Err(e) => return failed(format!("cannot start child process: {}", e), 0.0),
+  };
+  // The child reads the whole request before writing anything; one dying early just closes the pipe
+  if let Some(mut stdin) = child.stdin.take() {
+    let _ = stdin.write_all(request(solution).to_string().as_bytes());
+  }
+  // The pipes are read on other threads, which end once the child has exited and closed them; the child
+  // itself stays here, so it is only killed while not yet reaped
+  let (stdout, stderr) = (child.stdout.take(), child.stderr.take());
+  let (tx, rx) = mpsc::channel();
+  thread::spawn(move || {
+    let stderr = thread::spawn(move || read_all(stderr));
+    let stdout = read_all(stdout);
+    let _ = tx.send(stdout.and_then(|out| Ok((out, stderr.join().unwrap_or_else(|_| Ok(Vec::new()))?))));
+  });
+  let output = match timeout {
+    Some(t) => rx.recv_timeout(t),
+    None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+  };
+  let elapsed = solution::seconds(instant.elapsed());
+  let output = match output {
+    Ok(pipes) => Ok(pipes.and_then(|(stdout, stderr)| Ok(Output { status: child.wait()?, stdout, stderr }))),
+    Err(e) => {
+      let _ = child.kill();
+      let _ = child.wait();
+      Err(e)
+    }
+  };
+
+  match output {
+    Ok(Ok(ref output)) if output.status.success() => match response(solution, &output.stdout) {
+      Some(res) => res,
+      None => failed("child process sent no readable result".to_string(), elapsed),
+    },
+    Ok(Ok(output)) => failed(exit_message(&output, &limits), elapsed),
+    Ok(Err(e)) => failed(format!("cannot wait for child process: {}", e), elapsed),
+    Err(RecvTimeoutError::Timeout) => {
+      let limit = solution::seconds(timeout.unwrap_or_default());
+      solution.result(None, limit, Status::TimedOut(limit))
+    }
+    Err(RecvTimeoutError::Disconnected) => failed("child process exited without a result".to_string(), elapsed),
+  }
+}
+
+/// Serves the request of a parent process on standard input by running the solution it names among
+/// `solutions` and writing the result to standard output, returning the process exit code.
+pub fn serve(solutions: Vec<Solution>) -> i32 {
+  let mut text = String::new();
+  if let Err(e) = io::stdin().read_to_string(&mut text) {
+    eprintln!("cannot read request: {}", e);
+    return 2;
+  }
+  match requested(&Registry::new(solutions), &text) {
+    Ok(solution) => {
+      let res = solution.run();
+      let res = SolutionResult { rss: max_rss(), ..res };
+      println!("{}", output::result_to_json(&res));
+      0
+    }
+    Err(e) => {
+      eprintln!("{}", e);
+      2
+    }
+  }
+}
+
+// Reads a pipe of a child process to its end
+fn read_all<R: Read>(pipe: Option<R>) -> io::Result<Vec<u8>> {
+  let mut xs = Vec::new();
+  if let Some(mut pipe) = pipe {
+    pipe.read_to_end(&mut xs)?;
+  }
+  Ok(xs)
+}
+
+fn set_limits(limits: &Limits) -> io::Result<()> {
+  let set = |resource, soft: u64, hard: u64| {
+    let limit = libc::rlimit { rlim_cur: soft as libc::rlim_t, rlim_max: hard as libc::rlim_t };
+    match unsafe { libc::setrlimit(resource, &limit) } {
+      0 => Ok(()),
+      _ => Err(io::Error::last_os_error()),
+    }
+  };
+  if let Some(bytes) = limits.memory {
+    set(libc::RLIMIT_AS, bytes, bytes)?;
+  }
+  // SIGXCPU at the soft limit, SIGKILL a second later should the child survive it
+  if let Some(secs) = limits.cpu {
+    set(libc::RLIMIT_CPU, secs, secs + 1)?;
+  }
+  Ok(())
+}
+
+// Returns the peak resident set size of the calling process in bytes
+fn max_rss() -> Option<usize> {
+  let mut usage: libc::rusage = unsafe { mem::zeroed() };
+  if unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) } != 0 {
+    return None;
+  }
+  let unit = if cfg!(target_os = "macos") { 1 } else { 1024 };
+  Some(usage.ru_maxrss as usize * unit)
+}
+
+// Returns why a child process ended without a result, with the first line it wrote to standard error, e.g.
+// Rust's message on a failed allocation
+fn exit_message(output: &Output, limits: &Limits) -> String {
+  let reason = match (output.status.signal(), limits.cpu) {
+    (Some(libc::SIGXCPU), Some(secs)) | (Some(libc::SIGKILL), Some(secs)) => {
+      format!("child process ran out of its {} s of CPU time", secs)
+    }
+    (Some(signal), _) => format!("child process killed by signal {}", signal),
+    (None, _) => format!("child process {}", output.status),
+  };
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  match stderr.lines().find(|line| !line.trim().is_empty()) {
+    Some(line) => format!("{}: {}", reason, line.trim()),
+    None => reason,
+  }
+}
+
+// Returns the request running `solution` in a child process
+fn request(solution: &Solution) -> Value {
+  json!({
+    "id": solution.id(),
+    "task": solution.task.to_string(),
+    "params": solution.args.iter().map(|&(n, v)| (n.to_string(), json!(v))).collect::<serde_json::Map<_, _>>(),
+    "data": solution.input.as_ref().map(|i| json!({ "source": i.source, "text": *i.text })),
+    "seed": solution.seed,
+  })
+}
+
+// Returns the solution a request names, with its parameters, data, seed and task
+fn requested(registry: &Registry, text: &str) -> Result<Solution, String> {
+  let value: Value = serde_json::from_str(text).map_err(|e| format!("invalid request: {}", e))?;
+  let id = value["id"].as_str().ok_or("invalid request: missing id")?;
+  let mut solution = registry.get(id).ok_or(format!("unknown solution: {}", id))?.clone();
+  if let Some(params) = value["params"].as_object().filter(|xs| !xs.is_empty()) {
+    let args = params
+      .iter()
+      .map(|(n, v)| v.as_u64().map(|v| (n.clone(), v)).ok_or(format!("invalid value for parameter {}", n)))
+      .collect::<Result<Vec<_>, _>>()?;
+    solution = solution.with_args(&args)?;
+  }
+  if let (Some(source), Some(text)) = (value["data"]["source"].as_str(), value["data"]["text"].as_str()) {
+    solution = solution.with_input(&Input::new(source, text))?;
+  }
+  if let Some(seed) = value["seed"].as_u64() {
+    solution = solution.with_seed(seed)?;
+  }
+  if value["task"] == Task::Examples.to_string() {
+    solution = solution.examples_task().ok_or(format!("{} has no examples", id))?;
+  }
+  Ok(solution)
+}
+
+// Returns the result a child process wrote, as the result of `solution`
+fn response(solution: &Solution, stdout: &[u8]) -> Option<SolutionResult> {
+  let value: Value = serde_json::from_slice(stdout).ok()?;
+  let answer = match value["answer"] {
+    Value::Number(ref n) => Some(Answer::Integer(n.as_i64()?)),
+    Value::String(ref s) => Some(Answer::Text(s.clone())),
+    _ => None,
+  };
+  let status = match value["error"] {
+    Value::Null => Status::Ok,
+    ref error => Status::Failed {
+      message: error["message"].as_str().unwrap_or_default().to_string(),
+      location: error["location"].as_str().map(String::from),
+    },
+  };
+  let memory = match (value["memory"]["peak_bytes"].as_u64(), value["memory"]["allocated_bytes"].as_u64()) {
+    (Some(peak), Some(allocated)) => Some(Usage { peak: peak as usize, allocated: allocated as usize }),
+    _ => None,
+  };
+  let rss = value["max_rss_bytes"].as_u64().map(|n| n as usize);
+  Some(SolutionResult { memory, rss, ..solution.result(answer, value["elapsed_s"].as_f64()?, status) })
+}
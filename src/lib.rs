@@ -20,16 +20,20 @@
 //!
 //! ## Example
 //!
-//! ```
+//! ```no_run
 //! extern crate euler_rust;
 //!
+//! # #[cfg(feature = "euler_p011_020")]
 //! fn main() {
-//!     // test solution 16
-//!     assert_eq!(euler_rust::euler_p011_020::p016(), "p016 = 1366");
+//!     // test solution 16 against the salted hash of its answer
+//!     let verdict = euler_rust::verify::Manifest::embedded().check(16, &euler_rust::euler_p011_020::p016());
+//!     assert_eq!(verdict, euler_rust::verify::Verdict::Correct);
 //!
 //!     // run all solutions concurrently
 //!     euler_rust::run_all();
 //! }
+//! # #[cfg(not(feature = "euler_p011_020"))]
+//! # fn main() {}
 //! ```
 //!
 //! ## Build
@@ -38,6 +42,24 @@
 //! cargo build --release
 //! ```
 //!
+//! build only some of the problem crates and their dependencies with cargo features: `p001_050`, `p051_100`
+//! and `p101_130` (all three by default), `graphs` for the problems solved with petgraph, or any crate by
+//! its name; `library-only` builds the runner and `euler_library` without problems
+//!
+//! ```ignore
+//! cargo build --release --no-default-features --features p001_050,graphs
+//! cargo build --release --no-default-features --features euler_p081_090
+//! cargo build --release --no-default-features --features library-only
+//! ```
+//!
+//! the tests run for any such subset, skipping the doc examples of problem crates left out; CI tests the
+//! default build, `library-only` and `p001_050`
+//!
+//! ```ignore
+//! cargo test --no-default-features --features library-only
+//! cargo test --no-default-features --features p001_050
+//! ```
+//!
 //! ## Run
 //!
 //! run solution to problem 120
@@ -51,137 +73,897 @@
 //! ```ignore
 //! cargo run --release
 //! ```
-use rayon::prelude::*;
-use std::collections::HashMap;
+//!
+//! run a selection of solutions: ranges, lists, exclusions and whole crates
+//!
+//! ```ignore
+//! cargo run --release 1-50 '!7'
+//! cargo run --release 54,96,107
+//! cargo run --release crate:euler_p101_110
+//! ```
+//!
+//! print results as JSON or CSV instead of text
+//!
+//! ```ignore
+//! cargo run --release -- --format json
+//! cargo run --release -- 1-50 --format csv
+//! ```
+//!
+//! benchmark a selection sequentially: 2 warmup and 20 timed runs of each solution
+//!
+//! ```ignore
+//! cargo run --release -- 60,92 --warmup 2 --iterations 20
+//! ```
+//!
+//! save timings as a baseline, then fail (exit code 1) when a later run is more than 15% slower
+//!
+//! ```ignore
+//! cargo run --release -- --bench --save-baseline baseline.json
+//! cargo run --release -- --bench --baseline baseline.json --threshold 15
+//! ```
+//!
+//! report solutions running longer than 5 seconds as timed out and carry on with the rest
+//!
+//! ```ignore
+//! cargo run --release -- --timeout 5
+//! ```
+//!
+//! run 4 solutions at a time, or one after another on a single thread
+//!
+//! ```ignore
+//! cargo run --release -- --jobs 4
+//! cargo run --release -- --sequential
+//! ```
+//!
+//! solutions start slowest first, using their latest times in the run log `target/euler_runs.jsonl`; report
+//! results in selection order instead of by time, or log elsewhere or not at all, starting the solutions in
+//! selection order
+//!
+//! ```ignore
+//! cargo run --release -- 96,60,1 --order selection
+//! cargo run --release -- --run-log /tmp/runs.jsonl
+//! cargo run --release -- --no-run-log
+//! ```
+//!
+//! quickly check the small examples from the problem statements only, or before the answers
+//!
+//! ```ignore
+//! cargo run --release -- --mode examples
+//! cargo run --release -- --mode both
+//! ```
+//!
+//! compute answers for other inputs than the Euler problem's, e.g. prime power triples below 10^8
+//!
+//! ```ignore
+//! cargo run --release -- 87 --param limit=10^8
+//! ```
+//!
+//! the solutions whose problem is stated for a size, such as a limit, a count or a number of digits, take it
+//! as a parameter; the data-driven ones take a data file instead, and those whose problem has no size, such
+//! as problem 32, take none. Problems 51, 68 and 103 do not take their sizes yet, as their searches are built
+//! around the Euler problem's. Each parameter is bounded to the inputs its solver computes correctly
+//!
+//! run the data-driven solutions on your own files in the format of their embedded ones, or on standard
+//! input
+//!
+//! ```ignore
+//! cargo run --release -- 67 --data my_triangle.txt
+//! cat my_matrix.txt | cargo run --release -- 81-83 --data -
+//! ```
+//!
+//! stochastic solutions, e.g. the Monopoly simulation of problem 84, report the seed of their random numbers;
+//! replay a run with it
+//!
+//! ```ignore
+//! cargo run --release -- 84 --seed 42
+//! ```
+//!
+//! run each solution in a child process of its own, optionally limited to 2 GiB of address space and a
+//! minute of CPU time, so that one exhausting its memory only fails itself (Unix only)
+//!
+//! ```ignore
+//! cargo run --release -- --isolate
+//! cargo run --release -- --memory-limit 2G --cpu-limit 60
+//! ```
+//!
+//! results are streamed to standard error as solutions complete, under a count of those finished, running
+//! and pending, when it is a terminal; stream them anyway, e.g. into a log, or not at all
+//!
+//! ```ignore
+//! cargo run --release -- --progress 2> progress.log
+//! cargo run --release -- --no-progress
+//! ```
+//!
+//! every run appends its timings, git commit and machine to the run log `target/euler_runs.jsonl`, none when
+//! the program runs from outside a cargo `target` directory; show how the time of problems changed across the
+//! logged runs, the last 10 of each or all of them
+//!
+//! ```ignore
+//! cargo run --release -- history 60,92 --last 10
+//! cargo run --release -- history --format csv > trends.csv
+//! ```
+//!
+//! race every variant of a problem against its main solution, failing (exit code 1) when their answers
+//! differ; show how many problems are solved, which are unimplemented placeholders that runs skip and
+//! which solutions are variants
+//!
+//! ```ignore
+//! cargo run --release -- 70,75 --race
+//! cargo run --release -- coverage
+//! ```
+//!
+//! serve the solutions as JSON over HTTP on localhost, e.g. to a dashboard: list them, run one with
+//! parameters, or stream a run of a selection as server-sent events. Solutions time out after 60 seconds
+//! unless `--timeout` says otherwise, and connections arriving while all 8 are being answered are refused
+//! with `503 Service Unavailable`
+//!
+//! ```ignore
+//! cargo run --release -- serve --port 8080 --timeout 60
+//! curl localhost:8080/problems
+//! curl -X POST 'localhost:8080/problems/87/run?limit=10^6'
+//! curl -N 'localhost:8080/run?selection=1-50'
+//! ```
+//!
+//! publish the solved problems as a self-contained HTML page and a Markdown table, by default
+//! `report.html` and `report.md`, listing each problem's title, status, answer, time, memory and crate;
+//! mask the answers to keep them private
+//!
+//! ```ignore
+//! cargo run --release -- report
+//! cargo run --release -- report --markdown SOLUTIONS.md --mask-answers
+//! ```
+//!
+//! check every answer against the salted hashes in `answers.json`, failing (exit code 1) on a wrong one
+//!
+//! ```ignore
+//! cargo run --release -- --verify
+//! cargo run --release -- --answers my_answers.json
+//! ```
 use std::env;
-use std::time::{Duration, Instant};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 pub extern crate euler_library;
-pub extern crate euler_p001_010;
-pub extern crate euler_p011_020;
-pub extern crate euler_p021_030;
-pub extern crate euler_p031_040;
-pub extern crate euler_p041_050;
-pub extern crate euler_p051_060;
-pub extern crate euler_p061_070;
-pub extern crate euler_p071_080;
-pub extern crate euler_p081_090;
-pub extern crate euler_p091_100;
-pub extern crate euler_p101_110;
-pub extern crate euler_p111_120;
-pub extern crate euler_p121_130;
 
+#[cfg(unix)]
+extern crate libc;
 extern crate rayon;
+#[macro_use]
+extern crate serde_json;
+extern crate sha2;
+
+pub mod baseline;
+pub mod bench;
+pub mod cli;
+pub mod coverage;
+#[cfg(unix)]
+pub mod isolate;
+pub mod output;
+pub mod progress;
+pub mod race;
+pub mod registry;
+pub mod report;
+pub mod runlog;
+pub mod schedule;
+pub mod selection;
+pub mod server;
+pub mod verify;
 
-/// Executes one or more solutions stored in function vector fns.
+use euler_library::solution::{self, Implementation};
+pub use euler_library::solution::{Answer, Input, Solution, SolutionResult, Status, Task};
+use cli::{Command, CoverageOptions, HistoryOptions, Mode, Options, ReportOptions, ServeOptions};
+use baseline::Baseline;
+use bench::BenchConfig;
+use output::{Format, Report, Summary};
+use progress::Progress;
+use race::Race;
+use registry::Registry;
+use verify::{Manifest, Verdict};
+
+/// Executes the solutions stored in vector `solutions` chosen by selection expression `arg_maybe`.
+///
+/// See module [`selection`](selection/index.html) for the syntax, e.g. `1-50`, `54,96,107`, `!84` or
+/// `crate:euler_p101_110`.
 ///
 /// ```
 /// use std::env;
 ///
 /// extern crate euler_rust;
 ///
-/// // Invalid or no runtime argument executes p011 through p020 concurrently.
-/// // Executes solution given by single valid runtime argument
-/// // In this example valid args are integers 11 through 20
+/// // No runtime argument executes p011 through p020 concurrently.
+/// // Executes the solutions selected by a valid runtime argument, e.g. `15`, `11-14` or `!17`.
+/// # #[cfg(feature = "euler_p011_020")]
+/// fn main() {
+///     let solutions = euler_rust::euler_p011_020::get_functions();
+///     euler_rust::run(solutions, env::args().nth(1));
+/// }
+/// # #[cfg(not(feature = "euler_p011_020"))]
+/// # fn main() {}
+/// ```
+pub fn run(solutions: Vec<Solution>, arg_maybe: Option<String>) {
+  let options = Options { selection: arg_maybe, ..Options::default() };
+  run_with_options(solutions, &options);
+}
+
+/// Executes the solutions stored in vector `solutions` chosen and reported as given by `options`.
+///
+/// Returns the process exit code: 0 on success, 1 when a solution failed, timed out, regressed against
+/// the baseline or gave a wrong answer and 2 for invalid options or files.
+///
+/// ```
+/// extern crate euler_rust;
+///
+/// use euler_rust::cli::Options;
+/// use euler_rust::output::Format;
+///
+/// # #[cfg(feature = "euler_p011_020")]
 /// fn main() {
-///     let (start, fns) = euler_rust::euler_p011_020::get_functions();
-///     euler_rust::run(fns, env::args().nth(1), start);
+///     let solutions = euler_rust::euler_p011_020::get_functions();
+///     let options = Options { selection: Some("11-13".to_string()), format: Format::Csv, ..Options::default() };
+///     assert_eq!(euler_rust::run_with_options(solutions, &options), 0);
 /// }
+/// # #[cfg(not(feature = "euler_p011_020"))]
+/// # fn main() {}
 /// ```
-pub fn run(fns: Vec<fn() -> String>, arg_maybe: Option<String>, start: u32) {
-  if let Some(arg) = arg_maybe {
-    let fn_map = get_fn_map(&fns, start);
-    if fn_map.contains_key(&arg) {
-      let f = vec![*fn_map.get(&arg).unwrap()];
-      let (res, t) = execute_par_iter(f)[0].clone();
-      println!("{:25}, time = {}", res, t);
-    } else {
-      println!("invalid argument: {}", arg);
-      println!("valid argument: a number between 1 and {}", fns.len())
+pub fn run_with_options(solutions: Vec<Solution>, options: &Options) -> i32 {
+  let registry = Registry::new(solutions);
+  let solutions = match options.selection {
+    Some(ref arg) => match selection::select(&registry, arg) {
+      Ok(xs) => xs,
+      Err(e) => {
+        eprintln!("invalid argument: {}", arg);
+        eprintln!("{}", e);
+        return 2;
+      }
+    },
+    None => registry.main_solutions(),
+  };
+  let solutions = if options.race { with_variants(&registry, solutions) } else { solutions };
+  let (solutions, stubs): (Vec<_>, Vec<_>) =
+    solutions.into_iter().partition(|s| s.implementation != Implementation::Unimplemented);
+  if !stubs.is_empty() {
+    let ids = stubs.iter().map(|s| s.id()).collect::<Vec<_>>();
+    eprintln!("skipping unimplemented: {}", ids.join(", "));
+  }
+  let applied = apply_params(solutions, &options.params)
+    .and_then(|xs| apply_data(xs, &options.data))
+    .and_then(|xs| apply_seed(xs, options.seed));
+  let solutions = match applied {
+    Ok(xs) => tasks(xs, options.mode),
+    Err(e) => {
+      eprintln!("{}", e);
+      return 2;
+    }
+  };
+  let baseline = match options.baseline {
+    Some(ref path) => match Baseline::load(path) {
+      Ok(b) => Some(b),
+      Err(e) => {
+        eprintln!("{}", e);
+        return 2;
+      }
+    },
+    None => None,
+  };
+  // the log only orders the run, so an unreadable one starts the solutions in the order given
+  let history = match options.run_log {
+    Some(ref path) => match runlog::load(path) {
+      Ok(runs) => Some(schedule::latest_timings(&runs)),
+      Err(e) => {
+        eprintln!("{}", e);
+        None
+      }
+    },
+    None => None,
+  };
+  let manifest = match options.answers {
+    Some(ref path) => match Manifest::load(path) {
+      Ok(m) => Some(m),
+      Err(e) => {
+        eprintln!("{}", e);
+        return 2;
+      }
+    },
+    None if options.verify => Some(Manifest::embedded()),
+    None => None,
+  };
+  let selected = solutions.iter().map(|s| s.id()).collect::<Vec<_>>();
+
+  let report = if let Some(ref config) = options.bench {
+    let config = BenchConfig { timeout: options.timeout, ..*config };
+    let mut xs = bench::bench(&solutions, &config);
+    options.order.sort_by_key(&mut xs, &selected, |b| (b.result.id(), b.stats.median));
+    Report::Bench(xs, config)
+  } else {
+    let jobs = jobs(options);
+    if options.format == Format::Text && solutions.len() > 1 {
+      match jobs {
+        1 => println!("Solving {} Euler functions sequentially\n", solutions.len()),
+        n => println!("Solving {} Euler functions in parallel, {} jobs\n", solutions.len(), n),
+      }
+    }
+    let solutions = match history {
+      Some(ref h) => schedule::longest_first(solutions, h),
+      None => solutions,
+    };
+    let instant = Instant::now();
+    match execute(solutions, options) {
+      Ok(mut xs) => {
+        let total_elapsed = solution::seconds(instant.elapsed());
+        options.order.sort(&mut xs, &selected);
+        Report::Run(Summary::new(xs, total_elapsed, jobs))
+      }
+      Err(e) => {
+        eprintln!("{}", e);
+        return 2;
+      }
+    }
+  };
+
+  let timings = report.timings();
+  let comparison = baseline.map(|b| b.compare(&timings, options.threshold));
+  let answers = report.results().into_iter().filter(|r| r.task == Task::Answer);
+  let verification = manifest.map(|m| m.verify_all(answers));
+  let race = if options.race { Some(Race::new(&report)) } else { None };
+  print!("{}", report.render(options.format, comparison.as_ref(), verification.as_ref(), race.as_ref()));
+
+  if let Some(ref path) = options.save_baseline {
+    if let Err(e) = Baseline::new(timings.clone()).save(path) {
+      eprintln!("{}", e);
+      return 2;
     }
-    return;
   }
-  println!("Solving {} Euler functions in parallel\n", fns.len());
-  let instant = Instant::now();
-  let xs = execute_par_iter(fns);
-  for (res, t) in xs.clone() {
-    println!("{:25}, time = {:.6} s", res, t)
+  if let (Some(path), false) = (options.run_log.as_ref(), timings.is_empty()) {
+    if let Err(e) = runlog::append(path, &runlog::Run::new(&timings, options.bench.is_some())) {
+      eprintln!("{}", e);
+    }
   }
-  let duration = get_duration(instant.elapsed());
-  println!("\n     total elapsed time: {:.6} s", duration);
+  let regressed = comparison.is_some_and(|c| !c.regressions().is_empty());
+  let wrong = verification.is_some_and(|v| v.count(Verdict::Wrong) > 0);
+  let disagreed = race.is_some_and(|r| !r.disagreements().is_empty());
+  if regressed || wrong || disagreed || !report.failures().is_empty() {
+    1
+  } else {
+    0
+  }
+}
 
-  let sum_exec = xs.iter().fold(0.0, |acc, x| acc + x.1);
-  println!(" sum of execution times: {:.6} s", sum_exec);
+/// Prints the timing trend of the solutions in `solutions` chosen by `options` across the runs in the run
+/// log, returning the process exit code: 0 on success and 2 for invalid options or files.
+///
+/// ```
+/// extern crate euler_rust;
+///
+/// use euler_rust::cli::HistoryOptions;
+/// use euler_rust::output::Format;
+///
+/// # #[cfg(feature = "euler_p061_070")]
+/// fn main() {
+///     let solutions = euler_rust::euler_p061_070::get_functions();
+///     let options = HistoryOptions {
+///         selection: Some("67".to_string()),
+///         run_log: Some("no_such_log.jsonl".to_string()),
+///         format: Format::Json,
+///         last: None,
+///     };
+///     assert_eq!(euler_rust::history(solutions, &options), 0);
+/// }
+/// # #[cfg(not(feature = "euler_p061_070"))]
+/// # fn main() {}
+/// ```
+pub fn history(solutions: Vec<Solution>, options: &HistoryOptions) -> i32 {
+  let registry = Registry::new(solutions);
+  let solutions = match options.selection {
+    Some(ref arg) => match selection::select(&registry, arg) {
+      Ok(xs) => xs,
+      Err(e) => {
+        eprintln!("invalid argument: {}", arg);
+        eprintln!("{}", e);
+        return 2;
+      }
+    },
+    None => registry.solutions().to_vec(),
+  };
+  let path = match options.run_log {
+    Some(ref path) => path,
+    None => {
+      eprintln!("no run log next to the program, pass --run-log FILE");
+      return 2;
+    }
+  };
+  match runlog::load(path) {
+    Ok(runs) => {
+      print!("{}", runlog::render(&runlog::trends(&runs, &solutions, options.last), options.format));
+      0
+    }
+    Err(e) => {
+      eprintln!("{}", e);
+      2
+    }
+  }
+}
+
+/// Prints how many of the problems of the solutions in `solutions` chosen by `options` are solved, their
+/// variants included, returning the process exit code: 0 on success and 2 for an invalid selection.
+///
+/// ```
+/// extern crate euler_rust;
+///
+/// use euler_rust::cli::CoverageOptions;
+/// use euler_rust::output::Format;
+///
+/// # #[cfg(feature = "euler_p121_130")]
+/// fn main() {
+///     let solutions = euler_rust::euler_p121_130::get_functions();
+///     let options = CoverageOptions { selection: Some("125-130".to_string()), format: Format::Csv };
+///     assert_eq!(euler_rust::coverage(solutions, &options), 0);
+/// }
+/// # #[cfg(not(feature = "euler_p121_130"))]
+/// # fn main() {}
+/// ```
+pub fn coverage(solutions: Vec<Solution>, options: &CoverageOptions) -> i32 {
+  let registry = Registry::new(solutions);
+  let solutions = match options.selection {
+    Some(ref arg) => match selection::select(&registry, arg) {
+      Ok(xs) => with_variants(&registry, xs),
+      Err(e) => {
+        eprintln!("invalid argument: {}", arg);
+        eprintln!("{}", e);
+        return 2;
+      }
+    },
+    None => registry.solutions().to_vec(),
+  };
+  print!("{}", coverage::Coverage::new(&solutions).render(options.format));
+  0
+}
 
-  let par_fact = sum_exec / duration;
-  println!("parallel speedup factor: {:.3}", par_fact);
+/// Serves the solutions in `solutions` over HTTP on localhost as given by `options`, see module
+/// [`server`](server/index.html), until the listener fails. Returns the process exit code: 2 when the port
+/// cannot be listened on and 1 when listening stops.
+pub fn serve(solutions: Vec<Solution>, options: &ServeOptions) -> i32 {
+  let server = match server::Server::bind(options.port, solutions, options.timeout) {
+    Ok(s) => s,
+    Err(e) => {
+      eprintln!("cannot listen on port {}: {}", options.port, e);
+      return 2;
+    }
+  };
+  if let Ok(addr) = server.local_addr() {
+    eprintln!("serving the solutions on http://{}", addr);
+  }
+  match server.run() {
+    Ok(()) => 0,
+    Err(e) => {
+      eprintln!("{}", e);
+      1
+    }
+  }
 }
 
-/// Executes one or all solutions from `euler` crate.
+/// Runs the solutions in `solutions` chosen by `options` and writes HTML and Markdown reports of them,
+/// listing the unimplemented ones without running them. Returns the process exit code: 0 on success, 1
+/// when a solution failed or timed out and 2 for invalid options or a report that cannot be written.
 ///
 /// ```
 /// extern crate euler_rust;
 ///
-/// // Invalid or no runtime argument executes all solutions concurrently.
-/// // Executes solution given by single valid runtime argument.
-/// // In this example valid args are integers 1 through solved solutions.
+/// use euler_rust::cli::ReportOptions;
+///
+/// # #[cfg(feature = "euler_p121_130")]
+/// fn main() {
+///     let solutions = euler_rust::euler_p121_130::get_functions();
+///     let options = ReportOptions {
+///         selection: Some("121,128-130".to_string()),
+///         html: None,
+///         markdown: Some("-".to_string()),
+///         mask: true,
+///         timeout: None,
+///     };
+///     assert_eq!(euler_rust::report(solutions, &options), 0);
+/// }
+/// # #[cfg(not(feature = "euler_p121_130"))]
+/// # fn main() {}
+/// ```
+pub fn report(solutions: Vec<Solution>, options: &ReportOptions) -> i32 {
+  let registry = Registry::new(solutions);
+  let solutions = match options.selection {
+    Some(ref arg) => match selection::select(&registry, arg) {
+      Ok(xs) => xs,
+      Err(e) => {
+        eprintln!("invalid argument: {}", arg);
+        eprintln!("{}", e);
+        return 2;
+      }
+    },
+    None => registry.main_solutions(),
+  };
+  let (solutions, stubs): (Vec<_>, Vec<_>) =
+    solutions.into_iter().partition(|s| s.implementation != Implementation::Unimplemented);
+  let run = Options { timeout: options.timeout, ..Options::default() };
+  let results = match execute(solutions, &run) {
+    Ok(xs) => xs,
+    Err(e) => {
+      eprintln!("{}", e);
+      return 2;
+    }
+  };
+  let failed = results.iter().any(|r| !r.status.is_ok());
+  let generated = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+  let page = report::Page::new(results, &stubs, options.mask, generated);
+
+  let pages = [(&options.html, page.to_html()), (&options.markdown, page.to_markdown())];
+  for (path, text) in pages.iter().filter_map(|(path, text)| path.as_ref().map(|p| (p, text))) {
+    let written = match path.as_str() {
+      "-" => io::stdout().write_all(text.as_bytes()),
+      _ => fs::write(path, text),
+    };
+    if let Err(e) = written {
+      eprintln!("cannot write {}: {}", path, e);
+      return 2;
+    }
+  }
+  if failed {
+    1
+  } else {
+    0
+  }
+}
+
+// Adds the other solutions of each problem in `solutions` after its first one, keeping their order
+fn with_variants(registry: &Registry, solutions: Vec<Solution>) -> Vec<Solution> {
+  let mut xs: Vec<Solution> = Vec::new();
+  for s in solutions {
+    if !xs.iter().any(|x| x.problem == s.problem) {
+      xs.extend(registry.problem(s.problem));
+    }
+  }
+  xs
+}
+
+/// Executes one or all solutions from `euler` crate as given by the command line, returning the process
+/// exit code.
+///
+/// ```no_run
+/// extern crate euler_rust;
+///
+/// // No runtime argument executes all solutions concurrently.
+/// // Runtime arguments are joined with commas into a selection expression,
+/// // e.g. `1-50 !7` selects problems 1 through 50 except problem 7.
+/// // `--format json|csv|text` chooses how results are printed.
+/// // `--bench`, `--warmup N` and `--iterations N` benchmark the selection sequentially.
+/// // `--save-baseline FILE` saves the timings, `--baseline FILE` flags regressions against them.
+/// // `--timeout SECONDS` reports solutions running longer as timed out.
+/// // `--jobs N` runs N solutions at a time, `--sequential` one after another.
+/// // `--order time|selection|problem` chooses the order results are reported in.
+/// // `--mode examples` only checks the examples from the problem statements, `--mode both` adds them.
+/// // `--param NAME=VALUE` computes the answers for other inputs, e.g. `--param limit=10^8`, for the solutions
+/// // having that parameter.
+/// // `--data FILE` computes the answers of data-driven solutions from FILE, `--data -` from standard input.
+/// // `--seed N` seeds the random numbers of stochastic solutions, replaying a run reporting that seed.
+/// // `--verify` checks answers against the embedded manifest, `--answers FILE` against another one.
+/// // `--isolate` runs each solution in a child process, `--memory-limit SIZE` and `--cpu-limit SECONDS`
+/// // limit its address space and CPU time; Unix only.
+/// // `--progress` streams results to standard error as they complete, `--no-progress` does not; by default
+/// // they are streamed when standard error is a terminal.
+/// // `--run-log FILE` appends the timings to another run log than `target/euler_runs.jsonl`, `--no-run-log` to none;
+/// // the slowest solutions of the log are started first.
+/// // `--race` also runs every variant of the selected problems and ranks them by time.
+/// // Unimplemented solutions are skipped.
+/// // `history [SELECTION]` shows the timing trend of the selected problems across the logged runs.
+/// // `coverage [SELECTION]` shows how many of the selected problems are solved.
+/// // `serve [--port N]` serves the solutions over HTTP on localhost, e.g. for a dashboard.
+/// // `report [SELECTION]` writes `report.html` and `report.md` listing the selected problems, their status,
+/// // answer, time and memory; `--html FILE` or `--markdown FILE` writes one of them, `--mask-answers` hides
+/// // the answers.
 /// fn main() {
 ///     euler_rust::run_all();
 /// }
 /// ```
-pub fn run_all() {
-  run(get_all_functions().clone(), env::args().nth(1), 1);
+pub fn run_all() -> i32 {
+  #[cfg(unix)]
+  {
+    if env::args().nth(1).as_deref() == Some(isolate::CHILD_FLAG) {
+      return isolate::serve(get_all_functions());
+    }
+  }
+  match Command::parse(env::args().skip(1)) {
+    Ok(Command::Run(options)) => run_with_options(get_all_functions(), &options),
+    Ok(Command::History(options)) => history(get_all_functions(), &options),
+    Ok(Command::Coverage(options)) => coverage(get_all_functions(), &options),
+    Ok(Command::Serve(options)) => serve(get_all_functions(), &options),
+    Ok(Command::Report(options)) => report(get_all_functions(), &options),
+    Err(e) => {
+      eprintln!("{}", e);
+      2
+    }
+  }
 }
 
-// Returns a Vector of all euler functions
-pub fn get_all_functions() -> Vec<fn() -> String> {
-  let (_, mut fns) = euler_p001_010::get_functions();
-  fns.append(&mut euler_p011_020::get_functions().1);
-  fns.append(&mut euler_p021_030::get_functions().1);
-  fns.append(&mut euler_p031_040::get_functions().1);
-  fns.append(&mut euler_p041_050::get_functions().1);
-  fns.append(&mut euler_p051_060::get_functions().1);
-  fns.append(&mut euler_p061_070::get_functions().1);
-  fns.append(&mut euler_p071_080::get_functions().1);
-  fns.append(&mut euler_p081_090::get_functions().1);
-  fns.append(&mut euler_p091_100::get_functions().1);
-  fns.append(&mut euler_p101_110::get_functions().1);
-  fns.append(&mut euler_p111_120::get_functions().1);
-  fns.append(&mut euler_p121_130::get_functions().1);
+// Declares the problem crates and `get_all_functions`, which collects the solutions each registers with
+// `euler_library::solutions!`; a crate is added by naming it here, gated by its feature, and as an optional
+// dependency in Cargo.toml. A problem crate depended on but not named here fails the build, see
+// `euler_library::solution::dependencies_named` for how Cargo.toml is read
+macro_rules! problem_crates {
+  ($($(#[$attr:meta])* $krate:ident),* $(,)*) => {
+    $($(#[$attr])* pub extern crate $krate;)*
 
-  fns
+    /// Returns the solutions of every problem crate enabled by the cargo features the crate is built with.
+    ///
+    /// ```
+    /// extern crate euler_rust;
+    ///
+    /// use euler_rust::registry::Registry;
+    ///
+    /// fn main() {
+    ///     let registry = Registry::new(euler_rust::get_all_functions());
+    ///     if cfg!(all(feature = "p001_050", feature = "p051_100", feature = "p101_130")) {
+    ///         assert!((1..131).all(|n| registry.get(&n.to_string()).is_some()));
+    ///     }
+    /// }
+    /// ```
+    #[allow(unused_mut)]
+    pub fn get_all_functions() -> Vec<Solution> {
+      let mut solutions = Vec::new();
+      $($(#[$attr])* solutions.append(&mut $krate::get_functions());)*
+      solutions
+    }
+
+    const _: () = assert!(
+      solution::dependencies_named(include_str!("../Cargo.toml"), "euler_p", &[$(stringify!($krate)),*]),
+      "a problem crate of Cargo.toml is not named in `problem_crates!`"
+    );
+  };
 }
 
-// Returns a `HashMap` of ("problem number", function to execute) from list of functions fns
-fn get_fn_map(fns: &[fn() -> String], start: u32) -> HashMap<String, fn() -> String> {
-  fns
-    .iter()
-    .enumerate()
-    .map(|(i, &f)| ((i as u32 + start).to_string(), f))
-    .collect::<HashMap<_, _>>()
-}
-
-// execute all problems in parallel
-fn execute_par_iter(fns: Vec<fn() -> String>) -> Vec<(String, f64)> {
-  let mut xs: Vec<(String, f64)> = fns
-    .par_iter()
-    .map(|f| {
-      let instant = Instant::now();
-      let s = f();
-      let elapsed = instant.elapsed();
-      (s, get_duration(elapsed))
-    })
-    .collect();
+#[cfg(all(
+  feature = "library-only",
+  any(
+    feature = "euler_p001_010",
+    feature = "euler_p011_020",
+    feature = "euler_p021_030",
+    feature = "euler_p031_040",
+    feature = "euler_p041_050",
+    feature = "euler_p051_060",
+    feature = "euler_p061_070",
+    feature = "euler_p071_080",
+    feature = "euler_p081_090",
+    feature = "euler_p091_100",
+    feature = "euler_p101_110",
+    feature = "euler_p111_120",
+    feature = "euler_p121_130",
+  )
+))]
+compile_error!("feature library-only excludes the problem crates, build it with --no-default-features");
+
+problem_crates! {
+  #[cfg(feature = "euler_p001_010")]
+  euler_p001_010,
+  #[cfg(feature = "euler_p011_020")]
+  euler_p011_020,
+  #[cfg(feature = "euler_p021_030")]
+  euler_p021_030,
+  #[cfg(feature = "euler_p031_040")]
+  euler_p031_040,
+  #[cfg(feature = "euler_p041_050")]
+  euler_p041_050,
+  #[cfg(feature = "euler_p051_060")]
+  euler_p051_060,
+  #[cfg(feature = "euler_p061_070")]
+  euler_p061_070,
+  #[cfg(feature = "euler_p071_080")]
+  euler_p071_080,
+  #[cfg(feature = "euler_p081_090")]
+  euler_p081_090,
+  #[cfg(feature = "euler_p091_100")]
+  euler_p091_100,
+  #[cfg(feature = "euler_p101_110")]
+  euler_p101_110,
+  #[cfg(feature = "euler_p111_120")]
+  euler_p111_120,
+  #[cfg(feature = "euler_p121_130")]
+  euler_p121_130,
+}
+
+/// Executes all solutions in parallel and returns their results sorted by execution time.
+///
+/// A solution that panics is reported with `Status::Failed` and does not affect the others.
+///
+/// ```
+/// extern crate euler_rust;
+///
+/// use euler_rust::{Answer, Status};
+///
+/// # #[cfg(feature = "euler_p001_010")]
+/// fn main() {
+///     let solutions = euler_rust::euler_p001_010::get_functions();
+///     let results = euler_rust::execute_par_iter(solutions[..3].to_vec());
+///     assert_eq!(results.len(), 3);
+///     assert!(results.iter().all(|r| r.status == Status::Ok));
+///     assert!(results.iter().any(|r| r.problem == 1 && r.answer == Some(Answer::Integer(233168))));
+/// }
+/// # #[cfg(not(feature = "euler_p001_010"))]
+/// # fn main() {}
+/// ```
+pub fn execute_par_iter(solutions: Vec<Solution>) -> Vec<SolutionResult> {
+  execute_with_timeout(solutions, None)
+}
+
+/// Executes all solutions in parallel like `execute_par_iter`, reporting a solution still running after
+/// `timeout` as `Status::TimedOut` and moving on to the others.
+///
+/// ```
+/// extern crate euler_rust;
+///
+/// use std::time::Duration;
+/// use euler_rust::Status;
+///
+/// # #[cfg(feature = "euler_p061_070")]
+/// fn main() {
+///     let solutions = euler_rust::euler_p061_070::get_functions();
+///     let slow = solutions.into_iter().filter(|s| s.id() == "p070a").collect();
+///     let results = euler_rust::execute_with_timeout(slow, Some(Duration::from_millis(10)));
+///     assert_eq!(results[0].status, Status::TimedOut(0.01));
+/// }
+/// # #[cfg(not(feature = "euler_p061_070"))]
+/// # fn main() {}
+/// ```
+pub fn execute_with_timeout(solutions: Vec<Solution>, timeout: Option<Duration>) -> Vec<SolutionResult> {
+  let options = Options { timeout, ..Options::default() };
+  execute(solutions, &options).unwrap()
+}
+
+/// Executes solutions with the parallelism and time limit given by `options` and returns their results
+/// sorted by execution time.
+///
+/// `options.sequential` runs them one after another on the calling thread, `options.jobs` on a dedicated
+/// pool of that many threads and otherwise on rayon's global pool. `options.isolate` runs each of them in a
+/// child process, see module [`isolate`](isolate/index.html). Solutions are started in the order given, see
+/// [`schedule::longest_first`](schedule/fn.longest_first.html). `options.progress` streams every result to
+/// standard error as it completes, see module [`progress`](progress/index.html). Fails only when the pool
+/// cannot be built.
+///
+/// ```
+/// extern crate euler_rust;
+///
+/// use euler_rust::cli::Options;
+///
+/// # #[cfg(feature = "euler_p001_010")]
+/// fn main() {
+///     let solutions = euler_rust::euler_p001_010::get_functions();
+///     let options = Options { jobs: Some(2), ..Options::default() };
+///     assert_eq!(euler_rust::execute(solutions.clone(), &options).unwrap().len(), 10);
+///
+///     let options = Options { sequential: true, ..Options::default() };
+///     assert_eq!(euler_rust::execute(solutions, &options).unwrap().len(), 10);
+/// }
+/// # #[cfg(not(feature = "euler_p001_010"))]
+/// # fn main() {}
+/// ```
+pub fn execute(solutions: Vec<Solution>, options: &Options) -> Result<Vec<SolutionResult>, String> {
+  let progress = if options.progress && solutions.len() > 1 {
+    Some(Progress::new(solutions.len(), io::stderr().is_terminal()))
+  } else {
+    None
+  };
+  let progress = progress.as_ref();
+  let mut xs: Vec<SolutionResult> = if options.sequential {
+    solutions.iter().map(|s| run_tracked(s, options, progress)).collect()
+  } else if let Some(n) = options.jobs {
+    let pool = rayon::ThreadPoolBuilder::new()
+      .num_threads(n)
+      .build()
+      .map_err(|e| format!("cannot start {} jobs: {}", n, e))?;
+    pool.install(|| run_in_order(&solutions, options, progress))
+  } else {
+    run_in_order(&solutions, options, progress)
+  };
+  if let Some(p) = progress {
+    p.done();
+  }
 
   // sort by time taken to execute
-  xs.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-  xs
+  xs.sort_by(|a, b| a.elapsed.partial_cmp(&b.elapsed).unwrap());
+  Ok(xs)
+}
+
+// Sets the parameters in `params` of every solution having them; each must belong to at least one
+fn apply_params(solutions: Vec<Solution>, params: &[(String, u64)]) -> Result<Vec<Solution>, String> {
+  let has = |s: &Solution, name: &str| s.params.iter().any(|p| p.name == name);
+  if let Some((name, _)) = params.iter().find(|&(n, _)| !solutions.iter().any(|s| has(s, n))) {
+    return Err(format!("no selected solution has parameter {}", name));
+  }
+  solutions
+    .iter()
+    .map(|s| s.with_args(&params.iter().filter(|&(n, _)| has(s, n)).cloned().collect::<Vec<_>>()))
+    .collect()
 }
 
-// Return duration string in fractional seconds
-fn get_duration(dur: Duration) -> f64 {
-  let micros = dur.as_secs() * 1_000_000 + dur.subsec_nanos() as u64 / 1_000;
-  // let frac_time = micros as f64 / 1_000_000.0;
-  // format!("{:.6} s", frac_time)
-  micros as f64 / 1_000_000.0
+// Makes every solution reading data read the file at `data` instead, failing when none does
+fn apply_data(solutions: Vec<Solution>, data: &Option<String>) -> Result<Vec<Solution>, String> {
+  let path = match *data {
+    Some(ref path) => path,
+    None => return Ok(solutions),
+  };
+  if !solutions.iter().any(|s| s.data.is_some()) {
+    return Err("no selected solution reads data".to_string());
+  }
+  let input = Input::read(path)?;
+  Ok(solutions.into_iter().map(|s| s.with_input(&input).unwrap_or(s)).collect())
+}
+
+// Seeds every stochastic solution with `seed`, failing when none is, or with a seed drawn for the run so that
+// it can be replayed
+fn apply_seed(solutions: Vec<Solution>, seed: Option<u64>) -> Result<Vec<Solution>, String> {
+  if seed.is_some() && !solutions.iter().any(|s| s.func_seeded.is_some()) {
+    return Err("no selected solution draws random numbers".to_string());
+  }
+  let seed = seed.unwrap_or_else(solution::random_seed);
+  Ok(solutions.into_iter().map(|s| s.with_seed(seed).unwrap_or(s)).collect())
+}
+
+// Returns what to run of the selected solutions in `mode`: for both, each problem's examples right before
+// its answer
+fn tasks(solutions: Vec<Solution>, mode: Mode) -> Vec<Solution> {
+  match mode {
+    Mode::Answers => solutions,
+    Mode::Examples => solutions.iter().filter_map(Solution::examples_task).collect(),
+    Mode::Both => solutions.iter().flat_map(|s| s.examples_task().into_iter().chain(Some(s.clone()))).collect(),
+  }
+}
+
+// Runs solutions on every thread of the current rayon pool, each thread taking the next solution not yet
+// started; unlike `par_iter`, which splits the work up front, this keeps the start order
+fn run_in_order(solutions: &[Solution], options: &Options, progress: Option<&Progress>) -> Vec<SolutionResult> {
+  let next = AtomicUsize::new(0);
+  let results = Mutex::new(Vec::with_capacity(solutions.len()));
+  rayon::scope(|scope| {
+    for _ in 0..rayon::current_num_threads().min(solutions.len()) {
+      scope.spawn(|_| {
+        while let Some(s) = solutions.get(next.fetch_add(1, Ordering::SeqCst)) {
+          let res = run_tracked(s, options, progress);
+          results.lock().unwrap().push(res);
+        }
+      });
+    }
+  });
+  results.into_inner().unwrap()
+}
+
+// Returns the number of solutions `execute` runs at the same time
+fn jobs(options: &Options) -> usize {
+  if options.sequential {
+    1
+  } else {
+    options.jobs.unwrap_or_else(rayon::current_num_threads)
+  }
+}
+
+// Runs a solution as given by `options`, counting it as running then finished
+fn run_tracked(solution: &Solution, options: &Options, progress: Option<&Progress>) -> SolutionResult {
+  if let Some(p) = progress {
+    p.started();
+  }
+  #[cfg(unix)]
+  let res = match options.isolate {
+    Some(ref limits) => isolate::run(solution, limits, options.timeout),
+    None => run_one(solution, options.timeout),
+  };
+  #[cfg(not(unix))]
+  let res = run_one(solution, options.timeout);
+  if let Some(p) = progress {
+    p.finished(&res);
+  }
+  res
+}
+
+// Runs a solution on the current thread, or on a supervised worker thread when limited by a timeout
+fn run_one(solution: &Solution, timeout: Option<Duration>) -> SolutionResult {
+  match timeout {
+    Some(t) => solution.run_with_timeout(t),
+    None => solution.run(),
+  }
 }
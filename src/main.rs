@@ -1,6 +1,15 @@
+extern crate euler_library;
 extern crate euler_rust;
 
+use std::process;
+
+use euler_library::memory::CountingAllocator;
+
+// Counts the heap usage of every solution, see module `euler_library::memory`
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
 // run all Euler problems
 fn main() {
-  euler_rust::run_all();
+  process::exit(euler_rust::run_all());
 }
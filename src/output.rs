@@ -0,0 +1,362 @@
+//! Text, JSON and CSV rendering of run results.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::euler_library::memory::CountingAllocator;
+//! use euler_rust::output::{Format, Summary};
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//!
+//! # #[cfg(feature = "euler_p001_010")]
+//! fn main() {
+//!     let solutions = euler_rust::euler_p001_010::get_functions();
+//!     let summary = Summary::new(euler_rust::execute_par_iter(solutions[..2].to_vec()), 0.5, 4);
+//!
+//!     let json = summary.render(Format::Json);
+//!     assert!(json.contains("\"schema_version\": 2"));
+//!     assert!(json.contains("\"answer\": 233168"));
+//!     assert!(json.contains("\"peak_bytes\""));
+//!
+//!     let csv = summary.render(Format::Csv);
+//!     let header = "id,problem,variant,title,crate,status,answer,elapsed_s,task,peak_bytes,allocated_bytes,\
+//!                   max_rss_bytes,seed,params,data";
+//!     assert!(csv.contains(&format!("\n{}\n", header)));
+//!     assert!(csv.contains("p001,1,,Multiples of 3 and 5,euler_p001_010,ok,233168,"));
+//! }
+//! # #[cfg(not(feature = "euler_p001_010"))]
+//! # fn main() {}
+//! ```
+
+use std::str::FromStr;
+
+use baseline::Comparison;
+use bench::{self, BenchConfig, BenchResult};
+use euler_library::memory;
+use euler_library::solution::{Answer, SolutionResult, Status, Task};
+use race::Race;
+use serde_json::Value;
+use verify::Verification;
+
+/// Version of the JSON and CSV layouts, bumped whenever a field is added, changes meaning or goes away.
+/// Version 2 added the variant, parameters, data file, memory, resident set size and seed of each result.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Output format of a run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+  Text,
+  Json,
+  Csv,
+}
+
+impl FromStr for Format {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Format, String> {
+    match s {
+      "text" => Ok(Format::Text),
+      "json" => Ok(Format::Json),
+      "csv" => Ok(Format::Csv),
+      _ => Err(format!("unknown format: {} (expected json, csv or text)", s)),
+    }
+  }
+}
+
+/// Results of a run together with its aggregate timings.
+#[derive(Clone, Debug)]
+pub struct Summary {
+  pub results: Vec<SolutionResult>,
+  /// Wall clock time of the whole run in fractional seconds.
+  pub total_elapsed: f64,
+  /// Sum of the execution times of all solutions in fractional seconds.
+  pub sum_exec: f64,
+  /// `sum_exec / total_elapsed`
+  pub speedup: f64,
+  /// Number of solutions allowed to run at the same time.
+  pub jobs: usize,
+  /// Lower bound on the wall clock time of any schedule of the results on `jobs` threads: the longer of
+  /// the slowest solution and `sum_exec / jobs`.
+  pub best_makespan: f64,
+}
+
+impl Summary {
+  /// Returns a `Summary` of `results` for a run on `jobs` threads that took `total_elapsed` seconds.
+  ///
+  /// ```
+  /// extern crate euler_rust;
+  ///
+  /// use euler_rust::output::Summary;
+  ///
+  /// # #[cfg(feature = "euler_p001_010")]
+  /// fn main() {
+  ///     let mut results = euler_rust::execute_par_iter(euler_rust::euler_p001_010::get_functions());
+  ///     for (i, res) in results.iter_mut().enumerate() {
+  ///         res.elapsed = if i == 0 { 4.0 } else { 1.0 };
+  ///     }
+  ///     assert_eq!(Summary::new(results.clone(), 5.0, 4).best_makespan, 4.0);
+  ///     assert_eq!(Summary::new(results, 13.0, 1).best_makespan, 13.0);
+  /// }
+  /// # #[cfg(not(feature = "euler_p001_010"))]
+  /// # fn main() {}
+  /// ```
+  pub fn new(results: Vec<SolutionResult>, total_elapsed: f64, jobs: usize) -> Summary {
+    let sum_exec = results.iter().fold(0.0, |acc, x| acc + x.elapsed);
+    let longest = results.iter().fold(0.0, |acc: f64, x| acc.max(x.elapsed));
+    let best_makespan = longest.max(sum_exec / jobs.max(1) as f64);
+    Summary { results, total_elapsed, sum_exec, speedup: sum_exec / total_elapsed, jobs, best_makespan }
+  }
+
+  /// Returns the summary rendered in `format`.
+  pub fn render(&self, format: Format) -> String {
+    match format {
+      Format::Text => self.to_text(),
+      Format::Json => serde_json::to_string_pretty(&self.to_json()).unwrap() + "\n",
+      Format::Csv => self.to_csv(),
+    }
+  }
+
+  /// Returns the summary as a JSON value.
+  pub fn to_json(&self) -> Value {
+    json!({
+      "schema_version": SCHEMA_VERSION,
+      "results": self.results.iter().map(result_to_json).collect::<Vec<_>>(),
+      "total_elapsed_s": self.total_elapsed,
+      "sum_of_execution_times_s": self.sum_exec,
+      "parallel_speedup": self.speedup,
+      "jobs": self.jobs,
+      "best_makespan_s": self.best_makespan,
+    })
+  }
+
+  fn to_text(&self) -> String {
+    if self.results.len() == 1 {
+      let res = &self.results[0];
+      return format!("{:25}, time = {}{}\n", res, res.elapsed, memory_text(res)) + &failures_text(&self.results);
+    }
+    let mut s = String::new();
+    for res in &self.results {
+      s += &result_text(res);
+      s += "\n";
+    }
+    s += &format!("\n     total elapsed time: {:.6} s\n", self.total_elapsed);
+    s += &format!(" sum of execution times: {:.6} s\n", self.sum_exec);
+    s += &format!("parallel speedup factor: {:.3}\n", self.speedup);
+    s += &format!("{:>23}: {:.6} s\n", format!("best makespan, {}", plural(self.jobs, "job")), self.best_makespan);
+    s += &format!("  scheduling efficiency: {:.3}\n", self.best_makespan / self.total_elapsed);
+    s + &failures_text(&self.results)
+  }
+
+  // Aggregate timings go in leading `#` comment lines so the table itself stays rectangular;
+  // parameters are written as space separated `name=value` pairs, the form `--param` takes.
+  fn to_csv(&self) -> String {
+    let mut s = format!("# schema_version: {}\n", SCHEMA_VERSION);
+    s += &format!("# total_elapsed_s: {:.6}\n", self.total_elapsed);
+    s += &format!("# sum_of_execution_times_s: {:.6}\n", self.sum_exec);
+    s += &format!("# parallel_speedup: {:.3}\n", self.speedup);
+    s += &format!("# jobs: {}\n", self.jobs);
+    s += &format!("# best_makespan_s: {:.6}\n", self.best_makespan);
+    s += "id,problem,variant,title,crate,status,answer,elapsed_s,task,peak_bytes,allocated_bytes,max_rss_bytes,\
+          seed,params,data\n";
+    for res in &self.results {
+      let row = [
+        res.id(),
+        res.problem.to_string(),
+        res.variant.unwrap_or("").to_string(),
+        res.title.to_string(),
+        res.crate_name.to_string(),
+        res.status.to_string(),
+        res.answer.as_ref().map_or(String::new(), |a| a.to_string()),
+        format!("{:.6}", res.elapsed),
+        res.task.to_string(),
+        res.memory.map_or(String::new(), |m| m.peak.to_string()),
+        res.memory.map_or(String::new(), |m| m.allocated.to_string()),
+        res.rss.map_or(String::new(), |n| n.to_string()),
+        res.seed.map_or(String::new(), |n| n.to_string()),
+        res.args.iter().map(|&(n, v)| format!("{}={}", n, v)).collect::<Vec<_>>().join(" "),
+        res.input.clone().unwrap_or_default(),
+      ];
+      s += &row.iter().map(|x| csv_field(x)).collect::<Vec<_>>().join(",");
+      s += "\n";
+    }
+    s
+  }
+}
+
+/// Everything a run produced, ready to be rendered.
+#[derive(Clone, Debug)]
+pub enum Report {
+  Run(Summary),
+  Bench(Vec<BenchResult>, BenchConfig),
+}
+
+impl Report {
+  /// Returns `(problem id, seconds)` for every answer to the Euler problem; the median for benchmarks.
+  pub fn timings(&self) -> Vec<(String, f64)> {
+    let timed = |r: &SolutionResult| r.status.is_ok() && r.task == Task::Answer && !r.has_custom_input();
+    match *self {
+      Report::Run(ref summary) => summary.results.iter().filter(|r| timed(r)).map(|r| (r.id(), r.elapsed)).collect(),
+      Report::Bench(ref xs, _) => {
+        xs.iter().filter(|b| timed(&b.result)).map(|b| (b.result.id(), b.stats.median)).collect()
+      }
+    }
+  }
+
+  /// Returns the result of every solution; the last timed run for benchmarks.
+  pub fn results(&self) -> Vec<&SolutionResult> {
+    match *self {
+      Report::Run(ref summary) => summary.results.iter().collect(),
+      Report::Bench(ref xs, _) => xs.iter().map(|b| &b.result).collect(),
+    }
+  }
+
+  /// Returns the results of every solution that failed or timed out.
+  pub fn failures(&self) -> Vec<&SolutionResult> {
+    self.results().into_iter().filter(|r| !r.status.is_ok()).collect()
+  }
+
+  /// Returns the report rendered in `format`, followed by the baseline comparison, answer verification and
+  /// race between variants if any.
+  pub fn render(
+    &self,
+    format: Format,
+    comparison: Option<&Comparison>,
+    verification: Option<&Verification>,
+    race: Option<&Race>,
+  ) -> String {
+    match format {
+      Format::Json => {
+        let mut value = match *self {
+          Report::Run(ref summary) => summary.to_json(),
+          Report::Bench(ref xs, ref config) => bench::to_json(xs, config),
+        };
+        if let Some(c) = comparison {
+          value["baseline"] = c.to_json();
+        }
+        if let Some(v) = verification {
+          value["verification"] = v.to_json();
+        }
+        if let Some(r) = race {
+          value["race"] = r.to_json();
+        }
+        serde_json::to_string_pretty(&value).unwrap() + "\n"
+      }
+      _ => {
+        let mut s = match *self {
+          Report::Run(ref summary) => summary.render(format),
+          Report::Bench(ref xs, ref config) => bench::render(xs, config, format),
+        };
+        match comparison {
+          Some(c) if format == Format::Csv => s += &c.to_csv_comments(),
+          Some(c) => s += &c.to_text(),
+          None => {}
+        }
+        match verification {
+          Some(v) if format == Format::Csv => s += &v.to_csv_comments(),
+          Some(v) => s += &v.to_text(),
+          None => {}
+        }
+        match race {
+          Some(r) if format == Format::Csv => s += &r.to_csv_comments(),
+          Some(r) => s += &r.to_text(),
+          None => {}
+        }
+        s
+      }
+    }
+  }
+}
+
+/// Returns a single result as a line of text, without line break.
+pub fn result_text(res: &SolutionResult) -> String {
+  format!("{:25}, time = {:.6} s{}", res, res.elapsed, memory_text(res))
+}
+
+// Returns the heap usage and resident set size of a result as a suffix of its line of text
+fn memory_text(res: &SolutionResult) -> String {
+  let mut s = match res.memory {
+    Some(m) => format!(", peak = {}, allocated = {}", memory::format_bytes(m.peak), memory::format_bytes(m.allocated)),
+    None => String::new(),
+  };
+  if let Some(rss) = res.rss {
+    s += &format!(", rss = {}", memory::format_bytes(rss));
+  }
+  s
+}
+
+/// Returns a single result as a JSON value.
+pub fn result_to_json(res: &SolutionResult) -> Value {
+  json!({
+    "id": res.id(),
+    "problem": res.problem,
+    "variant": res.variant,
+    "task": res.task.to_string(),
+    "params": res.args.iter().map(|&(n, v)| (n.to_string(), json!(v))).collect::<serde_json::Map<_, _>>(),
+    "data": res.input,
+    "title": res.title,
+    "crate": res.crate_name,
+    "status": res.status.to_string(),
+    "answer": res.answer.as_ref().map(answer_to_json),
+    "elapsed_s": res.elapsed,
+    "memory": res.memory.map(|m| json!({ "peak_bytes": m.peak, "allocated_bytes": m.allocated })),
+    "max_rss_bytes": res.rss,
+    "seed": res.seed,
+    "error": error_to_json(&res.status),
+  })
+}
+
+fn error_to_json(status: &Status) -> Value {
+  match *status {
+    Status::Ok => Value::Null,
+    Status::Failed { ref message, ref location } => json!({ "message": message, "location": location }),
+    Status::TimedOut(limit) => json!({ "message": format!("timed out after {} s", limit), "location": null }),
+  }
+}
+
+/// Returns a paragraph describing every failed or timed out result, empty when there are none.
+pub fn failures_text(results: &[SolutionResult]) -> String {
+  let failed = results.iter().filter(|r| !r.status.is_ok()).collect::<Vec<_>>();
+  if failed.is_empty() {
+    return String::new();
+  }
+  let mut s = format!("\n{} failed:\n", plural(failed.len(), "solution"));
+  for res in failed {
+    match res.status {
+      Status::Failed { ref message, ref location } => {
+        let at = location.as_ref().map_or(String::new(), |l| format!(" at {}", l));
+        s += &format!("  {} failed{}: {}\n", res.id(), at, message.replace('\n', "\n    "));
+      }
+      Status::TimedOut(limit) => s += &format!("  {} timed out after {} s\n", res.id(), limit),
+      Status::Ok => {}
+    }
+  }
+  s
+}
+
+pub(crate) fn plural(n: usize, word: &str) -> String {
+  if n == 1 {
+    format!("{} {}", n, word)
+  } else {
+    format!("{} {}s", n, word)
+  }
+}
+
+/// Returns an answer as a JSON number or string.
+pub fn answer_to_json(answer: &Answer) -> Value {
+  match *answer {
+    Answer::Integer(n) => json!(n),
+    Answer::Text(ref s) => json!(s),
+  }
+}
+
+// Quotes a CSV field when it contains a separator, quote or line break
+pub(crate) fn csv_field(s: &str) -> String {
+  if s.contains(',') || s.contains('"') || s.contains('\n') {
+    format!("\"{}\"", s.replace('"', "\"\""))
+  } else {
+    s.to_string()
+  }
+}
@@ -0,0 +1,97 @@
+//! Progress of a run, streamed to standard error while solutions complete.
+//!
+//! Every result is printed as soon as its solution finishes, prefixed by the number finished so far. On a
+//! terminal a status line below the results also counts the solutions finished, running and pending, and
+//! is redrawn whenever one starts or finishes. The sorted summary still goes to standard output at the end
+//! of the run.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::progress::Progress;
+//!
+//! # #[cfg(feature = "euler_p001_010")]
+//! fn main() {
+//!     let solutions = euler_rust::euler_p001_010::get_functions();
+//!     let progress = Progress::new(3, false);
+//!     progress.started();
+//!     assert_eq!(progress.counts(), (0, 1, 2));
+//!     progress.finished(&solutions[0].run());
+//!     assert_eq!(progress.counts(), (1, 0, 2));
+//! }
+//! # #[cfg(not(feature = "euler_p001_010"))]
+//! # fn main() {}
+//! ```
+
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+use euler_library::solution::SolutionResult;
+use output;
+
+/// Counts of a run's solutions, shared by the threads running them.
+#[derive(Debug)]
+pub struct Progress {
+  total: usize,
+  /// Redraw a status line on a terminal.
+  live: bool,
+  // (finished, running)
+  counts: Mutex<(usize, usize)>,
+}
+
+impl Progress {
+  /// Returns the progress of a run of `total` solutions; `live` keeps a status line on a terminal.
+  pub fn new(total: usize, live: bool) -> Progress {
+    Progress { total, live, counts: Mutex::new((0, 0)) }
+  }
+
+  /// Returns the number of solutions finished, running and pending.
+  pub fn counts(&self) -> (usize, usize, usize) {
+    let (finished, running) = *self.counts.lock().unwrap();
+    (finished, running, self.total - finished - running)
+  }
+
+  /// Counts a solution as running.
+  pub fn started(&self) {
+    let mut counts = self.counts.lock().unwrap();
+    counts.1 += 1;
+    self.draw(*counts, None);
+  }
+
+  /// Counts the solution of `res` as finished and prints its result.
+  pub fn finished(&self, res: &SolutionResult) {
+    let mut counts = self.counts.lock().unwrap();
+    counts.0 += 1;
+    counts.1 = counts.1.saturating_sub(1);
+    self.draw(*counts, Some(res));
+  }
+
+  /// Clears the status line once the run is over.
+  pub fn done(&self) {
+    if self.live {
+      eprint!("\r\x1b[K");
+    }
+  }
+
+  // Called with the counts locked, so lines of different threads do not interleave
+  fn draw(&self, (finished, running): (usize, usize), res: Option<&SolutionResult>) {
+    let mut s = String::new();
+    if self.live {
+      s += "\r\x1b[K";
+    }
+    if let Some(res) = res {
+      let width = self.total.to_string().len();
+      s += &format!("[{:>w$}/{}] {}\n", finished, self.total, output::result_text(res), w = width);
+    }
+    if self.live {
+      let pending = self.total - finished - running;
+      s += &format!("{} finished, {} running, {} pending", finished, running, pending);
+    }
+    let stderr = io::stderr();
+    let mut handle = stderr.lock();
+    let _ = handle.write_all(s.as_bytes());
+    let _ = handle.flush();
+  }
+}
@@ -0,0 +1,138 @@
+//! Races the solutions of a problem against each other.
+//!
+//! With `--race` every variant of a selected problem runs alongside its main solution. Each problem with
+//! more than one answer in the run is a heat, its solutions ranked by time; solutions disagreeing on the
+//! answer fail the run.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::output::{Report, Summary};
+//! use euler_rust::race::Race;
+//!
+//! # #[cfg(feature = "euler_p061_070")]
+//! fn main() {
+//!     let solutions = euler_rust::euler_p061_070::get_functions();
+//!     let totients = solutions.into_iter().filter(|s| s.problem == 70).collect();
+//!     let mut results = euler_rust::execute_par_iter(totients);
+//!     results[0].elapsed = 2.0;
+//!     results[1].elapsed = 1.0;
+//!     let race = Race::new(&Report::Run(Summary::new(results, 3.0, 1)));
+//!     assert_eq!(race.heats.len(), 1);
+//!     assert_eq!(race.heats[0].entries[1].1, 2.0);
+//!     assert!(race.disagreements().is_empty());
+//! }
+//! # #[cfg(not(feature = "euler_p061_070"))]
+//! # fn main() {}
+//! ```
+
+use euler_library::solution::{Answer, Task};
+use output::{self, Report};
+use serde_json::Value;
+
+/// Solutions of one problem, fastest first, as `(solution id, seconds, answer)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Heat {
+  pub problem: u32,
+  pub entries: Vec<(String, f64, Answer)>,
+}
+
+impl Heat {
+  /// Returns true when every solution gave the same answer.
+  pub fn agrees(&self) -> bool {
+    self.entries.iter().all(|e| e.2 == self.entries[0].2)
+  }
+}
+
+/// Heats of a run, in problem order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Race {
+  pub heats: Vec<Heat>,
+}
+
+impl Race {
+  /// Returns the heats of every problem with more than one answer in `report`, timed by the median for
+  /// benchmarks.
+  pub fn new(report: &Report) -> Race {
+    let timed = match *report {
+      Report::Run(ref summary) => summary.results.iter().map(|r| (r, r.elapsed)).collect::<Vec<_>>(),
+      Report::Bench(ref xs, _) => xs.iter().map(|b| (&b.result, b.stats.median)).collect(),
+    };
+    let mut heats: Vec<Heat> = Vec::new();
+    for (r, elapsed) in timed.into_iter().filter(|&(r, _)| r.task == Task::Answer) {
+      let answer = match r.answer {
+        Some(ref a) if r.status.is_ok() => a.clone(),
+        _ => continue,
+      };
+      match heats.iter_mut().find(|h| h.problem == r.problem) {
+        Some(h) => h.entries.push((r.id(), elapsed, answer)),
+        None => heats.push(Heat { problem: r.problem, entries: vec![(r.id(), elapsed, answer)] }),
+      }
+    }
+    heats.retain(|h| h.entries.len() > 1);
+    for h in &mut heats {
+      h.entries.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    }
+    heats.sort_by_key(|h| h.problem);
+    Race { heats }
+  }
+
+  /// Returns the heats whose solutions gave different answers.
+  pub fn disagreements(&self) -> Vec<&Heat> {
+    self.heats.iter().filter(|h| !h.agrees()).collect()
+  }
+
+  /// Returns the race as a JSON value.
+  pub fn to_json(&self) -> Value {
+    let heats = self
+      .heats
+      .iter()
+      .map(|h| {
+        let entries = h
+          .entries
+          .iter()
+          .map(|e| json!({ "id": e.0, "elapsed_s": e.1, "answer": output::answer_to_json(&e.2) }))
+          .collect::<Vec<_>>();
+        json!({ "problem": h.problem, "agree": h.agrees(), "solutions": entries })
+      })
+      .collect::<Vec<_>>();
+    json!({ "disagreements": self.disagreements().len(), "heats": heats })
+  }
+
+  /// Returns the race as text, one line per problem with each solution's time relative to the fastest.
+  pub fn to_text(&self) -> String {
+    let mut s = format!("\nraced the solutions of {}, fastest first\n", output::plural(self.heats.len(), "problem"));
+    for h in &self.heats {
+      let fastest = h.entries[0].1;
+      let times = h
+        .entries
+        .iter()
+        .map(|e| {
+          if fastest > 0.0 {
+            format!("{} {:.6} s ({:.2}x)", e.0, e.1, e.1 / fastest)
+          } else {
+            format!("{} {:.6} s", e.0, e.1)
+          }
+        })
+        .collect::<Vec<_>>();
+      s += &format!("  p{:03}  {}\n", h.problem, times.join(", "));
+      if !h.agrees() {
+        let answers = h.entries.iter().map(|e| format!("{} = {}", e.0, e.2)).collect::<Vec<_>>();
+        s += &format!("        answers differ: {}\n", answers.join(", "));
+      }
+    }
+    s
+  }
+
+  /// Returns the heats as CSV comment lines.
+  pub fn to_csv_comments(&self) -> String {
+    let mut s = String::new();
+    for h in &self.heats {
+      let entries = h.entries.iter().map(|e| format!("{},{:.6}", e.0, e.1)).collect::<Vec<_>>();
+      s += &format!("# race: p{:03},{},{}\n", h.problem, h.agrees(), entries.join(","));
+    }
+    s
+  }
+}
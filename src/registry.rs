@@ -0,0 +1,63 @@
+//! Registry of solutions keyed by problem id.
+
+use euler_library::solution::{parse_id, Solution};
+
+/// Solutions indexed by problem number and variant rather than by position.
+///
+/// ```
+/// extern crate euler_rust;
+///
+/// use euler_rust::registry::Registry;
+///
+/// # #[cfg(feature = "euler_p061_070")]
+/// fn main() {
+///     let registry = Registry::new(euler_rust::euler_p061_070::get_functions());
+///     assert_eq!(registry.get("70").unwrap().id(), "p070");
+///     assert_eq!(registry.get("p070a").unwrap().crate_name, "euler_p061_070");
+///     assert!(registry.get("71").is_none());
+///     assert_eq!(registry.main_solutions().len(), 10);
+/// }
+/// # #[cfg(not(feature = "euler_p061_070"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Debug)]
+pub struct Registry {
+  solutions: Vec<Solution>,
+}
+
+impl Registry {
+  /// Returns a registry of `solutions` ordered by problem number then variant.
+  pub fn new(mut solutions: Vec<Solution>) -> Registry {
+    solutions.sort_by(|a, b| (a.problem, a.variant).cmp(&(b.problem, b.variant)));
+    Registry { solutions }
+  }
+
+  /// Returns the solution registered under `id`, e.g. `7`, `p007` or `p070a`.
+  pub fn get(&self, id: &str) -> Option<&Solution> {
+    let (problem, variant) = parse_id(id)?;
+    self
+      .solutions
+      .iter()
+      .find(|s| s.problem == problem && s.variant == variant.as_deref())
+  }
+
+  /// Returns every registered solution, variants included.
+  pub fn solutions(&self) -> &[Solution] {
+    &self.solutions
+  }
+
+  /// Returns the main solution of each problem, leaving out variants.
+  pub fn main_solutions(&self) -> Vec<Solution> {
+    self.solutions.iter().filter(|s| s.variant.is_none()).cloned().collect()
+  }
+
+  /// Returns the main solution and variants of `problem`.
+  pub fn problem(&self, problem: u32) -> Vec<Solution> {
+    self.solutions.iter().filter(|s| s.problem == problem).cloned().collect()
+  }
+
+  /// Returns the ids of every registered solution.
+  pub fn ids(&self) -> Vec<String> {
+    self.solutions.iter().map(|s| s.id()).collect()
+  }
+}
@@ -0,0 +1,192 @@
+//! HTML and Markdown reports of a run, listing every selected problem for publishing, written by
+//! `euler_rust report`.
+//!
+//! Unimplemented problems are listed without running them. Answers can be masked to publish which
+//! problems are solved without giving their answers away.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::euler_library::solution::{Implementation, Solution};
+//! use euler_rust::report::Page;
+//!
+//! # #[cfg(feature = "euler_p121_130")]
+//! fn main() {
+//!     let solutions = euler_rust::euler_p121_130::get_functions();
+//!     let (solutions, stubs): (Vec<_>, Vec<_>) =
+//!         solutions.into_iter().partition(|s| s.implementation != Implementation::Unimplemented);
+//!     let page = Page::new(euler_rust::execute_par_iter(solutions[..2].to_vec()), &stubs, true, 0);
+//!     assert_eq!(page.rows.len(), 5);
+//!     assert_eq!(page.solved(), 2);
+//!
+//!     let markdown = page.to_markdown();
+//!     assert!(markdown.contains("| 121 | Disc game prize fund | ok | •••••• |"));
+//!     assert!(markdown.contains("| 128 | Hexagonal tile differences | unimplemented |  |"));
+//!     assert!(page.to_html().contains("<td>Hexagonal tile differences</td>"));
+//!
+//!     let failing = Solution::new("my_crate", 1, "Multiples of 3 and 5", || panic!("no answer"));
+//!     let page = Page::new(vec![failing.run()], &[], false, 0);
+//!     assert_eq!(page.rows[0].status, "failed");
+//!     assert!(page.rows[0].error.as_ref().unwrap().contains("no answer"));
+//! }
+//! # #[cfg(not(feature = "euler_p121_130"))]
+//! # fn main() {}
+//! ```
+
+use euler_library::memory;
+use euler_library::solution::{Solution, SolutionResult, Status};
+use runlog;
+
+/// Shown instead of an answer when answers are masked.
+pub const MASK: &str = "••••••";
+
+/// A problem listed by the report.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row {
+  pub id: String,
+  pub problem: u32,
+  pub variant: Option<&'static str>,
+  pub title: &'static str,
+  pub crate_name: &'static str,
+  /// `ok`, `failed`, `timed out` or `unimplemented`.
+  pub status: String,
+  /// Why the solution failed or timed out, `None` otherwise.
+  pub error: Option<String>,
+  /// Answer as published, `None` when there is none.
+  pub answer: Option<String>,
+  /// Execution time in fractional seconds, `None` for unimplemented problems.
+  pub elapsed: Option<f64>,
+  /// Peak heap usage, or the peak resident set size of an isolated run, in bytes.
+  pub memory: Option<usize>,
+}
+
+/// Rows of a report, in problem order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Page {
+  pub rows: Vec<Row>,
+  /// Unix time the report was generated at.
+  pub generated: u64,
+}
+
+impl Page {
+  /// Returns the report of the run `results` and the unimplemented problems `unimplemented`, generated
+  /// at Unix time `generated`, masking the answers if `mask`.
+  pub fn new(results: Vec<SolutionResult>, unimplemented: &[Solution], mask: bool, generated: u64) -> Page {
+    let mut rows = results
+      .into_iter()
+      .map(|r| Row {
+        id: r.id(),
+        problem: r.problem,
+        variant: r.variant,
+        title: r.title,
+        crate_name: r.crate_name,
+        status: r.status.to_string(),
+        error: match r.status {
+          Status::Ok => None,
+          Status::Failed { ref message, .. } => Some(message.clone()),
+          Status::TimedOut(limit) => Some(format!("timed out after {} s", limit)),
+        },
+        answer: r.answer.as_ref().map(|a| if mask { MASK.to_string() } else { a.to_string() }),
+        elapsed: Some(r.elapsed),
+        memory: r.memory.map(|m| m.peak).or(r.rss),
+      })
+      .collect::<Vec<_>>();
+    rows.extend(unimplemented.iter().map(|s| Row {
+      id: s.id(),
+      problem: s.problem,
+      variant: s.variant,
+      title: s.title,
+      crate_name: s.crate_name,
+      status: s.implementation.to_string(),
+      error: None,
+      answer: None,
+      elapsed: None,
+      memory: None,
+    }));
+    rows.sort_by(|a, b| (a.problem, a.variant).cmp(&(b.problem, b.variant)));
+    Page { rows, generated }
+  }
+
+  /// Returns the number of problems whose main solution gave an answer.
+  pub fn solved(&self) -> usize {
+    self.rows.iter().filter(|r| r.variant.is_none() && r.answer.is_some()).count()
+  }
+
+  /// Returns the report as a Markdown heading, summary line and table.
+  pub fn to_markdown(&self) -> String {
+    let mut s = "# Project Euler solutions\n\n".to_string();
+    s += &format!("{}, generated {}.\n\n", self.summary(), runlog::format_timestamp(self.generated));
+    s += "| Problem | Title | Status | Answer | Time | Memory | Crate | Error |\n";
+    s += "|--------:|-------|--------|-------:|-----:|-------:|-------|-------|\n";
+    for row in &self.rows {
+      let cells = cells(row).iter().map(|c| c.replace('|', "\\|").replace('\n', " ")).collect::<Vec<_>>();
+      s += &format!("| {} |\n", cells.join(" | "));
+    }
+    s
+  }
+
+  /// Returns the report as a self-contained HTML page.
+  pub fn to_html(&self) -> String {
+    let mut s = String::from(
+      "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Project Euler solutions</title>\n\
+       <style>\n\
+       body { font-family: sans-serif; margin: 2em; }\n\
+       table { border-collapse: collapse; }\n\
+       th, td { padding: 0.25em 0.75em; border-bottom: 1px solid #ddd; text-align: left; }\n\
+       td.number { text-align: right; font-variant-numeric: tabular-nums; }\n\
+       tr.failed td { color: #b00020; }\n\
+       tr.unimplemented td { color: #888; }\n\
+       </style>\n</head>\n<body>\n<h1>Project Euler solutions</h1>\n",
+    );
+    s += &format!(
+      "<p>{}, generated {}.</p>\n",
+      escape(&self.summary()),
+      runlog::format_timestamp(self.generated)
+    );
+    s += "<table>\n<thead>\n<tr><th>Problem</th><th>Title</th><th>Status</th><th>Answer</th><th>Time</th>\
+          <th>Memory</th><th>Crate</th><th>Error</th></tr>\n</thead>\n<tbody>\n";
+    for row in &self.rows {
+      let class = match (row.elapsed, row.answer.is_some()) {
+        (None, _) => " class=\"unimplemented\"",
+        (Some(_), false) => " class=\"failed\"",
+        _ => "",
+      };
+      let cells = cells(row)
+        .iter()
+        .enumerate()
+        .map(|(i, c)| match i {
+          0 | 3 | 4 | 5 => format!("<td class=\"number\">{}</td>", escape(c)),
+          _ => format!("<td>{}</td>", escape(c)),
+        })
+        .collect::<String>();
+      s += &format!("<tr{}>{}</tr>\n", class, cells);
+    }
+    s + "</tbody>\n</table>\n</body>\n</html>\n"
+  }
+
+  fn summary(&self) -> String {
+    let mains = self.rows.iter().filter(|r| r.variant.is_none());
+    format!("{} of {} problems solved", self.solved(), mains.count())
+  }
+}
+
+// Returns the columns of a row as text
+fn cells(row: &Row) -> [String; 8] {
+  [
+    format!("{}{}", row.problem, row.variant.unwrap_or("")),
+    row.title.to_string(),
+    row.status.clone(),
+    row.answer.clone().unwrap_or_default(),
+    row.elapsed.map_or(String::new(), |t| format!("{:.6} s", t)),
+    row.memory.map_or(String::new(), memory::format_bytes),
+    row.crate_name.to_string(),
+    row.error.clone().unwrap_or_default(),
+  ]
+}
+
+// Escapes text for HTML element content
+fn escape(s: &str) -> String {
+  s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,412 @@
+//! Log of the timings of every run, and their trend across runs.
+//!
+//! Every run of the command line runner appends a line of JSON to the run log, by default `euler_runs.jsonl` in the
+//! cargo `target` directory the runner was built in: when it ran, the git commit checked out in the working directory,
+//! the machine, and the time of every answer to an Euler problem, the median for benchmarks. Nothing is ever
+//! overwritten. `euler_rust history` reads the log back and shows how the time of each selected problem changed from
+//! run to run, and the runner starts the slowest solutions first by their latest logged time, see module
+//! [`schedule`](../schedule/index.html).
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::output::Format;
+//! use euler_rust::runlog::{self, Run};
+//!
+//! # #[cfg(feature = "euler_p061_070")]
+//! fn main() {
+//!     let first = Run::new(&[("p067".to_string(), 0.002)], false);
+//!     let second = Run { timestamp: first.timestamp + 60, ..Run::new(&[("p067".to_string(), 0.003)], false) };
+//!     assert_eq!(Run::from_json(&first.to_json()), Some(first.clone()));
+//!
+//!     let solutions = euler_rust::euler_p061_070::get_functions();
+//!     let trends = runlog::trends(&[first, second], &solutions, None);
+//!     assert_eq!(trends.len(), 1);
+//!     assert_eq!(trends[0].points[1].change(&trends[0].points[0]), 50.0);
+//!     assert!(runlog::render(&trends, Format::Text).starts_with("p067 Maximum path sum II, 2 runs"));
+//! }
+//! # #[cfg(not(feature = "euler_p061_070"))]
+//! # fn main() {}
+//! ```
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::process::Command;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use euler_library::solution::Solution;
+#[cfg(unix)]
+use libc;
+use output::{self, Format, SCHEMA_VERSION};
+use serde_json::Value;
+
+/// File name of the default run log.
+pub const LOG_NAME: &str = "euler_runs.jsonl";
+
+/// Returns the run log the command line runner appends to unless told otherwise: `euler_runs.jsonl` in the
+/// cargo `target` directory holding the program, `None` when the program runs from elsewhere, e.g. installed.
+pub fn default_log() -> Option<String> {
+  let exe = env::current_exe().ok()?;
+  let target = exe.ancestors().find(|dir| dir.file_name().is_some_and(|name| name == "target"))?;
+  Some(target.join(LOG_NAME).to_string_lossy().into_owned())
+}
+
+/// The machine a run took place on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Machine {
+  pub hostname: String,
+  /// Operating system, e.g. `linux`.
+  pub os: String,
+  /// CPU architecture, e.g. `x86_64`.
+  pub arch: String,
+  /// Number of logical CPUs.
+  pub cpus: usize,
+  /// CPU model name, when the operating system tells.
+  pub cpu_model: Option<String>,
+}
+
+impl Machine {
+  /// Returns the machine the program runs on.
+  pub fn current() -> Machine {
+    Machine {
+      hostname: hostname().unwrap_or_default(),
+      os: env::consts::OS.to_string(),
+      arch: env::consts::ARCH.to_string(),
+      cpus: thread::available_parallelism().map_or(1, |n| n.get()),
+      cpu_model: cpu_model(),
+    }
+  }
+}
+
+/// One line of the run log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+  /// Seconds since the Unix epoch.
+  pub timestamp: u64,
+  /// Commit checked out in the working directory, `None` outside a git repository.
+  pub commit: Option<String>,
+  /// The working tree had uncommitted changes.
+  pub dirty: bool,
+  pub machine: Machine,
+  /// The program was built with optimizations.
+  pub release: bool,
+  /// Timings are benchmark medians rather than single runs.
+  pub bench: bool,
+  /// Execution times in fractional seconds, keyed by problem id.
+  pub timings: BTreeMap<String, f64>,
+}
+
+impl Run {
+  /// Returns a run of `(problem id, seconds)` timings taking place now on this machine.
+  pub fn new(timings: &[(String, f64)], bench: bool) -> Run {
+    let (commit, dirty) = git_commit();
+    Run {
+      timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+      commit,
+      dirty,
+      machine: Machine::current(),
+      release: !cfg!(debug_assertions),
+      bench,
+      timings: timings.iter().cloned().collect(),
+    }
+  }
+
+  /// Returns the run as a JSON value, one line of the run log.
+  pub fn to_json(&self) -> Value {
+    json!({
+      "schema_version": SCHEMA_VERSION,
+      "timestamp": format_timestamp(self.timestamp),
+      "unix_time": self.timestamp,
+      "commit": self.commit,
+      "dirty": self.dirty,
+      "machine": {
+        "hostname": self.machine.hostname,
+        "os": self.machine.os,
+        "arch": self.machine.arch,
+        "cpus": self.machine.cpus,
+        "cpu_model": self.machine.cpu_model,
+      },
+      "release": self.release,
+      "bench": self.bench,
+      "timings_s": self.timings,
+    })
+  }
+
+  /// Reads a run written by `to_json`, `None` when a field is missing.
+  pub fn from_json(value: &Value) -> Option<Run> {
+    let machine = &value["machine"];
+    Some(Run {
+      timestamp: value["unix_time"].as_u64()?,
+      commit: value["commit"].as_str().map(String::from),
+      dirty: value["dirty"].as_bool()?,
+      machine: Machine {
+        hostname: machine["hostname"].as_str()?.to_string(),
+        os: machine["os"].as_str()?.to_string(),
+        arch: machine["arch"].as_str()?.to_string(),
+        cpus: machine["cpus"].as_u64()? as usize,
+        cpu_model: machine["cpu_model"].as_str().map(String::from),
+      },
+      release: value["release"].as_bool()?,
+      bench: value["bench"].as_bool()?,
+      timings: value["timings_s"]
+        .as_object()?
+        .iter()
+        .filter_map(|(id, t)| t.as_f64().map(|t| (id.clone(), t)))
+        .collect(),
+    })
+  }
+}
+
+/// Appends `run` to the run log at `path`, creating it if needed.
+pub fn append(path: &str, run: &Run) -> Result<(), String> {
+  OpenOptions::new()
+    .create(true)
+    .append(true)
+    .open(path)
+    .and_then(|mut f| f.write_all(format!("{}\n", run.to_json()).as_bytes()))
+    .map_err(|e| format!("cannot write run log {}: {}", path, e))
+}
+
+/// Reads every run of the run log at `path`, oldest first; a missing file is an empty log. Lines that are
+/// not a run, e.g. half written by a killed run, are skipped with a warning on standard error.
+pub fn load(path: &str) -> Result<Vec<Run>, String> {
+  let text = match fs::read_to_string(path) {
+    Ok(text) => text,
+    Err(ref e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+    Err(e) => return Err(format!("cannot read run log {}: {}", path, e)),
+  };
+  let mut runs = Vec::new();
+  for (i, line) in text.lines().enumerate().filter(|&(_, line)| !line.trim().is_empty()) {
+    let run = serde_json::from_str::<Value>(line)
+      .map_err(|e| e.to_string())
+      .and_then(|value| Run::from_json(&value).ok_or_else(|| "missing field".to_string()));
+    match run {
+      Ok(run) => runs.push(run),
+      Err(e) => eprintln!("skipping invalid run log {}, line {}: {}", path, i + 1, e),
+    }
+  }
+  runs.sort_by_key(|r| r.timestamp);
+  Ok(runs)
+}
+
+/// Time of a problem in one run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Point {
+  /// Seconds since the Unix epoch.
+  pub timestamp: u64,
+  pub commit: Option<String>,
+  pub dirty: bool,
+  pub hostname: String,
+  pub release: bool,
+  /// Execution time in fractional seconds.
+  pub elapsed: f64,
+}
+
+impl Point {
+  /// Returns the relative change in percent from `previous`, positive when slower.
+  pub fn change(&self, previous: &Point) -> f64 {
+    if previous.elapsed > 0.0 {
+      (self.elapsed / previous.elapsed - 1.0) * 100.0
+    } else {
+      0.0
+    }
+  }
+
+  // Short commit hash, `*` marking uncommitted changes
+  fn commit_text(&self) -> String {
+    let commit = self.commit.as_ref().map_or("-", |c| &c[..c.len().min(9)]);
+    format!("{}{}", commit, if self.dirty { "*" } else { "" })
+  }
+}
+
+/// Times of a problem across runs, oldest first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trend {
+  pub id: String,
+  pub title: &'static str,
+  pub points: Vec<Point>,
+}
+
+/// Returns the trend of every solution in `solutions` timed in `runs`, keeping the `last` runs of each if
+/// given.
+pub fn trends(runs: &[Run], solutions: &[Solution], last: Option<usize>) -> Vec<Trend> {
+  solutions
+    .iter()
+    .map(|s| {
+      let id = s.id();
+      let mut points = runs
+        .iter()
+        .filter_map(|r| {
+          r.timings.get(&id).map(|&elapsed| Point {
+            timestamp: r.timestamp,
+            commit: r.commit.clone(),
+            dirty: r.dirty,
+            hostname: r.machine.hostname.clone(),
+            release: r.release,
+            elapsed,
+          })
+        })
+        .collect::<Vec<_>>();
+      if let Some(n) = last {
+        points.drain(..points.len().saturating_sub(n));
+      }
+      Trend { id, title: s.title, points }
+    })
+    .filter(|t| !t.points.is_empty())
+    .collect()
+}
+
+/// Returns `trends` rendered in `format`.
+pub fn render(trends: &[Trend], format: Format) -> String {
+  match format {
+    Format::Text => to_text(trends),
+    Format::Json => {
+      let value = json!({
+        "schema_version": SCHEMA_VERSION,
+        "trends": trends.iter().map(trend_to_json).collect::<Vec<_>>(),
+      });
+      serde_json::to_string_pretty(&value).unwrap() + "\n"
+    }
+    Format::Csv => {
+      let mut s = format!("# schema_version: {}\n", SCHEMA_VERSION);
+      s += "id,timestamp,commit,dirty,hostname,release,elapsed_s\n";
+      for t in trends {
+        for p in &t.points {
+          let row = [
+            t.id.clone(),
+            format_timestamp(p.timestamp),
+            p.commit.clone().unwrap_or_default(),
+            p.dirty.to_string(),
+            p.hostname.clone(),
+            p.release.to_string(),
+            format!("{:.6}", p.elapsed),
+          ];
+          s += &row.iter().map(|x| output::csv_field(x)).collect::<Vec<_>>().join(",");
+          s += "\n";
+        }
+      }
+      s
+    }
+  }
+}
+
+fn to_text(trends: &[Trend]) -> String {
+  if trends.is_empty() {
+    return "no runs logged for the selected problems\n".to_string();
+  }
+  let mut s = String::new();
+  for t in trends {
+    let mut sorted = t.points.iter().map(|p| p.elapsed).collect::<Vec<_>>();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let (first, latest) = (&t.points[0], &t.points[t.points.len() - 1]);
+    s += &format!(
+      "{} {}, {} run{}: min {:.6} s, median {:.6} s, latest {:.6} s ({:+.1}% since the first)\n",
+      t.id,
+      t.title,
+      t.points.len(),
+      if t.points.len() == 1 { "" } else { "s" },
+      sorted[0],
+      sorted[sorted.len() / 2],
+      latest.elapsed,
+      latest.change(first)
+    );
+    for (i, p) in t.points.iter().enumerate() {
+      s += &format!(
+        "  {}  {:10}  {:12}  {:7}  {:.6} s",
+        format_timestamp(p.timestamp),
+        p.commit_text(),
+        p.hostname,
+        if p.release { "release" } else { "debug" },
+        p.elapsed
+      );
+      if i > 0 {
+        s += &format!("  {:+.1}%", p.change(&t.points[i - 1]));
+      }
+      s += "\n";
+    }
+  }
+  s
+}
+
+fn trend_to_json(t: &Trend) -> Value {
+  let runs = t
+    .points
+    .iter()
+    .map(|p| {
+      json!({
+        "timestamp": format_timestamp(p.timestamp),
+        "commit": p.commit,
+        "dirty": p.dirty,
+        "hostname": p.hostname,
+        "release": p.release,
+        "elapsed_s": p.elapsed,
+      })
+    })
+    .collect::<Vec<_>>();
+  json!({ "id": t.id, "title": t.title, "runs": runs })
+}
+
+/// Formats seconds since the Unix epoch as an ISO 8601 UTC date and time.
+///
+/// ```
+/// use euler_rust::runlog::format_timestamp;
+///
+/// assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+/// assert_eq!(format_timestamp(1_700_000_000), "2023-11-14T22:13:20Z");
+/// ```
+pub fn format_timestamp(secs: u64) -> String {
+  // days to civil date, shifted to eras of 400 years starting on March 1st
+  let z = secs / 86_400 + 719_468;
+  let (era, doe) = (z / 146_097, z % 146_097);
+  let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = doy - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+  let rem = secs % 86_400;
+  format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, rem / 3600, rem / 60 % 60, rem % 60)
+}
+
+// Returns the commit checked out in the working directory and whether tracked files changed since
+fn git_commit() -> (Option<String>, bool) {
+  let args = ["status", "--porcelain=v2", "--branch", "--untracked-files=no"];
+  let status = match Command::new("git").args(args).output() {
+    Ok(ref out) if out.status.success() => String::from_utf8_lossy(&out.stdout).into_owned(),
+    _ => return (None, false),
+  };
+  // headers start with `#`, the commit is `(initial)` before the first one; every other line is a change
+  let commit = status
+    .lines()
+    .filter_map(|line| line.strip_prefix("# branch.oid "))
+    .find(|&oid| oid != "(initial)")
+    .map(String::from);
+  let dirty = status.lines().any(|line| !line.starts_with('#'));
+  (commit, dirty)
+}
+
+#[cfg(unix)]
+fn hostname() -> Option<String> {
+  let mut buf = [0u8; 256];
+  if unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) } != 0 {
+    return None;
+  }
+  let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+  Some(String::from_utf8_lossy(&buf[..len]).into_owned())
+}
+
+#[cfg(not(unix))]
+fn hostname() -> Option<String> {
+  env::var("COMPUTERNAME").ok()
+}
+
+fn cpu_model() -> Option<String> {
+  let text = fs::read_to_string("/proc/cpuinfo").ok()?;
+  let line = text.lines().find(|line| line.starts_with("model name"))?;
+  Some(line[line.find(':')? + 1..].trim().to_string())
+}
@@ -0,0 +1,106 @@
+//! Longest-first scheduling from the timings of earlier runs.
+//!
+//! Before a parallel run the runner reads its run log, see module [`runlog`](../runlog/index.html), and
+//! starts the solutions slowest first by their most recent logged time (LPT scheduling), so the long ones
+//! do not end up alone at the tail of the run. Solutions without a logged time are started before all
+//! others. The order solutions are
+//! started in does not affect the order their results are reported in, see [`Order`](enum.Order.html).
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::runlog::Run;
+//! use euler_rust::schedule;
+//!
+//! # #[cfg(feature = "euler_p001_010")]
+//! fn main() {
+//!     let solutions = euler_rust::euler_p001_010::get_functions()[..3].to_vec();
+//!     let runs = vec![
+//!         Run::new(&[("p001".to_string(), 0.2), ("p002".to_string(), 0.3)], false),
+//!         Run::new(&[("p001".to_string(), 0.1)], false),
+//!     ];
+//!     let history = schedule::latest_timings(&runs);
+//!     assert_eq!(history.timings["p001"], 0.1);
+//!     let ids = schedule::longest_first(solutions, &history).iter().map(|s| s.id()).collect::<Vec<_>>();
+//!     assert_eq!(ids, vec!["p003", "p002", "p001"]);
+//! }
+//! # #[cfg(not(feature = "euler_p001_010"))]
+//! # fn main() {}
+//! ```
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use baseline::Baseline;
+use euler_library::solution::{Solution, SolutionResult, Task};
+use runlog::Run;
+
+/// Order results are reported in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Order {
+  /// Fastest first.
+  Time,
+  /// As listed in the selection expression.
+  Selection,
+  /// By problem number, variants after their main solution.
+  Problem,
+}
+
+impl FromStr for Order {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Order, String> {
+    match s {
+      "time" => Ok(Order::Time),
+      "selection" => Ok(Order::Selection),
+      "problem" => Ok(Order::Problem),
+      _ => Err(format!("unknown order: {} (expected time, selection or problem)", s)),
+    }
+  }
+}
+
+impl Order {
+  /// Sorts `results` in this order; `selection` lists the selected ids in the order they were chosen.
+  /// A problem's examples come before its answer unless ordered by time.
+  pub fn sort(self, results: &mut [SolutionResult], selection: &[String]) {
+    results.sort_by_key(|r| r.task != Task::Examples);
+    self.sort_by_key(results, selection, |r| (r.id(), r.elapsed))
+  }
+
+  /// Sorts any kind of result given its `(problem id, seconds)`.
+  pub fn sort_by_key<T, F>(self, xs: &mut [T], selection: &[String], key: F)
+  where
+    F: Fn(&T) -> (String, f64),
+  {
+    let position = |x: &T| {
+      let id = key(x).0;
+      selection.iter().position(|s| *s == id).unwrap_or(selection.len())
+    };
+    match self {
+      Order::Time => xs.sort_by(|a, b| key(a).1.partial_cmp(&key(b).1).unwrap_or(Ordering::Equal)),
+      Order::Selection => xs.sort_by_key(position),
+      // ids are zero padded, so string order is problem order with variants last
+      Order::Problem => xs.sort_by_key(|x| key(x).0),
+    }
+  }
+}
+
+/// Returns the most recent time of every problem timed in `runs`, oldest run first as in the run log.
+pub fn latest_timings(runs: &[Run]) -> Baseline {
+  // later runs replace the times of earlier ones when collected into the map
+  Baseline { timings: runs.iter().flat_map(|r| r.timings.clone()).collect() }
+}
+
+/// Returns `solutions` ordered slowest first by their time in `history`, unknown ones first. Examples
+/// are taken to be instant.
+pub fn longest_first(mut solutions: Vec<Solution>, history: &Baseline) -> Vec<Solution> {
+  let expected = |s: &Solution| match s.task {
+    Task::Answer => history.timings.get(&s.id()).cloned().unwrap_or(f64::INFINITY),
+    Task::Examples => 0.0,
+  };
+  // stable, so solutions with equal or unknown times keep their relative order
+  solutions.sort_by(|a, b| expected(b).partial_cmp(&expected(a)).unwrap_or(Ordering::Equal));
+  solutions
+}
@@ -0,0 +1,166 @@
+//! Problem selection expressions accepted by the runner.
+//!
+//! An expression is a comma separated list of terms:
+//!
+//! * `54` or `p070a`: a single registered solution
+//! * `1-50`: the main solution of every registered problem in the range
+//! * `crate:euler_p101_110`: the main solutions in a crate
+//! * `!84`, `!1-10`, `!crate:euler_p081_090`: removes the matching solutions
+//!
+//! Without any including term every main solution is selected before exclusions apply.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::registry::Registry;
+//! use euler_rust::selection;
+//!
+//! # #[cfg(all(feature = "p001_050", feature = "p051_100", feature = "p101_130"))]
+//! fn main() {
+//!     let registry = Registry::new(euler_rust::get_all_functions());
+//!
+//!     let ids = |expr| {
+//!         selection::select(&registry, expr).unwrap().iter().map(|s| s.id()).collect::<Vec<_>>()
+//!     };
+//!     assert_eq!(ids("54,96,107"), ["p054", "p096", "p107"]);
+//!     assert_eq!(ids("1-5,!3"), ["p001", "p002", "p004", "p005"]);
+//!     assert_eq!(ids("crate:euler_p121_130,!p127").len(), 9);
+//!     assert_eq!(ids("!84").len(), registry.main_solutions().len() - 1);
+//!
+//!     assert!(selection::select(&registry, "131").is_err());
+//! }
+//! # #[cfg(not(all(feature = "p001_050", feature = "p051_100", feature = "p101_130")))]
+//! # fn main() {}
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use euler_library::solution::{format_id, parse_id, Solution};
+use registry::Registry;
+
+/// Error returned for a selection expression that cannot be resolved.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelectionError {
+  /// The term is not an id, range or crate.
+  Syntax(String),
+  /// No solution is registered under the id or range; holds the registered ids.
+  UnknownId(String, String),
+  /// No crate of that name is registered; holds the registered crates.
+  UnknownCrate(String, Vec<String>),
+}
+
+impl fmt::Display for SelectionError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      SelectionError::Syntax(ref term) => write!(
+        f,
+        "invalid selection term: {:?} (expected e.g. 54, p070a, 1-50, crate:euler_p101_110 or !84)",
+        term
+      ),
+      SelectionError::UnknownId(ref term, ref registered) => {
+        write!(f, "no registered solution matches {}; registered ids: {}", term, registered)
+      }
+      SelectionError::UnknownCrate(ref name, ref registered) => {
+        write!(f, "unknown crate: {}; registered crates: {}", name, registered.join(", "))
+      }
+    }
+  }
+}
+
+impl Error for SelectionError {}
+
+/// Returns the solutions in `registry` chosen by selection expression `expr`.
+pub fn select(registry: &Registry, expr: &str) -> Result<Vec<Solution>, SelectionError> {
+  let mut included: Vec<Solution> = Vec::new();
+  let mut excluded: Vec<Solution> = Vec::new();
+  let mut any_included = false;
+
+  for term in expr.split(',').map(|t| t.trim()).filter(|t| !t.is_empty()) {
+    if let Some(rest) = term.strip_prefix('!') {
+      excluded.append(&mut matches(registry, rest)?);
+    } else {
+      any_included = true;
+      for s in matches(registry, term)? {
+        if !contains(&included, &s) {
+          included.push(s);
+        }
+      }
+    }
+  }
+
+  if !any_included {
+    included = registry.main_solutions();
+  }
+  Ok(included.into_iter().filter(|s| !contains(&excluded, s)).collect())
+}
+
+/// Returns the registered ids compressed into ranges, e.g. `p001-p130, p070a`, or `none` when the
+/// cargo features enable no problem crate.
+pub fn describe_ids(registry: &Registry) -> String {
+  if registry.solutions().is_empty() {
+    return "none".to_string();
+  }
+  let mut parts: Vec<String> = Vec::new();
+  let mains = registry.main_solutions();
+  let mut i = 0;
+  while i < mains.len() {
+    let mut j = i;
+    while j + 1 < mains.len() && mains[j + 1].problem == mains[j].problem + 1 {
+      j += 1;
+    }
+    if i == j {
+      parts.push(mains[i].id());
+    } else {
+      parts.push(format!("{}-{}", mains[i].id(), mains[j].id()));
+    }
+    i = j + 1;
+  }
+  for s in registry.solutions().iter().filter(|s| s.variant.is_some()) {
+    parts.push(s.id());
+  }
+  parts.join(", ")
+}
+
+// Returns the solutions matched by a single term without its `!` prefix
+fn matches(registry: &Registry, term: &str) -> Result<Vec<Solution>, SelectionError> {
+  if let Some(name) = term.strip_prefix("crate:") {
+    let xs: Vec<Solution> = registry.main_solutions().into_iter().filter(|s| s.crate_name == name).collect();
+    if xs.is_empty() {
+      let mut crates: Vec<String> = registry.solutions().iter().map(|s| s.crate_name.to_string()).collect();
+      crates.dedup();
+      return Err(SelectionError::UnknownCrate(name.to_string(), crates));
+    }
+    return Ok(xs);
+  }
+
+  if let Some(pos) = term.find('-') {
+    let bound = |s: &str| match s.trim().trim_start_matches('p').parse::<u32>() {
+      Ok(n) => Ok(n),
+      Err(_) => Err(SelectionError::Syntax(term.to_string())),
+    };
+    let (lo, hi) = (bound(&term[..pos])?, bound(&term[pos + 1..])?);
+    if lo > hi {
+      return Err(SelectionError::Syntax(term.to_string()));
+    }
+    let xs: Vec<Solution> =
+      registry.main_solutions().into_iter().filter(|s| s.problem >= lo && s.problem <= hi).collect();
+    if xs.is_empty() {
+      let range = format!("{}-{}", format_id(lo, None), format_id(hi, None));
+      return Err(SelectionError::UnknownId(range, describe_ids(registry)));
+    }
+    return Ok(xs);
+  }
+
+  match registry.get(term) {
+    Some(s) => Ok(vec![s.clone()]),
+    None if parse_id(term).is_none() => Err(SelectionError::Syntax(term.to_string())),
+    None => Err(SelectionError::UnknownId(term.to_string(), describe_ids(registry))),
+  }
+}
+
+fn contains(xs: &[Solution], s: &Solution) -> bool {
+  xs.iter().any(|x| x.problem == s.problem && x.variant == s.variant)
+}
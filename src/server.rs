@@ -0,0 +1,400 @@
+//! Local HTTP service exposing the registered solutions as JSON, started by `euler_rust serve`.
+//!
+//! The server listens on `127.0.0.1` only and answers one request per connection:
+//!
+//! * `GET /problems`: every registered solution with its parameters
+//! * `GET /problems/{id}`: a single solution, e.g. `/problems/87` or `/problems/p070a`
+//! * `POST /problems/{id}/run?NAME=VALUE&seed=N`: runs the solution, for other parameter values or with a
+//!   seed if given, and returns its result in the layout of `--format json`
+//! * `GET /run?selection=EXPR`: runs the selected main solutions, or all of them, streaming each result as a
+//!   server-sent `result` event as soon as it completes, then a `summary` event with the aggregate timings
+//!
+//! Errors are answered with a 4xx status and a JSON object holding an `error` message, as is a request
+//! arriving slower than [`READ_TIMEOUT`](constant.READ_TIMEOUT.html) or with a line longer than
+//! [`MAX_LINE`](constant.MAX_LINE.html). While all [`MAX_CONNECTIONS`](constant.MAX_CONNECTIONS.html) threads
+//! are answering, new connections are refused with `503 Service Unavailable` rather than queued.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use std::io::{Read, Write};
+//! use std::net::TcpStream;
+//! use std::thread;
+//! use std::time::Duration;
+//!
+//! use euler_rust::server::{Server, MAX_CONNECTIONS};
+//!
+//! # #[cfg(feature = "euler_p001_010")]
+//! fn main() {
+//!     let server = Server::bind(0, euler_rust::euler_p001_010::get_functions(), Server::DEFAULT_TIMEOUT).unwrap();
+//!     let addr = server.local_addr().unwrap();
+//!     thread::spawn(move || server.run());
+//!
+//!     let request = |text: &str| {
+//!         let mut stream = TcpStream::connect(addr).unwrap();
+//!         stream.write_all(text.as_bytes()).unwrap();
+//!         let mut response = String::new();
+//!         stream.read_to_string(&mut response).unwrap();
+//!         response
+//!     };
+//!     let response = request("POST /problems/1/run?n=10 HTTP/1.1\r\nHost: localhost\r\n\r\n");
+//!     assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+//!     assert!(response.contains(r#""answer":23"#));
+//!
+//!     let response = request("GET /run?selection=1-3 HTTP/1.1\r\n\r\n");
+//!     assert_eq!(response.matches("event: result\n").count(), 3);
+//!     assert!(response.contains("event: summary\n"));
+//!
+//!     assert!(request("GET /problems/11 HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 404 Not Found\r\n"));
+//!
+//!     let long = format!("GET /problems HTTP/1.1\r\nX-Padding: {}\r\n\r\n", "x".repeat(10_000));
+//!     assert!(request(&long).starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"));
+//!
+//!     // clients still sending their requests hold every thread, so the next one is refused
+//!     thread::sleep(Duration::from_millis(100));
+//!     let idle = (0..MAX_CONNECTIONS).map(|_| TcpStream::connect(addr).unwrap()).collect::<Vec<_>>();
+//!     let response = request("GET /problems HTTP/1.1\r\n\r\n");
+//!     assert!(response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+//!     drop(idle);
+//! }
+//! # #[cfg(not(feature = "euler_p001_010"))]
+//! # fn main() {}
+//! ```
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use serde_json::Value;
+
+use euler_library::solution::{self, Implementation, Solution};
+use output::{self, Summary};
+use registry::Registry;
+use selection;
+
+/// Time a client has to send each read of its request before the connection is dropped.
+pub const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest request line or header line read, in bytes, newline included; longer ones are refused.
+pub const MAX_LINE: usize = 8 * 1024;
+
+/// Most header lines read from a request; requests with more are refused.
+pub const MAX_HEADERS: usize = 100;
+
+/// Number of connections answered at a time; further clients are refused until a thread is free.
+pub const MAX_CONNECTIONS: usize = 8;
+
+/// Number of refused connections waiting for their `503` answer; further ones are closed unanswered.
+pub const MAX_REFUSED: usize = 8;
+
+/// HTTP server bound to a port of `127.0.0.1`.
+#[derive(Debug)]
+pub struct Server {
+  listener: TcpListener,
+  registry: Arc<Registry>,
+  timeout: Duration,
+}
+
+impl Server {
+  /// Time after which a running solution is reported as timed out unless `serve --timeout` says otherwise.
+  pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+  /// Returns a server of `solutions` listening on `port`, any free one for 0, reporting solutions running
+  /// longer than `timeout` as timed out.
+  pub fn bind(port: u16, solutions: Vec<Solution>, timeout: Duration) -> io::Result<Server> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    Ok(Server { listener, registry: Arc::new(Registry::new(solutions)), timeout })
+  }
+
+  /// Returns the address the server listens on.
+  pub fn local_addr(&self) -> io::Result<SocketAddr> {
+    self.listener.local_addr()
+  }
+
+  /// Answers requests until the listener fails, on a pool of [`MAX_CONNECTIONS`](constant.MAX_CONNECTIONS.html)
+  /// threads answering one connection each at a time, and one more thread refusing the connections arriving
+  /// while they are all busy.
+  pub fn run(self) -> io::Result<()> {
+    // connections being answered or waiting for a thread, only ever raised by this thread so never above the
+    // size of the pool, which the channel then always has room for
+    let busy = Arc::new(AtomicUsize::new(0));
+    let (tx, rx) = mpsc::sync_channel::<TcpStream>(MAX_CONNECTIONS);
+    let rx = Arc::new(Mutex::new(rx));
+    for _ in 0..MAX_CONNECTIONS {
+      let (rx, busy, registry, timeout) = (rx.clone(), busy.clone(), self.registry.clone(), self.timeout);
+      thread::spawn(move || loop {
+        let stream = match rx.lock().unwrap().recv() {
+          Ok(stream) => stream,
+          Err(_) => return,
+        };
+        if let Err(e) = handle(stream, &registry, timeout) {
+          eprintln!("serve: {}", e);
+        }
+        busy.fetch_sub(1, Ordering::SeqCst);
+      });
+    }
+    let (refuse, refused) = mpsc::sync_channel::<TcpStream>(MAX_REFUSED);
+    thread::spawn(move || {
+      for stream in refused {
+        if let Err(e) = refuse_busy(stream) {
+          eprintln!("serve: {}", e);
+        }
+      }
+    });
+    for stream in self.listener.incoming() {
+      let stream = stream?;
+      if busy.load(Ordering::SeqCst) < MAX_CONNECTIONS {
+        busy.fetch_add(1, Ordering::SeqCst);
+        if tx.send(stream).is_err() {
+          break;
+        }
+      } else {
+        // dropping the connection when even the refusing thread is behind keeps the backlog bounded
+        let _ = refuse.try_send(stream);
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Returns a solution as a JSON value, as listed by `GET /problems`.
+pub fn solution_to_json(s: &Solution) -> Value {
+  let params = s
+    .params
+    .iter()
+    .map(|p| json!({ "name": p.name, "default": p.default, "min": p.min, "max": p.max, "help": p.help }))
+    .collect::<Vec<_>>();
+  json!({
+    "id": s.id(),
+    "problem": s.problem,
+    "variant": s.variant,
+    "title": s.title,
+    "crate": s.crate_name,
+    "implementation": s.implementation.to_string(),
+    "params": params,
+    "data": s.data,
+    "examples": s.examples.is_some(),
+    "stochastic": s.func_seeded.is_some(),
+  })
+}
+
+// Request line of an HTTP request, its query string decoded into name and value pairs
+struct Request {
+  method: String,
+  path: String,
+  query: Vec<(String, String)>,
+}
+
+// Reads the request line and headers, discarding any body; fails with the status to answer
+fn read_request(reader: &mut BufReader<TcpStream>) -> Result<Request, (u16, String)> {
+  let bad = |e: io::Error| (400, e.to_string());
+  let mut line = String::new();
+  if !read_line(reader, &mut line).map_err(bad)? {
+    return Err((414, format!("request line longer than {} bytes", MAX_LINE)));
+  }
+  let mut parts = line.split_whitespace();
+  let (method, target) = match (parts.next(), parts.next()) {
+    (Some(method), Some(target)) => (method.to_string(), target.to_string()),
+    _ => return Err((400, format!("invalid request line: {:?}", line.trim_end()))),
+  };
+  let mut length = 0;
+  let mut headers = 0;
+  loop {
+    let mut header = String::new();
+    if !read_line(reader, &mut header).map_err(bad)? {
+      return Err((431, format!("header line longer than {} bytes", MAX_LINE)));
+    }
+    if header.trim_end().is_empty() {
+      break;
+    }
+    headers += 1;
+    if headers > MAX_HEADERS {
+      return Err((431, format!("more than {} header lines", MAX_HEADERS)));
+    }
+    if let Some((name, value)) = header.split_once(':') {
+      if name.trim().eq_ignore_ascii_case("content-length") {
+        length = value.trim().parse().map_err(|_| (400, format!("invalid Content-Length: {}", value.trim())))?;
+      }
+    }
+  }
+  io::copy(&mut reader.by_ref().take(length), &mut io::sink()).map_err(bad)?;
+
+  let (path, query) = match target.split_once('?') {
+    Some((path, query)) => (path.to_string(), query),
+    None => (target.clone(), ""),
+  };
+  let query = query
+    .split('&')
+    .filter(|x| !x.is_empty())
+    .map(|x| match x.split_once('=') {
+      Some((name, value)) => (decode(name), decode(value)),
+      None => (decode(x), String::new()),
+    })
+    .collect();
+  Ok(Request { method, path, query })
+}
+
+// Reads a line of at most `MAX_LINE` bytes into `line`, returning false when it is longer
+fn read_line(reader: &mut BufReader<TcpStream>, line: &mut String) -> io::Result<bool> {
+  let n = reader.by_ref().take(MAX_LINE as u64).read_line(line)?;
+  Ok(n < MAX_LINE || line.ends_with('\n'))
+}
+
+// Decodes the `%XX` escapes and `+` of a query string component
+fn decode(s: &str) -> String {
+  let bytes = s.as_bytes();
+  let hex = |i: usize| bytes.get(i..i + 2).and_then(|x| u8::from_str_radix(std::str::from_utf8(x).ok()?, 16).ok());
+  let mut xs = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    match (bytes[i], hex(i + 1)) {
+      (b'%', Some(b)) => {
+        xs.push(b);
+        i += 2;
+      }
+      (b'+', _) => xs.push(b' '),
+      (b, _) => xs.push(b),
+    }
+    i += 1;
+  }
+  String::from_utf8_lossy(&xs).into_owned()
+}
+
+// Answers `503` to a connection arriving while every thread of the pool is busy. The request is read first,
+// closing a socket with unread data would reset the connection before the client reads the answer
+fn refuse_busy(stream: TcpStream) -> io::Result<()> {
+  stream.set_read_timeout(Some(READ_TIMEOUT))?;
+  let mut reader = BufReader::new(stream.try_clone()?);
+  let mut stream = stream;
+  let _ = read_request(&mut reader);
+  let error = format!("all {} connections are busy, retry later", MAX_CONNECTIONS);
+  respond(&mut stream, 503, &json!({ "error": error }))
+}
+
+fn handle(stream: TcpStream, registry: &Registry, timeout: Duration) -> io::Result<()> {
+  // a client that never finishes its request would otherwise hold its thread forever
+  stream.set_read_timeout(Some(READ_TIMEOUT))?;
+  let mut reader = BufReader::new(stream.try_clone()?);
+  let mut stream = stream;
+  let request = match read_request(&mut reader) {
+    Ok(r) => r,
+    Err((status, e)) => return respond(&mut stream, status, &json!({ "error": e })),
+  };
+  let segments = request.path.trim_matches('/').split('/').collect::<Vec<_>>();
+  match (request.method.as_str(), segments.as_slice()) {
+    ("GET", ["problems"]) => {
+      let xs = registry.solutions().iter().map(solution_to_json).collect::<Vec<_>>();
+      respond(&mut stream, 200, &Value::Array(xs))
+    }
+    ("GET", ["problems", id]) => match registry.get(id) {
+      Some(s) => respond(&mut stream, 200, &solution_to_json(s)),
+      None => not_found(&mut stream, id),
+    },
+    ("POST", ["problems", id, "run"]) => match registry.get(id) {
+      Some(s) => match configured(s, &request.query) {
+        Ok(s) => respond(&mut stream, 200, &output::result_to_json(&::run_one(&s, Some(timeout)))),
+        Err(e) => respond(&mut stream, 400, &json!({ "error": e })),
+      },
+      None => not_found(&mut stream, id),
+    },
+    ("GET", ["run"]) => {
+      let selected = match request.query.iter().find(|&(n, _)| n == "selection") {
+        Some((_, expr)) => selection::select(registry, expr).map_err(|e| e.to_string()),
+        None => Ok(registry.main_solutions()),
+      };
+      match selected {
+        Ok(xs) => stream_run(&mut stream, xs, timeout),
+        Err(e) => respond(&mut stream, 400, &json!({ "error": e })),
+      }
+    }
+    (_, ["problems"]) | (_, ["problems", _]) | (_, ["problems", _, "run"]) | (_, ["run"]) => {
+      respond(&mut stream, 405, &json!({ "error": format!("{} is not allowed on {}", request.method, request.path) }))
+    }
+    _ => respond(&mut stream, 404, &json!({ "error": format!("no such endpoint: {}", request.path) })),
+  }
+}
+
+// Returns the solution with the parameter values and seed of a run request, refusing unimplemented ones
+fn configured(solution: &Solution, query: &[(String, String)]) -> Result<Solution, String> {
+  if solution.implementation == Implementation::Unimplemented {
+    return Err(format!("{} is unimplemented", solution.id()));
+  }
+  let mut args = Vec::new();
+  let mut seed = None;
+  for (name, value) in query {
+    let number = solution::parse_param_value(value).map_err(|e| format!("invalid value for {}: {}", name, e))?;
+    match name.as_str() {
+      "seed" => seed = Some(number),
+      _ => args.push((name.clone(), number)),
+    }
+  }
+  let solution = solution.with_args(&args)?;
+  match seed {
+    Some(seed) => solution.with_seed(seed),
+    None => Ok(solution),
+  }
+}
+
+// Runs `solutions` on rayon's global pool, writing each result as an event while they complete. A failed
+// write ends the stream; dropping the receiver then stops the pool from starting the solutions left
+fn stream_run(stream: &mut TcpStream, solutions: Vec<Solution>, timeout: Duration) -> io::Result<()> {
+  let solutions = solutions.into_iter().filter(|s| s.implementation != Implementation::Unimplemented).collect();
+  write!(
+    stream,
+    "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n"
+  )?;
+  let instant = Instant::now();
+  let (tx, rx) = mpsc::channel();
+  thread::spawn(move || {
+    let solutions: Vec<Solution> = solutions;
+    let _ = solutions.par_iter().try_for_each_with(tx, |tx, s| tx.send(::run_one(s, Some(timeout))).map_err(|_| ()));
+  });
+  let mut results = Vec::new();
+  for res in rx {
+    event(stream, "result", &output::result_to_json(&res))?;
+    results.push(res);
+  }
+  let mut summary = Summary::new(results, solution::seconds(instant.elapsed()), rayon::current_num_threads()).to_json();
+  summary.as_object_mut().unwrap().shift_remove("results");
+  event(stream, "summary", &summary)
+}
+
+fn event(stream: &mut TcpStream, name: &str, data: &Value) -> io::Result<()> {
+  write!(stream, "event: {}\ndata: {}\n\n", name, data)?;
+  stream.flush()
+}
+
+fn not_found(stream: &mut TcpStream, id: &str) -> io::Result<()> {
+  respond(stream, 404, &json!({ "error": format!("no registered solution matches {}", id) }))
+}
+
+fn respond(stream: &mut TcpStream, status: u16, body: &Value) -> io::Result<()> {
+  let reason = match status {
+    200 => "OK",
+    400 => "Bad Request",
+    404 => "Not Found",
+    405 => "Method Not Allowed",
+    414 => "URI Too Long",
+    431 => "Request Header Fields Too Large",
+    503 => "Service Unavailable",
+    _ if status < 500 => "Client Error",
+    _ => "Internal Server Error",
+  };
+  let body = body.to_string();
+  write!(
+    stream,
+    "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    status,
+    reason,
+    body.len(),
+    body
+  )?;
+  stream.flush()
+}
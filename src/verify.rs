@@ -0,0 +1,194 @@
+//! Spoiler-safe verification of answers.
+//!
+//! Known answers are kept in a manifest of salted SHA-256 hashes, `answers.json`, which is compiled into
+//! the crate; the solutions themselves contain no answers. The hash of problem `p001` with answer `42`
+//! under salt `s` is the hex digest of `s:p001:42`. Variants are checked against their problem's entry.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate euler_rust;
+//!
+//! use euler_rust::verify::{Manifest, Verdict};
+//! use euler_rust::Answer;
+//!
+//! # #[cfg(feature = "euler_p011_020")]
+//! fn main() {
+//!     let manifest = Manifest::embedded();
+//!     assert_eq!(manifest.check(16, &euler_rust::euler_p011_020::p016()), Verdict::Correct);
+//!     assert_eq!(manifest.check(16, &Answer::from(16)), Verdict::Wrong);
+//!     assert_eq!(manifest.check(999, &Answer::from(16)), Verdict::Unknown);
+//!
+//!     let mut manifest = Manifest::new("pepper");
+//!     manifest.insert(1, &Answer::from(42));
+//!     assert_eq!(manifest.check(1, &Answer::from(42)), Verdict::Correct);
+//! }
+//! # #[cfg(not(feature = "euler_p011_020"))]
+//! # fn main() {}
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+
+use euler_library::solution::{self, Answer, SolutionResult};
+use output::SCHEMA_VERSION;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Outcome of checking one answer against the manifest.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+  Correct,
+  Wrong,
+  /// The manifest has no entry for the problem, or the solution produced no answer to the Euler problem.
+  Unknown,
+}
+
+impl fmt::Display for Verdict {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let s = match *self {
+      Verdict::Correct => "correct",
+      Verdict::Wrong => "wrong",
+      Verdict::Unknown => "unknown",
+    };
+    f.pad(s)
+  }
+}
+
+/// Salted hashes of known answers, keyed by problem id.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Manifest {
+  pub salt: String,
+  pub hashes: BTreeMap<String, String>,
+}
+
+impl Manifest {
+  /// Returns an empty manifest hashing with `salt`.
+  pub fn new(salt: &str) -> Manifest {
+    Manifest { salt: salt.to_string(), hashes: BTreeMap::new() }
+  }
+
+  /// Returns the manifest compiled into the crate.
+  pub fn embedded() -> Manifest {
+    Manifest::parse(include_str!("../answers.json"), "answers.json").unwrap()
+  }
+
+  /// Reads a manifest written by `save`.
+  pub fn load(path: &str) -> Result<Manifest, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("cannot read answers {}: {}", path, e))?;
+    Manifest::parse(&text, path)
+  }
+
+  fn parse(text: &str, path: &str) -> Result<Manifest, String> {
+    let value: Value = serde_json::from_str(text).map_err(|e| format!("invalid answers {}: {}", path, e))?;
+    if value["algorithm"] != "sha256" {
+      return Err(format!("invalid answers {}: algorithm must be sha256", path));
+    }
+    let salt = value["salt"].as_str().ok_or(format!("invalid answers {}: missing salt", path))?;
+    let hashes = value["answers"]
+      .as_object()
+      .ok_or(format!("invalid answers {}: missing answers", path))?
+      .iter()
+      .filter_map(|(id, h)| h.as_str().map(|h| (id.clone(), h.to_string())))
+      .collect();
+    Ok(Manifest { salt: salt.to_string(), hashes })
+  }
+
+  /// Writes the manifest to `path` as JSON.
+  pub fn save(&self, path: &str) -> Result<(), String> {
+    let value = json!({
+      "schema_version": SCHEMA_VERSION,
+      "algorithm": "sha256",
+      "salt": self.salt,
+      "answers": self.hashes,
+    });
+    fs::write(path, serde_json::to_string_pretty(&value).unwrap() + "\n")
+      .map_err(|e| format!("cannot write answers {}: {}", path, e))
+  }
+
+  /// Records `answer` as the answer to `problem`.
+  pub fn insert(&mut self, problem: u32, answer: &Answer) {
+    self.hashes.insert(solution::format_id(problem, None), hash(&self.salt, problem, answer));
+  }
+
+  /// Checks `answer` against the recorded answer to `problem`.
+  pub fn check(&self, problem: u32, answer: &Answer) -> Verdict {
+    match self.hashes.get(&solution::format_id(problem, None)) {
+      Some(h) if *h == hash(&self.salt, problem, answer) => Verdict::Correct,
+      Some(_) => Verdict::Wrong,
+      None => Verdict::Unknown,
+    }
+  }
+
+  /// Checks the answer of a result; results without an answer or computed for other inputs are
+  /// `Unknown`.
+  pub fn verify(&self, result: &SolutionResult) -> Verdict {
+    match result.answer {
+      Some(ref a) if !result.has_custom_input() => self.check(result.problem, a),
+      _ => Verdict::Unknown,
+    }
+  }
+
+  /// Checks every result, keeping their order.
+  pub fn verify_all<'a, I>(&self, results: I) -> Verification
+  where
+    I: IntoIterator<Item = &'a SolutionResult>,
+  {
+    Verification { verdicts: results.into_iter().map(|r| (r.id(), self.verify(r))).collect() }
+  }
+}
+
+/// Returns the hex encoded salted hash of an answer.
+pub fn hash(salt: &str, problem: u32, answer: &Answer) -> String {
+  let digest = Sha256::digest(format!("{}:{}:{}", salt, solution::format_id(problem, None), answer).as_bytes());
+  digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Verdicts of a run, as `(solution id, verdict)` pairs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verification {
+  pub verdicts: Vec<(String, Verdict)>,
+}
+
+impl Verification {
+  /// Returns the number of verdicts equal to `verdict`.
+  pub fn count(&self, verdict: Verdict) -> usize {
+    self.verdicts.iter().filter(|&&(_, v)| v == verdict).count()
+  }
+
+  /// Returns the verification as a JSON value.
+  pub fn to_json(&self) -> Value {
+    let verdicts = self.verdicts.iter().map(|&(ref id, v)| (id.clone(), json!(v.to_string()))).collect();
+    json!({
+      "correct": self.count(Verdict::Correct),
+      "wrong": self.count(Verdict::Wrong),
+      "unknown": self.count(Verdict::Unknown),
+      "verdicts": Value::Object(verdicts),
+    })
+  }
+
+  /// Returns the verification as text, listing wrong and unknown answers only.
+  pub fn to_text(&self) -> String {
+    let mut s = format!(
+      "\nverified {} answers: {} correct, {} wrong, {} unknown\n",
+      self.verdicts.len(),
+      self.count(Verdict::Correct),
+      self.count(Verdict::Wrong),
+      self.count(Verdict::Unknown)
+    );
+    for &(ref id, v) in self.verdicts.iter().filter(|&&(_, v)| v != Verdict::Correct) {
+      s += &format!("  {:7} {}\n", id, v);
+    }
+    s
+  }
+
+  /// Returns the verdicts as CSV comment lines.
+  pub fn to_csv_comments(&self) -> String {
+    let mut s = String::new();
+    for &(ref id, v) in &self.verdicts {
+      s += &format!("# verdict: {},{}\n", id, v);
+    }
+    s
+  }
+}